@@ -0,0 +1,50 @@
+use std::env;
+use std::fs;
+use std::process;
+use std::time::Duration;
+
+use botv1::BotV1;
+use fenlib::Fen;
+use fenlib::bot::{Bot, SearchInfo, SearchLimits};
+use fenlib::epd::{self, Epd, SuiteReport};
+
+/*
+Runs an EPD test suite (e.g. WAC, ECM or STS) against BotV1 and reports how many positions it solves.
+
+Usage: cargo run --release --bin epd -- <file.epd> [miliseconds per position]
+*/
+
+const DEFAULT_TIME_MILI: u64 = 1000;
+
+fn main() {
+    let args: Vec<String> = env::args().collect();
+    if args.len() < 2 {
+        eprintln!("usage: {} <file.epd> [miliseconds per position]", args[0]);
+        process::exit(2);
+    }
+
+    let text: String = fs::read_to_string(&args[1]).unwrap_or_else(|err| {
+        eprintln!("could not read {}: {}", args[1], err);
+        process::exit(1);
+    });
+
+    let time_mili: u64 = match args.get(2) {
+        Some(time) => time.parse().unwrap_or_else(|_| {
+            eprintln!("invalid time '{}'", time);
+            process::exit(2);
+        }),
+        None => DEFAULT_TIME_MILI,
+    };
+
+    let positions: Vec<Epd> = epd::read_epd(&text).unwrap_or_else(|err| {
+        eprintln!("{}", err);
+        process::exit(1);
+    });
+
+    let report: SuiteReport = epd::run_suite(&positions, Duration::from_millis(time_mili), |fen: &Fen, time: Duration| {
+        let mut bot: BotV1 = BotV1::from_position(fen.clone());
+        bot.choose_move(&SearchLimits::movetime(time), &mut |_: &SearchInfo| {}).best_move
+    });
+
+    println!("{}", report);
+}
@@ -1,207 +1,625 @@
 use fenlib::*;
-use std::time::Instant;
-use std::time::Duration;
+use fenlib::bot::{Bot, BotOption, MATE_SCORE, MAX_PLY, OptionError, SearchInfo, SearchLimits, SearchResult};
+use fenlib::chess_move::Move;
+use fenlib::error::FenError;
+use fenlib::movegen::MoveList;
+use fenlib::ordering::{self, MoveOrdering, MovePicker, Stage};
+use fenlib::piece::{Color, Piece, PieceKind};
+use fenlib::see;
+use fenlib::time_manager::TimeManager;
+use fenlib::transposition::{self, Bound, Entry, TranspositionTable};
+use fenlib::undo::Undo;
+use fenlib::utils::{BISHOP_PROM, KNIGHT_PROM, QUEEN_PROM, ROOK_PROM};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, LazyLock};
 
 /*
 BotV1 implements:
 
-- minimax algorithm to find the best move
+- negamax search with alpha-beta pruning to find the best move
+- signed centipawn scores from the view of the side to move, with mates scored by their distance
+- iterative deepening that searches the principal variation of the previous depth first
+- a transposition table that cuts off positions searched before and orders their best move first
+- move ordering by captures (MVV-LVA), killer moves, countermoves and the history of quiet moves
 - eval function based on material score
-- a dynamic tree structure for the moves tree
-- time constraint for searching tree
+- principal variation search: every move after the first is searched with a null window first
+- selective search: null move pruning, late move reductions, reverse futility and futility pruning, razoring,
+  late move pruning and check extensions, each can be switched off with a bot option
+- a quiescence search at the leaves that plays out captures and promotions, with delta pruning and without captures that lose material by static exchange evaluation
+- a time manager that spends the clock over the game, and depth and node limits and a stop flag to end the search early
+- the Bot trait from fenlib, so it can be driven by the match runners, protocols and Python
 
 */
 
-// Values from https://www.chessprogramming.org/Simplified_Evaluation_Function
-const PAWN_VAL: u32 = 100;
-const KNIGHT_VAL: u32 = 320;
-const BISHOP_VAL: u32 = 330;
-const ROOK_VAL: u32 = 500;
-const QUEEN_VAL: u32 = 900;
-const KING_VAL: u32 = 20000;
+/// A score beyond every real score, used as the initial bounds of the search.
+const INFINITY: i32 = MATE_SCORE + 1;
 
-const EQUAL: u32 = 0x80000000;
+/// A capture in the quiescence search is skipped if winning the captured piece and this much more
+/// would still not raise alpha, the margin covers the positional gains the evaluation might see.
+const DELTA_MARGIN: i32 = 200;
 
-const INFINITY: u32 = u32::max_value();
-const MAX_TIME_MILI: u64 = 0x3E8; //1000 miliseconds per move
+/// Null move pruning searches the position after passing this many plies less deep, and one more every 6 plies of depth.
+const NULL_MOVE_REDUCTION: u32 = 3;
+const NULL_MOVE_MIN_DEPTH: u32 = 3;
 
-pub fn eval(boards: &[u64; 12]) -> u32 {
-    let mut score: u32 = EQUAL;
+/// Reverse futility pruning returns the static evaluation if it beats beta by this much per ply of depth.
+const REVERSE_FUTILITY_MARGIN: i32 = 100;
+const REVERSE_FUTILITY_MAX_DEPTH: u32 = 6;
 
-    score += PAWN_VAL * boards[0].count_ones();
-    score -= PAWN_VAL * boards[6].count_ones();
+/// Futility pruning skips the quiet moves if the static evaluation plus this much per ply of depth does not reach alpha.
+const FUTILITY_MARGIN: i32 = 150;
+const FUTILITY_MAX_DEPTH: u32 = 3;
 
-    score += KNIGHT_VAL * boards[1].count_ones();
-    score -= KNIGHT_VAL * boards[7].count_ones();
+/// Razoring drops into the quiescence search if the static evaluation plus this much per ply of depth does not reach alpha.
+const RAZOR_MARGIN: i32 = 300;
+const RAZOR_MAX_DEPTH: u32 = 2;
 
-    score += BISHOP_VAL * boards[2].count_ones();
-    score -= BISHOP_VAL * boards[8].count_ones();
+/// Late move pruning skips the quiet moves after the first `3 + depth * depth` moves.
+const LATE_MOVE_PRUNING_MAX_DEPTH: u32 = 4;
 
-    score += ROOK_VAL * boards[3].count_ones();
-    score -= ROOK_VAL * boards[9].count_ones();
+/// Late move reductions start with this move, the first moves are searched at full depth.
+const LATE_MOVE_REDUCTION_MIN_MOVES: usize = 3;
+const LATE_MOVE_REDUCTION_MIN_DEPTH: u32 = 3;
 
-    score += QUEEN_VAL * boards[4].count_ones();
-    score -= QUEEN_VAL * boards[10].count_ones();
+/// The reduction of a late move by depth and move number, `0.75 + ln(depth) * ln(moves) / 2.25`
+/// (https://www.chessprogramming.org/Late_Move_Reductions).
+static REDUCTIONS: LazyLock<[[u32; 64]; 64]> = LazyLock::new(|| {
+    let mut reductions: [[u32; 64]; 64] = [[0; 64]; 64];
+    for (depth, row) in reductions.iter_mut().enumerate().skip(1) {
+        for (moves, reduction) in row.iter_mut().enumerate().skip(1) {
+            *reduction = (0.75 + (depth as f64).ln() * (moves as f64).ln() / 2.25) as u32;
+        }
+    }
+    reductions
+});
 
-    score += KING_VAL * boards[5].count_ones();
-    score -= KING_VAL * boards[11].count_ones();
+/// The selective search techniques of BotV1, they search the promising moves deeper by searching the others
+/// less deep or not at all. Each one is a bot option, so its strength can be measured by playing without it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Selectivity {
+    /// Pass the turn, if the position is still good enough for a cutoff the real moves will be too.
+    /// Not used in check, right after another pass or without pieces other than pawns, where passing would
+    /// be better than any move (zugzwang).
+    pub null_move: bool,
 
-    score
+    /// Search the late quiet moves less deep, and again at full depth if they turn out better than expected.
+    pub late_move_reductions: bool,
+
+    /// Cut off when the static evaluation is far above beta near the leaves.
+    pub reverse_futility: bool,
+
+    /// Skip the quiet moves when the static evaluation is far below alpha near the leaves.
+    pub futility: bool,
+
+    /// Only search the captures when the static evaluation is far below alpha right before the leaves.
+    pub razoring: bool,
+
+    /// Skip the late quiet moves near the leaves.
+    pub late_move_pruning: bool,
+
+    /// Search one ply deeper after a move that gives check.
+    pub check_extensions: bool,
 }
 
-pub fn get_worst_eval(white_to_move: bool) -> u32 {
-    match white_to_move {
-        true => 0x0,
-        false => INFINITY
+impl Selectivity {
+    /// Every technique switched on, the default.
+    pub const ALL: Selectivity = Selectivity {
+        null_move: true,
+        late_move_reductions: true,
+        reverse_futility: true,
+        futility: true,
+        razoring: true,
+        late_move_pruning: true,
+        check_extensions: true,
+    };
+
+    /// A full width alpha-beta search.
+    pub const NONE: Selectivity = Selectivity {
+        null_move: false,
+        late_move_reductions: false,
+        reverse_futility: false,
+        futility: false,
+        razoring: false,
+        late_move_pruning: false,
+        check_extensions: false,
+    };
+
+    /// The names of the bot options that switch the techniques on (1) and off (0).
+    pub const OPTIONS: [&'static str; 7] = ["NullMove", "LMR", "ReverseFutility", "Futility", "Razoring", "LMP", "CheckExtensions"];
+
+    /// Returns the technique switched by the bot option, `None` for an unknown name.
+    fn option_mut(&mut self, name: &str) -> Option<&mut bool> {
+        match name.to_ascii_lowercase().as_str() {
+            "nullmove" => Some(&mut self.null_move),
+            "lmr" => Some(&mut self.late_move_reductions),
+            "reversefutility" => Some(&mut self.reverse_futility),
+            "futility" => Some(&mut self.futility),
+            "razoring" => Some(&mut self.razoring),
+            "lmp" => Some(&mut self.late_move_pruning),
+            "checkextensions" => Some(&mut self.check_extensions),
+            _ => None,
+        }
     }
 }
 
-pub fn get_better_move(white_to_move: bool, old: u32, new: u32) -> u32 {
-    let new_is_greater: bool;
-    if new > old {
-        new_is_greater = true
-    } else {
-        new_is_greater = false
+impl Default for Selectivity {
+    fn default() -> Self {
+        Self::ALL
     }
+}
 
-    if (white_to_move && new_is_greater) || (!white_to_move && !new_is_greater) {
-        return new;
-    } else {
-        return old;
+/// The size of the transposition table in MB, see `BotV1::set_option`.
+const HASH_OPTION: BotOption = BotOption { name: "Hash", default: transposition::DEFAULT_HASH_MB as i64, min: 1, max: 1024 };
+
+/// Returns the value of the piece kind in centipawns, the same values the static exchange evaluation uses.
+pub fn piece_value(kind: PieceKind) -> i32 {
+    see::see_value(kind)
+}
+
+/// Returns the material balance in centipawns from the view of white.
+pub fn eval(fen: &Fen) -> i32 {
+    let mut score: i32 = 0;
+
+    for kind in PieceKind::ALL {
+        score += piece_value(kind) * fen.pieces(Piece::new(Color::White, kind)).popcount() as i32;
+        score -= piece_value(kind) * fen.pieces(Piece::new(Color::Black, kind)).popcount() as i32;
     }
+
+    score
 }
 
-pub struct Move {
-    move1: [u64; 3],
-    fen: Fen,
-    eval: u32,
-    index: usize,
-    new_moves: Vec<[u64; 3]>,
-    children: Vec<Box<Move>>,
-    child_count: usize,
+/// Returns the material balance in centipawns from the view of the side to move, as negamax needs it.
+pub fn relative_eval(fen: &Fen) -> i32 {
+    if fen.white_to_move { eval(fen) } else { -eval(fen) }
 }
 
-impl Move {
-    pub fn new(move1: [u64; 3], old_fen: &Fen) -> Self {
+#[derive(Debug)]
+pub struct BotV1 {
+    fen: Fen,
+    nodes: u64,
+    stop: Arc<AtomicBool>,
+
+    /// Kept between searches, so the next move starts with what was found for the last one.
+    tt: TranspositionTable,
+    ordering: MoveOrdering,
 
-        // cloning fen seems to take 0-100 ns, this is a built-in Rust function, so there is little we can do to optimise this
-        let mut fen: Fen = old_fen.clone();
+    /// The moves from the root of the search to the current position, `Move::NULL` for a null move.
+    line: Vec<Move>,
 
-        // move to fen seems to take 100-200 ns, we could speed this up even furthur, but it is quite optimised already
-        fen.move_to_fen(&move1);
-        
-        // getting possible moves seems to take 45-55 us, we could speed this up by precomputing attacked and defended squares, and perhaps magic bitboards
-        let new_moves: Vec<[u64; 3]> = fen.get_all_possible_moves();
+    selectivity: Selectivity,
 
-        // getting the eval seems to take 0-100 ns, though this is still a very primitive function so time could increase in future versions
-        let eval: u32 = eval(&fen.boards);
+    /// The depth of the current iteration, check extensions stop at twice this ply.
+    root_depth: u32,
+}
 
-        let child_count: usize = new_moves.len();
+impl Default for BotV1 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl BotV1 {
+    pub fn new() -> Self {
+        Self::from_position(Fen::new())
+    }
+
+    pub fn from_fen(fen_str: &str) -> Result<Self, FenError> {
+        let fen: Fen = fen_str.parse()?;
+
+        Ok(Self::from_position(fen))
+    }
 
+    pub fn from_position(fen: Fen) -> Self {
         Self {
-            move1,
             fen,
-            eval,
-            index: 0,
-            new_moves,
-            children: Vec::new(),
-            child_count,
+            nodes: 0,
+            stop: Arc::new(AtomicBool::new(false)),
+            tt: TranspositionTable::default(),
+            ordering: MoveOrdering::new(),
+            line: Vec::new(),
+            selectivity: Selectivity::ALL,
+            root_depth: 0,
         }
     }
 
-    pub fn add_child(&mut self) {
-        let new_child: Box<Move> = Box::new(Self::new(self.new_moves[self.index], &self.fen));
-        self.children.push(new_child);
-        self.index += 1;
+    pub fn selectivity(&self) -> Selectivity {
+        self.selectivity
     }
-}
 
-#[derive(Debug, Clone)]
-pub struct BotV1 {
-    fen: Fen,
-}
+    /// Switches the selective search techniques on and off, like the bot options do.
+    pub fn set_selectivity(&mut self, selectivity: Selectivity) {
+        self.selectivity = selectivity;
+    }
 
-impl BotV1 {
-    pub fn new() -> Self {
-        let fen: Fen = Fen::new();
+    /// Returns what the search learned about ordering the moves, with how well it did in the last search.
+    pub fn ordering(&self) -> &MoveOrdering {
+        &self.ordering
+    }
 
-        Self { fen, }
+    /// Searches the position `depth` plies deep and returns its score from the view of the side to move,
+    /// or `None` if the search was aborted by the time manager or the stop flag.
+    ///
+    /// Scores at or below `alpha` and at or above `beta` are bounds instead of exact scores.
+    /// `pv` is filled with the best line found, if a move scored above `alpha`.
+    ///
+    /// **NOTE:** an exact score from the transposition table inside the window is searched again anyway,
+    /// so the principal variation does not end early
+    pub fn negamax(&mut self, depth: u32, ply: u32, mut alpha: i32, mut beta: i32, time_manager: &TimeManager, pv: &mut Vec<Move>) -> Option<i32> {
+        pv.clear();
+        if depth == 0 || ply >= MAX_PLY {
+            return self.quiescence(ply, alpha, beta, time_manager)
+        }
+
+        if time_manager.is_out_of_time(self.nodes) || self.stop.load(Ordering::Relaxed) {
+            return None
+        }
+
+        self.nodes += 1;
+
+        let entry: Option<Entry> = self.tt.probe(self.fen.hash());
+        if ply > 0
+            && let Some(entry) = entry
+            && let Some(score) = entry.cutoff(depth, ply, alpha, beta)
+            && (entry.bound() != Bound::Exact || score <= alpha || score >= beta)
+        {
+            return Some(score)
+        }
+
+        let moves: MoveList = self.fen.legal_moves();
+        if moves.is_empty() {
+            // being mated sooner is worse, so the search prefers the fastest mate and the slowest defeat
+            return Some(if self.fen.in_check() { ply as i32 - MATE_SCORE } else { 0 })
+        }
+
+        // no line from here can do better than mating on the next move, or worse than being mated right now
+        alpha = alpha.max(ply as i32 - MATE_SCORE);
+        beta = beta.min(MATE_SCORE - ply as i32 - 1);
+        if alpha >= beta {
+            return Some(alpha)
+        }
+
+        let pv_node: bool = beta - alpha > 1;
+        let in_check: bool = self.fen.in_check();
+        let static_eval: i32 = relative_eval(&self.fen);
+        let previous: Move = self.line.last().copied().unwrap_or(Move::NULL);
+        let selectivity: Selectivity = self.selectivity;
+
+        // the pruning below relies on the static evaluation, which means nothing in check or when a mate is near
+        let prunable: bool = !pv_node && !in_check && alpha.abs() < MATE_SCORE - MAX_PLY as i32 && beta.abs() < MATE_SCORE - MAX_PLY as i32;
+
+        if prunable && selectivity.reverse_futility && depth <= REVERSE_FUTILITY_MAX_DEPTH && static_eval - REVERSE_FUTILITY_MARGIN * depth as i32 >= beta {
+            return Some(static_eval)
+        }
+
+        if prunable && selectivity.razoring && depth <= RAZOR_MAX_DEPTH && static_eval + RAZOR_MARGIN * (depth as i32) < alpha {
+            let score: i32 = self.quiescence(ply, alpha, alpha + 1, time_manager)?;
+            if score <= alpha {
+                return Some(score)
+            }
+        }
+
+        if prunable
+            && selectivity.null_move
+            && depth >= NULL_MOVE_MIN_DEPTH
+            && ply > 0
+            && !previous.is_null()
+            && static_eval >= beta
+            && self.has_pieces(self.fen.side_to_move())
+        {
+            let reduction: u32 = NULL_MOVE_REDUCTION + depth / 6;
+            let undo: Undo = self.fen.make_null_move();
+            self.line.push(Move::NULL);
+            let score: Option<i32> = self.negamax(depth.saturating_sub(1 + reduction), ply + 1, -beta, -beta + 1, time_manager, &mut Vec::new());
+            self.line.pop();
+            self.fen.unmake_null_move(undo);
+
+            // a mate found after passing is not a real mate
+            let score: i32 = -score?;
+            if score >= beta {
+                return Some(if score >= MATE_SCORE - MAX_PLY as i32 { beta } else { score })
+            }
+        }
+
+        let futile: bool = prunable && selectivity.futility && depth <= FUTILITY_MAX_DEPTH && static_eval + FUTILITY_MARGIN * (depth as i32) <= alpha;
+        let late_move_limit: usize = if prunable && selectivity.late_move_pruning && depth <= LATE_MOVE_PRUNING_MAX_DEPTH {
+            3 + (depth * depth) as usize
+        } else {
+            usize::MAX
+        };
+
+        // the best move found the last time the position was searched, e.g. in the previous depth, is likely to be good again
+        let tt_move: Move = entry.map_or(Move::NULL, |entry: Entry| entry.best_move());
+        let mut picker: MovePicker = MovePicker::new(&self.fen, moves, tt_move, previous, ply, &self.ordering);
+
+        let original_alpha: i32 = alpha;
+        let mut best_score: i32 = -INFINITY;
+        let mut best_move: Move = Move::NULL;
+        let mut child_pv: Vec<Move> = Vec::new();
+        let mut tried: MoveList = MoveList::new();
+
+        while let Some((move1, stage)) = picker.next_move(&self.fen, &self.ordering) {
+            let quiet: bool = ordering::is_quiet(move1) && stage != Stage::TtMove;
+            if quiet && !tried.is_empty() && tried.len() >= late_move_limit {
+                continue
+            }
+
+            let undo: Undo = self.fen.make_move(move1);
+            let gives_check: bool = self.fen.in_check();
+            if futile && quiet && !gives_check && !tried.is_empty() {
+                self.fen.unmake_move(move1, undo);
+                continue
+            }
+
+            let extension: u32 = u32::from(selectivity.check_extensions && gives_check && ply < 2 * self.root_depth);
+            let new_depth: u32 = depth - 1 + extension;
+
+            let reduction: u32 = if selectivity.late_move_reductions
+                && quiet
+                && !in_check
+                && !gives_check
+                && depth >= LATE_MOVE_REDUCTION_MIN_DEPTH
+                && tried.len() >= LATE_MOVE_REDUCTION_MIN_MOVES
+            {
+                let reduction: u32 = REDUCTIONS[depth.min(63) as usize][tried.len().min(63)];
+                // the principal variation is reduced less, and a reduced move still gets at least one ply
+                reduction.saturating_sub(u32::from(pv_node)).min(new_depth.saturating_sub(1))
+            } else {
+                0
+            };
+
+            self.line.push(move1);
+            let score: Option<i32> = self.search_move(new_depth, reduction, ply, alpha, beta, tried.is_empty(), time_manager, &mut child_pv);
+            self.line.pop();
+            self.fen.unmake_move(move1, undo);
+
+            let score: i32 = score?;
+            if score > best_score {
+                best_score = score;
+                best_move = move1;
+            }
+
+            if score > alpha {
+                alpha = score;
+                pv.clear();
+                pv.push(move1);
+                pv.extend_from_slice(&child_pv);
+            }
+
+            if alpha >= beta {
+                self.ordering.update(&self.fen, move1, previous, ply, depth, &tried);
+                break
+            }
+            tried.push(move1);
+        }
+
+        let bound: Bound = if best_score >= beta {
+            Bound::Lower
+        } else if best_score > original_alpha {
+            Bound::Exact
+        } else {
+            Bound::Upper
+        };
+        // after failing low every move is only known to be bad, none of them is the best
+        let best_move: Move = if bound == Bound::Upper { Move::NULL } else { best_move };
+        self.tt.store(self.fen.hash(), depth, ply, best_score, bound, best_move);
+
+        Some(best_score)
     }
 
-    pub fn from_fen(fen_str: &str) -> Self {
-        let fen: Fen = Fen::from_str(fen_str);
+    /// Searches the position after a move `new_depth` plies deep with principal variation search and returns its score
+    /// from the view of the side that made the move. Only the first move is searched with the full window, the others
+    /// with a null window, `reduction` plies less deep, to prove they are not better than alpha. A move that turns out
+    /// better is searched again at full depth and then with the full window.
+    #[allow(clippy::too_many_arguments)]
+    fn search_move(&mut self, new_depth: u32, reduction: u32, ply: u32, alpha: i32, beta: i32, first: bool, time_manager: &TimeManager, pv: &mut Vec<Move>) -> Option<i32> {
+        if first {
+            return Some(-self.negamax(new_depth, ply + 1, -beta, -alpha, time_manager, pv)?)
+        }
 
-        Self { fen, }
+        let mut score: i32 = -self.negamax(new_depth - reduction, ply + 1, -alpha - 1, -alpha, time_manager, pv)?;
+        if score > alpha && reduction > 0 {
+            score = -self.negamax(new_depth, ply + 1, -alpha - 1, -alpha, time_manager, pv)?;
+        }
+        if score > alpha && score < beta {
+            score = -self.negamax(new_depth, ply + 1, -beta, -alpha, time_manager, pv)?;
+        }
+
+        Some(score)
     }
 
-    pub fn minimax(&self, move1: &mut Move, depth: u32, start_time: Instant, max_time: Duration) -> Option<u32> {
+    /// Checks whether the side has pieces other than pawns and its king, without them passing is often the best move
+    /// and null move pruning goes wrong.
+    fn has_pieces(&self, color: Color) -> bool {
+        [PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen]
+            .iter()
+            .any(|&kind: &PieceKind| !self.fen.pieces(Piece::new(color, kind)).is_empty())
+    }
 
-        if start_time.elapsed() >= max_time {
+    /// Searches only captures and promotions, or every move when in check, until the position is quiet,
+    /// so the position is not evaluated in the middle of an exchange (the horizon effect).
+    /// Returns the score from the view of the side to move, or `None` if the search was aborted.
+    ///
+    /// The side to move may also stand pat: not capturing is always possible when not in check,
+    /// so the static evaluation is a lower bound of the score.
+    pub fn quiescence(&mut self, ply: u32, mut alpha: i32, beta: i32, time_manager: &TimeManager) -> Option<i32> {
+        if time_manager.is_out_of_time(self.nodes) || self.stop.load(Ordering::Relaxed) {
             return None
         }
-        
-        if depth == 0 {
-            return Some(move1.eval)
+
+        self.nodes += 1;
+
+        let in_check: bool = self.fen.in_check();
+        let moves: MoveList = self.fen.tactical_moves();
+        if in_check && moves.is_empty() {
+            return Some(ply as i32 - MATE_SCORE)
+        }
+
+        let stand_pat: i32 = relative_eval(&self.fen);
+        if ply >= MAX_PLY {
+            return Some(stand_pat)
         }
 
-        let white_to_move: bool = move1.fen.white_to_move;
-        let mut value: u32 = get_worst_eval(white_to_move);
+        let mut best_score: i32 = -INFINITY;
+        if !in_check {
+            if stand_pat >= beta {
+                return Some(stand_pat)
+            }
 
-        for i in 0..move1.child_count {
+            best_score = stand_pat;
+            alpha = alpha.max(stand_pat);
+        }
 
-            if i == move1.index {
-                move1.add_child()
+        let mut picker: MovePicker = MovePicker::new(&self.fen, moves, Move::NULL, Move::NULL, ply, &self.ordering);
+        while let Some((move1, _)) = picker.next_move(&self.fen, &self.ordering) {
+            if !in_check && (stand_pat + self.material_gain(move1) + DELTA_MARGIN <= alpha || !self.fen.see_ge(move1, 0)) {
+                continue
             }
 
-            if let Some(new_value) = self.minimax(&mut move1.children[i], depth - 1, start_time, max_time) {
-                value = get_better_move(white_to_move, value, new_value);
-            } else {
-                return None
+            let undo: Undo = self.fen.make_move(move1);
+            let score: Option<i32> = self.quiescence(ply + 1, -beta, -alpha, time_manager);
+            self.fen.unmake_move(move1, undo);
+
+            let score: i32 = -score?;
+            if score > best_score {
+                best_score = score;
+            }
+            if score > alpha {
+                alpha = score;
+            }
+            if alpha >= beta {
+                break
+            }
+        }
+
+        Some(best_score)
+    }
+
+    /// Returns the material the move wins at once: the captured piece and what a pawn gains by promoting.
+    fn material_gain(&self, move1: Move) -> i32 {
+        let captured: i32 = if move1.is_en_passant() {
+            piece_value(PieceKind::Pawn)
+        } else {
+            self.fen.piece_on(move1.end_square()).map_or(0, |piece: Piece| piece_value(piece.kind))
+        };
+        let promoted: i32 = match move1.promotion() {
+            QUEEN_PROM => piece_value(PieceKind::Queen) - piece_value(PieceKind::Pawn),
+            ROOK_PROM => piece_value(PieceKind::Rook) - piece_value(PieceKind::Pawn),
+            BISHOP_PROM => piece_value(PieceKind::Bishop) - piece_value(PieceKind::Pawn),
+            KNIGHT_PROM => piece_value(PieceKind::Knight) - piece_value(PieceKind::Pawn),
+            _ => 0,
+        };
+
+        captured + promoted
+    }
+
+    /// Searches the position with iterative deepening until the time manager ends the search or the stop flag is set,
+    /// calling `report` after every completed depth. Returns the best move of the last completed depth,
+    /// or `Move::NULL` if there are no legal moves.
+    ///
+    /// Every depth starts again from the root, the transposition table keeps what earlier depths found.
+    fn search(&mut self, time_manager: &mut TimeManager, report: &mut dyn FnMut(&SearchInfo)) -> Move {
+        self.nodes = 0;
+        self.tt.new_search();
+        self.ordering.new_search();
+        self.line.clear();
+
+        // if even the first depth does not complete, any legal move is better than none
+        let mut best_move: Move = match self.fen.legal_moves().first() {
+            Some(&move1) => move1,
+            None => return Move::NULL,
+        };
+
+        let mut pv: Vec<Move> = Vec::new();
+        for depth in 1..=MAX_PLY {
+            self.root_depth = depth;
+            let score: i32 = match self.negamax(depth, 0, -INFINITY, INFINITY, time_manager, &mut pv) {
+                Some(score) => score,
+                None => return best_move,
+            };
+
+            best_move = pv[0];
+
+            report(&SearchInfo {
+                depth,
+                score,
+                nodes: self.nodes,
+                pv: pv.clone(),
+                hashfull: Some(self.tt.hashfull()),
+            });
+
+            if !time_manager.next_iteration(depth, best_move) {
+                break
             }
         }
-        return Some(value)
-    }
-
-    pub fn get_move(&mut self) -> String {
-        let start_time: Instant = Instant::now();
-        let max_time: Duration = Duration::from_millis(MAX_TIME_MILI);
-        let white_to_move: bool = self.fen.white_to_move;
-
-        let moves: Vec<[u64; 3]> = self.fen.get_all_possible_moves();
-        let mut possible_moves: Vec<Move> = Vec::new();
-        for move1 in moves {
-            let new_move = Move::new(move1, &self.fen);
-            possible_moves.push(new_move);
-        }
-
-        let mut best_move: [u64; 3] = possible_moves[0].move1;
-        let mut best_score: u32 = get_worst_eval(white_to_move);
-        let mut depth: u32 = 1;
-
-        loop {
-            println!("We are currently looking at depth: {:?}", depth);
-            let best_move_prev_iter: [u64; 3] = best_move;
-            for move1 in &mut possible_moves {
-                if let Some(new_score) = self.minimax(move1, depth, start_time, max_time) {
-                    if self.fen.white_to_move && new_score > best_score {
-                        best_score = new_score;
-                        best_move = move1.move1;
-                    } else if new_score < best_score {
-                        best_score = new_score;
-                        best_move = move1.move1;
-                    }
-                } else {
-                    self.fen.move_to_fen(&best_move_prev_iter);
-                    println!("Calculating move took {:?}", start_time.elapsed());
-                    return fenlib::parsing::move_to_lan(&best_move_prev_iter);
-                }
+
+        best_move
+    }
+}
+
+impl Bot for BotV1 {
+    fn name(&self) -> &str {
+        "BotV1"
+    }
+
+    fn options(&self) -> Vec<BotOption> {
+        let mut options: Vec<BotOption> = vec![HASH_OPTION];
+        for name in Selectivity::OPTIONS {
+            options.push(BotOption { name, default: 1, min: 0, max: 1 });
+        }
+
+        options
+    }
+
+    /// Sets "Hash", the size of the transposition table in MB, this clears the table,
+    /// or switches one of the techniques of `Selectivity` on (1) or off (0).
+    fn set_option(&mut self, name: &str, value: i64) -> Result<(), OptionError> {
+        if name.eq_ignore_ascii_case(HASH_OPTION.name) {
+            if !(HASH_OPTION.min..=HASH_OPTION.max).contains(&value) {
+                return Err(OptionError::BadValue(HASH_OPTION.name.to_string(), value));
             }
-            depth += 1
+
+            self.tt.resize(value as usize);
+            return Ok(());
+        }
+
+        let Some(enabled) = self.selectivity.option_mut(name) else {
+            return Err(OptionError::Unknown(name.to_string()));
+        };
+        match value {
+            0 | 1 => {
+                *enabled = value == 1;
+                Ok(())
+            },
+            _ => Err(OptionError::BadValue(name.to_string(), value)),
         }
+    }
+
+    fn new_game(&mut self) {
+        self.tt.clear();
+        self.ordering.clear();
+    }
 
+    fn set_position(&mut self, fen: Fen) {
+        self.fen = fen;
     }
 
-    pub fn receive_move(&mut self, lan: &str) {
-        self.fen.lan_to_fen(lan);
+    fn apply_move(&mut self, move1: Move) {
+        self.fen.move_to_fen(&move1);
     }
-}
\ No newline at end of file
+
+    fn choose_move(&mut self, limits: &SearchLimits, report: &mut dyn FnMut(&SearchInfo)) -> SearchResult {
+        let mut time_manager: TimeManager = TimeManager::new(limits, &self.fen);
+
+        let mut info: Option<SearchInfo> = None;
+        let best_move: Move = self.search(&mut time_manager, &mut |new_info: &SearchInfo| {
+            report(new_info);
+            info = Some(new_info.clone());
+        });
+
+        SearchResult { best_move, info }
+    }
+
+    fn stop_signal(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+}
@@ -0,0 +1,231 @@
+use botv1::{BotV1, Selectivity};
+use fenlib::Fen;
+use fenlib::bot::{Bot, MATE_SCORE, SearchInfo, SearchLimits, SearchResult};
+use fenlib::chess_move::Move;
+use fenlib::piece::PieceKind;
+use fenlib::time_manager::TimeManager;
+use fenlib::undo::Undo;
+
+const MATE_IN_ONE: &str = "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1";
+
+/// White mates in two, for example with Qb8+ Kxb8 Rd8#.
+const MATE_IN_TWO: &str = "2k5/ppp5/8/8/8/8/8/1Q1R2K1 w - - 0 1";
+
+const MIDDLEGAME: &str = "r1bqkb1r/pppp1ppp/2n2n2/4p3/2B1P3/5N2/PPPP1PPP/RNBQK2R w KQkq - 4 4";
+
+fn search(fen_str: &str, depth: u32) -> (SearchResult, Vec<SearchInfo>) {
+    search_with(fen_str, depth, Selectivity::ALL)
+}
+
+fn search_with(fen_str: &str, depth: u32, selectivity: Selectivity) -> (SearchResult, Vec<SearchInfo>) {
+    let mut bot: BotV1 = BotV1::from_fen(fen_str).unwrap();
+    bot.set_selectivity(selectivity);
+    let mut infos: Vec<SearchInfo> = Vec::new();
+    let result: SearchResult = bot.choose_move(&SearchLimits::depth(depth), &mut |info: &SearchInfo| infos.push(info.clone()));
+
+    (result, infos)
+}
+
+/// Plain negamax without pruning, to check that alpha-beta does not change the result.
+/// The leaves are scored by the quiescence search of the bot with an open window.
+fn full_width(bot: &mut BotV1, fen: &mut Fen, depth: u32, ply: u32) -> i32 {
+    if depth == 0 {
+        let time_manager: TimeManager = TimeManager::new(&SearchLimits::default(), fen);
+        bot.set_position(fen.clone());
+        return bot.quiescence(ply, -MATE_SCORE, MATE_SCORE, &time_manager).unwrap();
+    }
+
+    let moves: Vec<Move> = fen.legal_moves().to_vec();
+    if moves.is_empty() {
+        return if fen.in_check() { ply as i32 - MATE_SCORE } else { 0 };
+    }
+
+    let mut best_score: i32 = -MATE_SCORE;
+    for move1 in moves {
+        let undo: Undo = fen.make_move(move1);
+        best_score = best_score.max(-full_width(bot, fen, depth - 1, ply + 1));
+        fen.unmake_move(move1, undo);
+    }
+
+    best_score
+}
+
+#[test]
+fn mate_in_one() {
+    let (result, infos) = search(MATE_IN_ONE, 3);
+    let info: SearchInfo = result.info.unwrap();
+
+    assert_eq!(result.best_move.to_string(), "a1a8");
+    assert_eq!(info.score, MATE_SCORE - 1);
+    assert_eq!(info.mate(), Some(1));
+
+    // the mate is already seen at the first depth and keeps its score
+    assert!(infos.iter().all(|info: &SearchInfo| info.mate() == Some(1)));
+}
+
+#[test]
+fn mate_in_two() {
+    let (result, _) = search(MATE_IN_TWO, 4);
+    let info: SearchInfo = result.info.unwrap();
+
+    assert_eq!(info.score, MATE_SCORE - 3);
+    assert_eq!(info.mate(), Some(2));
+    assert_eq!(info.pv[0], result.best_move);
+
+    // the principal variation ends in the mate
+    let mut fen: Fen = MATE_IN_TWO.parse().unwrap();
+    assert_eq!(info.pv.len(), 3);
+    for move1 in &info.pv {
+        fen.make_move(*move1);
+    }
+    assert!(fen.in_check() && fen.legal_moves().is_empty());
+}
+
+#[test]
+fn getting_mated() {
+    // black to move makes room for the king in time
+    let (result, _) = search("6k1/5ppp/8/8/8/8/5PPP/R5K1 b - - 0 1", 2);
+    assert_eq!(result.info.unwrap().mate(), None);
+
+    // Kb8 is the only move and Rh8 mates
+    let (result, _) = search("k7/8/1K6/8/8/8/8/7R b - - 0 1", 2);
+    let info: SearchInfo = result.info.unwrap();
+    assert_eq!(info.score, -(MATE_SCORE - 2));
+    assert_eq!(info.mate(), Some(-1));
+}
+
+#[test]
+fn stalemate_is_a_draw() {
+    // black has no legal moves but is not in check
+    let mut bot: BotV1 = BotV1::from_fen("k7/P7/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+    let mut pv: Vec<Move> = Vec::new();
+    let time_manager: TimeManager = TimeManager::new(&SearchLimits::default(), &Fen::new());
+
+    assert_eq!(bot.negamax(3, 0, -MATE_SCORE, MATE_SCORE, &time_manager, &mut pv), Some(0));
+    assert!(pv.is_empty());
+}
+
+#[test]
+fn principal_variation_is_as_long_as_the_depth() {
+    // without reductions and extensions, which change how deep the lines are searched
+    let (_, infos) = search_with(MIDDLEGAME, 4, Selectivity::NONE);
+
+    assert_eq!(infos.len(), 4);
+    for info in &infos {
+        assert_eq!(info.pv.len(), info.depth as usize, "{:?}", info);
+    }
+
+    // the principal variation is a sequence of legal moves
+    let mut fen: Fen = MIDDLEGAME.parse().unwrap();
+    for move1 in &infos[3].pv {
+        assert!(fen.is_legal_move(move1), "{}", move1);
+        fen.make_move(*move1);
+    }
+}
+
+#[test]
+fn alpha_beta_scores_like_full_width_search() {
+    for fen_str in [MIDDLEGAME, MATE_IN_ONE, "4k3/8/8/3q4/8/2N5/8/4K3 w - - 0 1"] {
+        for depth in 1..=3 {
+            let (result, _) = search_with(fen_str, depth, Selectivity::NONE);
+            let mut fen: Fen = fen_str.parse().unwrap();
+            let mut bot: BotV1 = BotV1::from_fen(fen_str).unwrap();
+
+            assert_eq!(result.info.unwrap().score, full_width(&mut bot, &mut fen, depth, 0), "{} at depth {}", fen_str, depth);
+        }
+    }
+}
+
+#[test]
+fn white_keeps_the_best_root_move() {
+    // the rook takes the undefended knight, every other move is worse for white
+    let (result, _) = search("4k3/8/8/3n4/8/8/3R4/4K3 w - - 0 1", 2);
+    assert_eq!(result.best_move.to_string(), "d2d5");
+}
+
+#[test]
+fn transposition_table_is_kept_between_searches() {
+    let mut bot: BotV1 = BotV1::from_fen(MIDDLEGAME).unwrap();
+    let search = |bot: &mut BotV1| bot.choose_move(&SearchLimits::depth(4), &mut |_: &SearchInfo| {}).info.unwrap();
+
+    let first: SearchInfo = search(&mut bot);
+    let second: SearchInfo = search(&mut bot);
+    assert_eq!(second.pv[0], first.pv[0]);
+    assert!(second.nodes < first.nodes, "{} >= {}", second.nodes, first.nodes);
+    assert!(second.hashfull.is_some());
+
+    // a new game starts with an empty table
+    bot.new_game();
+    assert_eq!(search(&mut bot).nodes, first.nodes);
+}
+
+#[test]
+fn move_ordering_finds_most_cutoffs_with_the_first_move() {
+    let mut bot: BotV1 = BotV1::from_fen(MIDDLEGAME).unwrap();
+    bot.choose_move(&SearchLimits::depth(5), &mut |_: &SearchInfo| {});
+
+    let rate: f64 = bot.ordering().first_move_cutoff_rate().unwrap();
+    assert!(rate > 0.8, "{}", rate);
+}
+
+#[test]
+fn quiescence_sees_the_recapture() {
+    // the pawn on d5 is defended, taking it loses the queen for a pawn
+    let (result, _) = search("4k3/8/4p3/3p4/8/8/8/3QK3 w - - 0 1", 1);
+    assert_ne!(result.best_move.to_string(), "d1d5");
+
+    let mut bot: BotV1 = BotV1::from_fen("4k3/8/4p3/3Q4/8/8/8/4K3 b - - 0 1").unwrap();
+    let time_manager: TimeManager = TimeManager::new(&SearchLimits::default(), &Fen::new());
+    assert_eq!(bot.quiescence(0, -MATE_SCORE, MATE_SCORE, &time_manager), Some(botv1::piece_value(PieceKind::Pawn)));
+}
+
+#[test]
+fn quiescence_answers_checks() {
+    let time_manager: TimeManager = TimeManager::new(&SearchLimits::default(), &Fen::new());
+
+    // the quiet position is scored as it stands
+    let mut bot: BotV1 = BotV1::from_fen("4k3/8/8/8/8/8/4P3/4K3 w - - 0 1").unwrap();
+    assert_eq!(bot.quiescence(0, -MATE_SCORE, MATE_SCORE, &time_manager), Some(botv1::piece_value(PieceKind::Pawn)));
+
+    // in check there is no standing pat, so a mate is found
+    let mut bot: BotV1 = BotV1::from_fen("k7/1Q6/1K6/8/8/8/8/8 b - - 0 1").unwrap();
+    assert_eq!(bot.quiescence(3, -MATE_SCORE, MATE_SCORE, &time_manager), Some(3 - MATE_SCORE));
+}
+
+#[test]
+fn selective_search_needs_fewer_nodes() {
+    let (full, _) = search_with(MIDDLEGAME, 5, Selectivity::NONE);
+    let (selective, _) = search(MIDDLEGAME, 5);
+    let full_nodes: u64 = full.info.unwrap().nodes;
+    let selective_nodes: u64 = selective.info.unwrap().nodes;
+
+    assert!(selective_nodes < full_nodes, "{} >= {}", selective_nodes, full_nodes);
+}
+
+#[test]
+fn selective_search_keeps_the_tactics() {
+    for fen_str in [MATE_IN_ONE, MATE_IN_TWO] {
+        let (full, _) = search_with(fen_str, 4, Selectivity::NONE);
+        let (selective, _) = search(fen_str, 4);
+        assert_eq!(selective.info.unwrap().score, full.info.unwrap().score, "{}", fen_str);
+    }
+
+    // the reductions do not hide that the queen is hanging
+    let (result, _) = search("4k3/8/8/3q4/8/2N5/8/4K3 w - - 0 1", 5);
+    assert_eq!(result.best_move.to_string(), "c3d5");
+}
+
+#[test]
+fn selectivity_options() {
+    let mut bot: BotV1 = BotV1::from_fen(MIDDLEGAME).unwrap();
+    assert_eq!(bot.selectivity(), Selectivity::ALL);
+
+    for name in Selectivity::OPTIONS {
+        bot.set_option(name, 0).unwrap();
+    }
+    assert_eq!(bot.selectivity(), Selectivity::NONE);
+
+    bot.set_option("nullmove", 1).unwrap();
+    assert_eq!(bot.selectivity(), Selectivity { null_move: true, ..Selectivity::NONE });
+    assert!(bot.set_option("NullMove", -1).is_err());
+}
@@ -1,5 +1,12 @@
 use fenlib::*;
-use std::{collections::HashMap, time::Instant};
+use fenlib::bot::{Bot, BotOption, OptionError, SearchInfo, SearchLimits, SearchResult};
+use fenlib::chess_move::Move;
+use fenlib::error::FenError;
+use fenlib::piece::{Color, Piece, PieceKind};
+use fenlib::time_manager::TimeManager;
+use fenlib::undo::Undo;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 
 // Values from https://www.chessprogramming.org/Simplified_Evaluation_Function
 const PAWN_VAL: u32 = 100;
@@ -11,116 +18,196 @@ const KING_VAL: u32 = 20000;
 
 const EQUAL: u32 = 0x80000000;
 
-const INFINITY: u32 = u32::max_value();
+const INFINITY: u32 = u32::MAX;
 const MAX_DEPTH: u32 = 4;
+const DEPTH_OPTION: &str = "Depth";
+
+pub fn piece_value(kind: PieceKind) -> u32 {
+    match kind {
+        PieceKind::Pawn => PAWN_VAL,
+        PieceKind::Knight => KNIGHT_VAL,
+        PieceKind::Bishop => BISHOP_VAL,
+        PieceKind::Rook => ROOK_VAL,
+        PieceKind::Queen => QUEEN_VAL,
+        PieceKind::King => KING_VAL,
+    }
+}
 
-pub fn eval(boards: &[u64; 12]) -> u32 {
+pub fn eval(fen: &Fen) -> u32 {
     let mut score: u32 = EQUAL;
 
-    score += PAWN_VAL * boards[0].count_ones();
-    score -= PAWN_VAL * boards[6].count_ones();
-
-    score += KNIGHT_VAL * boards[1].count_ones();
-    score -= KNIGHT_VAL * boards[7].count_ones();
+    for kind in PieceKind::ALL {
+        score += piece_value(kind) * fen.pieces(Piece::new(Color::White, kind)).popcount();
+        score -= piece_value(kind) * fen.pieces(Piece::new(Color::Black, kind)).popcount();
+    }
 
-    score += BISHOP_VAL * boards[2].count_ones();
-    score -= BISHOP_VAL * boards[8].count_ones();
+    score
+}
 
-    score += ROOK_VAL * boards[3].count_ones();
-    score -= ROOK_VAL * boards[9].count_ones();
+/// Converts an eval to centipawns from the view of the side to move.
+pub fn centipawns(eval: u32, white_to_move: bool) -> i32 {
+    let score: i64 = eval as i64 - EQUAL as i64;
+    let score: i64 = if white_to_move { score } else { -score };
 
-    score += QUEEN_VAL * boards[4].count_ones();
-    score -= QUEEN_VAL * boards[10].count_ones();
+    score.clamp(-(i32::MAX as i64), i32::MAX as i64) as i32
+}
 
-    score += KING_VAL * boards[5].count_ones();
-    score -= KING_VAL * boards[11].count_ones();
+#[derive(Debug)]
+pub struct BotV2 {
+    fen: Fen,
 
-    score
+    /// The deepest depth searched when the limits do not give a depth, see the "Depth" option.
+    depth: u32,
+    nodes: u64,
+    stop: Arc<AtomicBool>,
 }
 
-#[derive(Debug, Clone)]
-pub struct BotV1 {
-    fen: Fen,
+impl Default for BotV2 {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
-impl BotV1 {
+impl BotV2 {
     pub fn new() -> Self {
-        let fen: Fen = Fen::new();
-
-        Self { fen, }
+        Self::from_position(Fen::new())
     }
 
-    pub fn from_fen(fen_str: &str) -> Self {
-        let fen: Fen = Fen::from_str(fen_str);
+    pub fn from_fen(fen_str: &str) -> Result<Self, FenError> {
+        let fen: Fen = fen_str.parse()?;
 
-        Self { fen, }
+        Ok(Self::from_position(fen))
     }
 
-    pub fn get_move(&mut self) -> String {
-        let t1: Instant = Instant::now();
+    pub fn from_position(fen: Fen) -> Self {
+        Self { fen, depth: MAX_DEPTH, nodes: 0, stop: Arc::new(AtomicBool::new(false)) }
+    }
 
-        let possible_moves: Vec<[u64; 3]> = self.fen.get_all_possible_moves();
-        let mut best_move: [u64; 3] = possible_moves[0];
+    /// Searches every move of the root to `depth` and returns the best one with its eval,
+    /// or `None` if the search was aborted by the time manager or the stop flag.
+    fn search_root(&mut self, possible_moves: &[Move], depth: u32, time_manager: &TimeManager) -> Option<(Move, u32)> {
+        let mut best_move: Move = possible_moves[0];
         let mut best_score: u32 = match self.fen.white_to_move {
             true => 0x0,
             false => INFINITY
         };
 
-        for move1 in possible_moves {
-            let mut new_fen: Fen = self.fen.clone();
-            new_fen.move_to_fen(&move1);
-            let new_score: u32 = minimax(new_fen, MAX_DEPTH - 1);
+        for &move1 in possible_moves {
+            let undo: Undo = self.fen.make_move(move1);
+            let new_score: Option<u32> = self.minimax(depth - 1, time_manager);
+            self.fen.unmake_move(move1, undo);
 
-            if self.fen.white_to_move && new_score > best_score {
-                best_score = new_score;
-                best_move = move1;
-            } else if new_score < best_score {
+            let new_score: u32 = new_score?;
+            if (self.fen.white_to_move && new_score > best_score) || (!self.fen.white_to_move && new_score < best_score) {
                 best_score = new_score;
                 best_move = move1;
             }
         }
 
-        self.fen.move_to_fen(&best_move);
+        Some((best_move, best_score))
+    }
 
-        println!("Calculating move took {:?}", t1.elapsed());
+    pub fn minimax(&mut self, depth: u32, time_manager: &TimeManager) -> Option<u32> {
+        if time_manager.is_out_of_time(self.nodes) || self.stop.load(Ordering::Relaxed) {
+            return None
+        }
 
-        fenlib::parsing::move_to_lan(&best_move)
-    }
+        self.nodes += 1;
 
-    pub fn receive_move(&mut self, lan: &str) {
-        self.fen.lan_to_fen(lan);
-    }
-}
+        if depth == 0 {
+            let eval: u32 = eval(&self.fen);
+            return Some(eval)
+        }
 
-pub fn minimax(fen: Fen, depth: u32) -> u32 {
-    if depth == 0 {
-        let eval: u32 = eval(&fen.boards);
-        return eval
-    }
+        let white_to_move: bool = self.fen.white_to_move;
+        let mut value: u32 = match white_to_move {
+            true => 0x0,
+            false => INFINITY
+        };
 
-    if fen.white_to_move {
-        let mut value: u32 = 0x0;
-        let possible_moves: Vec<[u64; 3]> = fen.get_all_possible_moves();
+        let possible_moves: Vec<Move> = self.fen.get_all_possible_moves();
         for move1 in possible_moves {
-            let mut new_fen: Fen = fen.clone();
-            new_fen.move_to_fen(&move1);
-            let new_value: u32 = minimax(new_fen, depth - 1);
-            if new_value > value {
+            let undo: Undo = self.fen.make_move(move1);
+            let new_value: Option<u32> = self.minimax(depth - 1, time_manager);
+            self.fen.unmake_move(move1, undo);
+
+            let new_value: u32 = new_value?;
+            if (white_to_move && new_value > value) || (!white_to_move && new_value < value) {
                 value = new_value
             }
         }
-        return value;
-    } else {
-        let mut value: u32 = INFINITY;
-        let possible_moves: Vec<[u64; 3]> = fen.get_all_possible_moves();
-        for move1 in possible_moves {
-            let mut new_fen: Fen = fen.clone();
-            new_fen.move_to_fen(&move1);
-            let new_value: u32 = minimax(new_fen, depth - 1);
-            if new_value < value {
-                value = new_value
+        Some(value)
+    }
+}
+
+impl Bot for BotV2 {
+    fn name(&self) -> &str {
+        "BotV2"
+    }
+
+    fn options(&self) -> Vec<BotOption> {
+        vec![BotOption { name: DEPTH_OPTION, default: MAX_DEPTH as i64, min: 1, max: 8 }]
+    }
+
+    fn set_option(&mut self, name: &str, value: i64) -> Result<(), OptionError> {
+        if !name.eq_ignore_ascii_case(DEPTH_OPTION) {
+            return Err(OptionError::Unknown(name.to_string()));
+        }
+
+        match u32::try_from(value) {
+            Ok(depth) if (1..=8).contains(&depth) => {
+                self.depth = depth;
+                Ok(())
+            },
+            _ => Err(OptionError::BadValue(name.to_string(), value)),
+        }
+    }
+
+    fn set_position(&mut self, fen: Fen) {
+        self.fen = fen;
+    }
+
+    fn apply_move(&mut self, move1: Move) {
+        self.fen.move_to_fen(&move1);
+    }
+
+    /// Searches with iterative deepening until the time manager ends the search, at most to the depth of the limits
+    /// or else the "Depth" option.
+    fn choose_move(&mut self, limits: &SearchLimits, report: &mut dyn FnMut(&SearchInfo)) -> SearchResult {
+        let limits: SearchLimits = SearchLimits { depth: Some(limits.depth.unwrap_or(self.depth).max(1)), ..*limits };
+        let mut time_manager: TimeManager = TimeManager::new(&limits, &self.fen);
+        self.nodes = 0;
+
+        let possible_moves: Vec<Move> = self.fen.get_all_possible_moves();
+        let Some(&first_move) = possible_moves.first() else {
+            return SearchResult { best_move: Move::NULL, info: None };
+        };
+
+        let mut result: SearchResult = SearchResult { best_move: first_move, info: None };
+        let mut depth: u32 = 1;
+
+        while let Some((best_move, best_score)) = self.search_root(&possible_moves, depth, &time_manager) {
+            let info: SearchInfo = SearchInfo {
+                depth,
+                score: centipawns(best_score, self.fen.white_to_move),
+                nodes: self.nodes,
+                pv: vec![best_move],
+                hashfull: None,
+            };
+            report(&info);
+            result = SearchResult { best_move, info: Some(info) };
+
+            if !time_manager.next_iteration(depth, best_move) {
+                break;
             }
+
+            depth += 1;
         }
-        return value;
+
+        result
     }
-}
\ No newline at end of file
+
+    fn stop_signal(&self) -> Arc<AtomicBool> {
+        Arc::clone(&self.stop)
+    }
+}
@@ -0,0 +1,10 @@
+use botv2::BotV2;
+use fenlib::bot::{Bot, SearchInfo, SearchLimits, SearchResult};
+
+#[test]
+fn white_keeps_the_best_root_move() {
+    // the rook takes the undefended knight, every other move is worse for white
+    let mut bot: BotV2 = BotV2::from_fen("4k3/8/8/3n4/8/8/3R4/4K3 w - - 0 1").unwrap();
+    let result: SearchResult = bot.choose_move(&SearchLimits { depth: Some(2), ..SearchLimits::default() }, &mut |_: &SearchInfo| {});
+    assert_eq!(result.best_move.to_string(), "d2d5");
+}
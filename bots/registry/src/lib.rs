@@ -0,0 +1,15 @@
+use botv1::BotV1;
+use botv2::BotV2;
+use fenlib::bot::Bot;
+
+/// The names of the bots the front ends and the Python bindings can play with, the first one is the default.
+pub const BOTS: [&str; 2] = ["botv1", "botv2"];
+
+/// Creates the bot with the given name, see `BOTS`.
+pub fn bot_by_name(name: &str) -> Option<Box<dyn Bot>> {
+    match name.to_ascii_lowercase().as_str() {
+        "botv1" => Some(Box::new(BotV1::new())),
+        "botv2" => Some(Box::new(BotV2::new())),
+        _ => None,
+    }
+}
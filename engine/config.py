@@ -16,7 +16,7 @@ import random
 import multiprocessing
 from math import floor, sqrt
 from os import getcwd
-from rust_utils import FenPy, BotV1Py
+from rust_utils import FenPy, Bot, Outcome, Reason
 
 MAIN_LOCATION = getcwd()
 
@@ -28,10 +28,14 @@ NO_PIECE = 0
 GAMES = 1020
 BATCH_SIZE = 17
 
-WIN = {
-    '1-0': 'white wins',
-    '0-1': 'black wins',
-    '½-½': 'draw',
-}
+# the games played by run() are saved to this file in pgn, None to not save them
+PGN_OUTPUT = None
 
-USER = 'user'
\ No newline at end of file
+# the bots that play each other in run(), bot 1 plays white, see Bot.names() for the options
+BOT1 = 'botv1'
+BOT2 = 'botv1'
+
+USER = 'user'
+
+# returned by Visual.get_move when the player takes back the last move
+UNDO = 'undo'
\ No newline at end of file
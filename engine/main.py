@@ -7,10 +7,8 @@ from matches import run, bench
 def test():
     fen = FenPy()
     # fen = fen.from_str('6k1/5ppp/8/8/8/8/5PPP/5qK1 w - - 0 1')
-    bot1 = BotV1Py()
-    bot2 = BotV1Py()
-    bot1 = bot1.from_fen(fen.to_string())
-    bot2 = bot2.from_fen(fen.to_string())
+    bot1 = Bot('botv1', fen.to_string())
+    bot2 = Bot('botv1', fen.to_string())
     PlayerVsBotMatch(bot1, player_is_white=True, fen=fen, perspective=WHITE).run_match()
     # BotVsBotMatch(visual_debugger, bot1, bot2, delay=0, fen=fen, is_visual=True, perspective=WHITE).run_match()
     # PlayerVsPlayerMatch(fen, WHITE).run_match()
@@ -7,7 +7,7 @@ class PlayerVsPlayerMatch:
     def __init__(self, fen=FenPy(), perspective=WHITE):
         self.fen = fen
         self.perspective = perspective
-        self.visual = Visual(self.fen, self.perspective)
+        self.visual = Visual(self.fen, self.perspective, allow_undo=True)
 
     def run_match(self):
         self.visual.setup()
@@ -21,12 +21,14 @@ class PlayerVsPlayerMatch:
             
             if not move:
                 running = False
+            elif move == UNDO:
+                self.fen.undo()
             else:
                 self.fen.lan_to_fen(move)
 
-            game_ended = self.fen.game_ended()
-            if game_ended == '1-0' or game_ended == '0-1' or game_ended == '½-½':
-                print(WIN[game_ended])
+            result = self.fen.result()
+            if not result.is_ongoing():
+                print(result)
                 running = False
 
         pygame.quit()
@@ -43,7 +45,7 @@ class BotVsBotMatch:
         self.visual = Visual(self.fen, self.perspective)
 
     def run_match(self):
-        win = 'not ended'
+        result = self.fen.result()
 
         if self.is_visual:
             self.visual.setup()
@@ -56,16 +58,15 @@ class BotVsBotMatch:
             else:
                 self.get_move()
 
-            game_ended = self.fen.game_ended()
-            if game_ended == '1-0' or game_ended == '0-1' or game_ended == '½-½':
-                win = game_ended
-                print(WIN[win])
+            result = self.fen.result()
+            if not result.is_ongoing():
+                print(result)
                 running = False
         
         if self.is_visual:
             pygame.quit()
         
-        return win
+        return result
 
     def get_move(self):
         time.sleep(self.delay)
@@ -112,10 +113,10 @@ class PlayerVsBotMatch:
                 self.fen.lan_to_fen(move)
                 self.bot.receive_move(move)
 
-            game_ended = self.fen.game_ended()
-            if game_ended == '1-0' or game_ended == '0-1' or game_ended == '½-½':
+            result = self.fen.result()
+            if not result.is_ongoing():
                 print(self.fen.to_string())
-                print(WIN[game_ended])
+                print(result)
                 running = False
 
         pygame.quit()
@@ -15,12 +15,14 @@ def run_match_worker(games):
         fen = FenPy()
         fen = fen.from_str(game)
 
-        bot1 = BotV1Py().from_fen(fen.to_string())
-        bot2 = BotV1Py().from_fen(fen.to_string())
+        bot1 = Bot(BOT1, fen.to_string())
+        bot2 = Bot(BOT2, fen.to_string())
         
-        match = BotVsBotMatch(None, bot1, bot2, delay=0, fen=fen, is_visual=False, perspective=WHITE)
+        match = BotVsBotMatch(bot1, bot2, delay=0, fen=fen, is_visual=False, perspective=WHITE)
         result = match.run_match()
-        results.append(result)
+
+        # results are sent back to the main process, so they are returned as plain strings
+        results.append((result.pgn(), fen.to_pgn([('White', bot1.name()), ('Black', bot2.name())])))
 
     return results
 
@@ -35,15 +37,22 @@ def run():
     with multiprocessing.Pool(processes=len(batches)) as pool:
         all_results = pool.map(run_match_worker, batches)
 
+    pgns = []
     for result_list in all_results:
-        for result in result_list:
+        for result, pgn in result_list:
+            pgns.append(pgn)
+
             if result == '1-0':
                 bot1_wins += 1
             elif result == '0-1':
                 bot2_wins += 1
-            elif result == '½-½' or result == '1/2-1/2':
+            elif result == '1/2-1/2':
                 draws += 1
             else:
-                print(f"Unknown result: {result}")
+                print(f"Unfinished game: {result}")
+
+    if PGN_OUTPUT is not None:
+        with open(PGN_OUTPUT, 'w') as file:
+            file.write('\n'.join(pgns))
 
     return bot1_wins, bot2_wins, draws
\ No newline at end of file
@@ -1,6 +1,10 @@
 from config import *
 
 class TestBot:
+    """
+    A bot that plays random moves, with the same methods as the Bot class from rust_utils.
+    """
+
     def class_name():
         return 'testbot'
 
@@ -8,18 +12,32 @@ class TestBot:
         self.fen = fen
         self.delay = delay
 
-    def get_move(self):
+    def name(self):
+        return 'TestBot'
+
+    def options(self):
+        return []
+
+    def new_game(self):
+        pass
+
+    def set_position(self, fen_str):
+        self.fen = FenPy.from_str(fen_str)
+
+    def apply_move(self, move):
+        self.fen.lan_to_fen(move)
+
+    def choose_move(self, **limits):
         time.sleep(self.delay)
         moves = self.fen.get_all_possible_moves_lan()
-        index = random.randint(0, len(moves) - 1)
-        move = moves[index]
-        # print('----')
-        # print(self.fen.to_string())
-        # print(moves)
-        # print(move)
-        self.fen.lan_to_fen(move)
-        # print(self.fen.to_string())
+        if not moves:
+            return None
+        return random.choice(moves)
+
+    def get_move(self, **limits):
+        move = self.choose_move(**limits)
+        self.apply_move(move)
         return move
-    
+
     def receive_move(self, move):
-        self.fen.lan_to_fen(move)
\ No newline at end of file
+        self.apply_move(move)
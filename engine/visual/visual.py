@@ -5,9 +5,10 @@ from visual.piece import Piece
 from visual.utils import *
 
 class Visual:
-    def __init__(self, fen: FenPy, perspective):
+    def __init__(self, fen: FenPy, perspective, allow_undo=False):
         self.fen = fen
         self.perspective = perspective
+        self.allow_undo = allow_undo
 
     def setup(self):
         pygame.init()
@@ -121,6 +122,10 @@ class Visual:
             for event in pygame.event.get():
                 if event.type == pygame.QUIT:
                     return False
+                if event.type == pygame.KEYDOWN and event.key == pygame.K_BACKSPACE:
+                    if self.allow_undo and not self.current_tile_active:
+                        self.clear_previous_move()
+                        return UNDO
                 if event.type == pygame.MOUSEBUTTONDOWN:
                     self.mouse_down = True
                     if not self.current_tile_active:
@@ -264,6 +269,12 @@ class Visual:
                 elif tile in self.previous_move:
                     tile.set_display(tile.default_color, tile.border, False)
     
+    def clear_previous_move(self):
+        if self.show_previous_move:
+            for tile in self.previous_move:
+                tile.reset()
+            self.show_previous_move = False
+
     def place_piece(self, start, end, legal):
         self.reset_tiles()
         if legal:
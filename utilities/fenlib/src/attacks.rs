@@ -0,0 +1,345 @@
+//! Precomputed attack tables for every piece.
+//!
+//! Knights, kings and pawns use a table indexed by square that is computed at compile time.
+//! Rooks and bishops use magic bitboards (https://www.chessprogramming.org/Magic_Bitboards): the relevant
+//! blockers on the rays of a square are multiplied by a magic number, and the top bits of the product
+//! index a table that holds the attacks for that configuration of blockers. Those tables are built the
+//! first time a sliding piece is looked up, as are the tables of squares between and on a line through two squares.
+//!
+//! **NOTE:** An attacked square may contain a piece of either color, the caller has to remove its own pieces.
+
+use std::sync::LazyLock;
+
+use crate::bitboard::Bitboard;
+use crate::piece::Color;
+use crate::square::Square;
+
+const ROOK_DIRECTIONS: [(i8, i8); 4] = [(1, 0), (-1, 0), (0, 1), (0, -1)];
+const BISHOP_DIRECTIONS: [(i8, i8); 4] = [(1, 1), (1, -1), (-1, 1), (-1, -1)];
+
+/// Squares a knight attacks, indexed by square.
+pub const KNIGHT_ATTACKS: [u64; 64] = knight_table();
+
+/// Squares a king attacks, indexed by square. Castling is not included.
+pub const KING_ATTACKS: [u64; 64] = king_table();
+
+/// Squares a pawn attacks diagonally, indexed by color and square.
+pub const PAWN_ATTACKS: [[u64; 64]; 2] = pawn_table();
+
+/// Magic numbers for the rook tables, found with `find_magic`.
+pub const ROOK_MAGICS: [u64; 64] = [
+    0x2080002080400010, 0x0040200010004004, 0x0880200080899000, 0x0200120020044008,
+    0x2200100408200200, 0x2200010410088200, 0x8200220000A82104, 0x0200010200408024,
+    0x0020800040008028, 0x0004400050002000, 0x0840801000802000, 0x02160020481200C0,
+    0x8026001008220004, 0x2002000200041008, 0x8084004402080110, 0x4440800080004100,
+    0x4020008080004000, 0x00A0818020024001, 0x0900820010220840, 0x068081801000C801,
+    0x400D808004000800, 0x4100808002000400, 0x4101040008011002, 0x1100120008408124,
+    0x0200800080204003, 0x60106000C0100840, 0x2054482200120180, 0x2180080080100080,
+    0x4004008080080004, 0x0000020080800400, 0x0000020400B00108, 0x0165000100004082,
+    0x00314008A0800280, 0x0240004289802002, 0x0010801042002200, 0x0040081001002100,
+    0x2100800800800400, 0x2202000402001008, 0x3900100204000108, 0x0020204106000484,
+    0x8820208040008000, 0x04005000A000C000, 0x5001001020010040, 0x9801001000090020,
+    0x2001001008030004, 0x040100A400030008, 0x0000022841040090, 0xB0090440840A0005,
+    0x000500C130800100, 0x0040400020088C80, 0x0322A00050068680, 0x4003480480100180,
+    0x9080080080040080, 0x0A02040080020080, 0x0040800100020080, 0x1002008041040200,
+    0x0084408000102501, 0x8000401102248202, 0x0022108820004501, 0x0001002008041001,
+    0x0001000800040211, 0x8001000204000803, 0x20003008010200A4, 0xA200010408204082,
+];
+
+/// Magic numbers for the bishop tables, found with `find_magic`.
+pub const BISHOP_MAGICS: [u64; 64] = [
+    0x8008029802002200, 0x0408020800630248, 0x0411080220408410, 0x0208068900000420,
+    0x000110408000B000, 0xA002022320000020, 0x0400840402420000, 0x200A044202500200,
+    0x9820111010088480, 0x0B88044184050201, 0x4100080884089050, 0x0044144400801005,
+    0x48A4220210000011, 0x4008408210C00014, 0x2104950401A02841, 0x1000010C11044200,
+    0x1029043220010402, 0x002008109200810C, 0x0010008440428104, 0x008420B802002444,
+    0x1004001220A00020, 0x00020085009A0100, 0x4001201484100200, 0x0200A212138C0400,
+    0x0004040040104490, 0x8004200010020080, 0x2008020104140010, 0x00300400C0440008,
+    0x0101001001004000, 0x8008012286028400, 0x0C0218404A280208, 0x2044110420250900,
+    0x0010100840040820, 0x0002029010200140, 0x4004002208240401, 0x0001400808208200,
+    0x0000440400004100, 0x44011802010C2210, 0x0090840841888620, 0xA0010A2080182404,
+    0x0088221010400400, 0x0500421004101100, 0x0220220030000200, 0xA080044200801808,
+    0x0010403008888100, 0x0248100080809808, 0x8020080200940040, 0x0541A10404800100,
+    0x2009110120204010, 0x810202010108A001, 0x06A0220084041000, 0x0102000108581400,
+    0x0802002004240000, 0x104C200202220500, 0x2009021032020028, 0x1004080484198020,
+    0x6084210250046000, 0x0020008898080203, 0x0000020100809000, 0x0240600100208820,
+    0x0610013004050400, 0x0040804810410210, 0x8080202001210100, 0x88C0040400821610,
+];
+
+/// The magic lookup data for one slider.
+struct SliderTable {
+    masks: [u64; 64],
+    magics: [u64; 64],
+    shifts: [u32; 64],
+    offsets: [usize; 64],
+    attacks: Vec<u64>,
+}
+
+impl SliderTable {
+    fn new(magics: &[u64; 64], directions: &[(i8, i8); 4]) -> Self {
+        let mut table: Self = Self {
+            masks: [0; 64],
+            magics: *magics,
+            shifts: [0; 64],
+            offsets: [0; 64],
+            attacks: Vec::new(),
+        };
+
+        for square in Square::ALL {
+            let index: usize = square.index() as usize;
+            let mask: u64 = relevant_mask(square, directions);
+            let bits: u32 = mask.count_ones();
+
+            table.masks[index] = mask;
+            table.shifts[index] = 64 - bits;
+            table.offsets[index] = table.attacks.len();
+            table.attacks.resize(table.attacks.len() + (1 << bits), 0);
+
+            // walk through every subset of the mask with the carry-rippler trick
+            let mut blockers: u64 = 0;
+            loop {
+                let attacks: u64 = ray_attacks(square, blockers, directions);
+                let entry: &mut u64 = &mut table.attacks[table.offsets[index] + magic_index(blockers, mask, magics[index], 64 - bits)];
+                debug_assert!(*entry == 0 || *entry == attacks, "magic number for {} causes a collision", square);
+                *entry = attacks;
+
+                blockers = blockers.wrapping_sub(mask) & mask;
+                if blockers == 0 {
+                    break;
+                }
+            }
+        }
+
+        table
+    }
+
+    fn attacks(&self, square: Square, occupancy: u64) -> u64 {
+        let index: usize = square.index() as usize;
+        let offset: usize = magic_index(occupancy, self.masks[index], self.magics[index], self.shifts[index]);
+        self.attacks[self.offsets[index] + offset]
+    }
+}
+
+static ROOK_TABLE: LazyLock<SliderTable> = LazyLock::new(|| SliderTable::new(&ROOK_MAGICS, &ROOK_DIRECTIONS));
+static BISHOP_TABLE: LazyLock<SliderTable> = LazyLock::new(|| SliderTable::new(&BISHOP_MAGICS, &BISHOP_DIRECTIONS));
+
+/// Squares strictly between two squares and the full line through them, see `between` and `line`.
+struct LineTable {
+    between: Vec<[u64; 64]>,
+    line: Vec<[u64; 64]>,
+}
+
+impl LineTable {
+    fn new() -> Self {
+        let mut table: Self = Self {
+            between: vec![[0; 64]; 64],
+            line: vec![[0; 64]; 64],
+        };
+
+        for square in Square::ALL {
+            let from: usize = square.index() as usize;
+
+            for &(files, ranks) in ROOK_DIRECTIONS.iter().chain(BISHOP_DIRECTIONS.iter()) {
+                let full_line: u64 = square.bit() | ray(square, files, ranks) | ray(square, -files, -ranks);
+
+                let mut passed: u64 = 0;
+                let mut current: Square = square;
+                while let Some(next) = current.offset(files, ranks) {
+                    let to: usize = next.index() as usize;
+                    table.between[from][to] = passed;
+                    table.line[from][to] = full_line;
+                    passed |= next.bit();
+                    current = next;
+                }
+            }
+        }
+
+        table
+    }
+}
+
+static LINE_TABLE: LazyLock<LineTable> = LazyLock::new(LineTable::new);
+
+/// Returns the squares a knight on the given square attacks.
+pub fn knight_attacks(square: Square) -> Bitboard {
+    Bitboard(KNIGHT_ATTACKS[square.index() as usize])
+}
+
+/// Returns the squares a king on the given square attacks.
+pub fn king_attacks(square: Square) -> Bitboard {
+    Bitboard(KING_ATTACKS[square.index() as usize])
+}
+
+/// Returns the squares a pawn of the given color on the given square attacks diagonally.
+pub fn pawn_attacks(color: Color, square: Square) -> Bitboard {
+    Bitboard(PAWN_ATTACKS[color.index()][square.index() as usize])
+}
+
+/// Returns the squares a rook on the given square attacks, stopping at the first piece on every ray.
+pub fn rook_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    Bitboard(ROOK_TABLE.attacks(square, occupancy.0))
+}
+
+/// Returns the squares a bishop on the given square attacks, stopping at the first piece on every ray.
+pub fn bishop_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    Bitboard(BISHOP_TABLE.attacks(square, occupancy.0))
+}
+
+/// Returns the squares a queen on the given square attacks, stopping at the first piece on every ray.
+pub fn queen_attacks(square: Square, occupancy: Bitboard) -> Bitboard {
+    rook_attacks(square, occupancy) | bishop_attacks(square, occupancy)
+}
+
+/// Returns the squares strictly between two squares on the same rank, file or diagonal,
+/// or an empty set if the squares are not aligned.
+pub fn between(a: Square, b: Square) -> Bitboard {
+    Bitboard(LINE_TABLE.between[a.index() as usize][b.index() as usize])
+}
+
+/// Returns the whole rank, file or diagonal through two squares, including both of them,
+/// or an empty set if the squares are not aligned.
+pub fn line(a: Square, b: Square) -> Bitboard {
+    Bitboard(LINE_TABLE.line[a.index() as usize][b.index() as usize])
+}
+
+/// Searches a magic number for the rook (or bishop) tables of a square.
+///
+/// This is how `ROOK_MAGICS` and `BISHOP_MAGICS` were generated, it only has to be run again if the
+/// layout of the bitboards changes. The search is deterministic for a given seed.
+pub fn find_magic(square: Square, bishop: bool, seed: u64) -> u64 {
+    let directions: &[(i8, i8); 4] = if bishop { &BISHOP_DIRECTIONS } else { &ROOK_DIRECTIONS };
+    let mask: u64 = relevant_mask(square, directions);
+    let bits: u32 = mask.count_ones();
+
+    let mut occupancies: Vec<u64> = Vec::new();
+    let mut blockers: u64 = 0;
+    loop {
+        occupancies.push(blockers);
+        blockers = blockers.wrapping_sub(mask) & mask;
+        if blockers == 0 {
+            break;
+        }
+    }
+
+    let attacks: Vec<u64> = occupancies.iter().map(|&occupancy: &u64| ray_attacks(square, occupancy, directions)).collect();
+    let mut used: Vec<u64> = vec![0; 1 << bits];
+    let mut state: u64 = seed | 1;
+
+    loop {
+        // sparse random numbers make good magic candidates
+        let magic: u64 = xorshift(&mut state) & xorshift(&mut state) & xorshift(&mut state);
+        if (mask.wrapping_mul(magic) & 0xFF00000000000000).count_ones() < 6 {
+            continue;
+        }
+
+        used.iter_mut().for_each(|entry: &mut u64| *entry = 0);
+        let fits: bool = occupancies.iter().zip(&attacks).all(|(&occupancy, &attack): (&u64, &u64)| {
+            let entry: &mut u64 = &mut used[magic_index(occupancy, mask, magic, 64 - bits)];
+            if *entry == 0 || *entry == attack {
+                *entry = attack;
+                true
+            } else {
+                false
+            }
+        });
+
+        if fits {
+            return magic;
+        }
+    }
+}
+
+fn xorshift(state: &mut u64) -> u64 {
+    *state ^= *state << 13;
+    *state ^= *state >> 7;
+    *state ^= *state << 17;
+    *state
+}
+
+fn magic_index(occupancy: u64, mask: u64, magic: u64, shift: u32) -> usize {
+    ((occupancy & mask).wrapping_mul(magic) >> shift) as usize
+}
+
+/// Walks every ray from the square until it leaves the board or hits a piece, the piece is included.
+fn ray_attacks(square: Square, occupancy: u64, directions: &[(i8, i8); 4]) -> u64 {
+    let mut attacks: u64 = 0;
+
+    for &(files, ranks) in directions {
+        let mut current: Square = square;
+        while let Some(next) = current.offset(files, ranks) {
+            attacks |= next.bit();
+            if occupancy & next.bit() != 0 {
+                break;
+            }
+            current = next;
+        }
+    }
+
+    attacks
+}
+
+/// Returns every square from the square in one direction up to the edge of the board, the square itself is left out.
+fn ray(square: Square, files: i8, ranks: i8) -> u64 {
+    let mut squares: u64 = 0;
+    let mut current: Square = square;
+    while let Some(next) = current.offset(files, ranks) {
+        squares |= next.bit();
+        current = next;
+    }
+    squares
+}
+
+/// Returns the squares on the rays of the square whose occupancy changes the attacks.
+/// The last square of every ray is left out, a piece there does not block anything.
+fn relevant_mask(square: Square, directions: &[(i8, i8); 4]) -> u64 {
+    let mut mask: u64 = 0;
+
+    for &(files, ranks) in directions {
+        let mut current: Square = square;
+        while let Some(next) = current.offset(files, ranks) {
+            if next.offset(files, ranks).is_none() {
+                break;
+            }
+            mask |= next.bit();
+            current = next;
+        }
+    }
+
+    mask
+}
+
+const fn knight_table() -> [u64; 64] {
+    let mut table: [u64; 64] = [0; 64];
+    let mut index: usize = 0;
+    while index < 64 {
+        let start: Bitboard = Bitboard(1u64 << index);
+        table[index] = start.shift(1, 2).0 | start.shift(-1, 2).0 | start.shift(2, 1).0 | start.shift(-2, 1).0
+            | start.shift(1, -2).0 | start.shift(-1, -2).0 | start.shift(2, -1).0 | start.shift(-2, -1).0;
+        index += 1;
+    }
+    table
+}
+
+const fn king_table() -> [u64; 64] {
+    let mut table: [u64; 64] = [0; 64];
+    let mut index: usize = 0;
+    while index < 64 {
+        let start: Bitboard = Bitboard(1u64 << index);
+        table[index] = start.north().0 | start.south().0 | start.east().0 | start.west().0
+            | start.north_east().0 | start.north_west().0 | start.south_east().0 | start.south_west().0;
+        index += 1;
+    }
+    table
+}
+
+const fn pawn_table() -> [[u64; 64]; 2] {
+    let mut table: [[u64; 64]; 2] = [[0; 64]; 2];
+    let mut index: usize = 0;
+    while index < 64 {
+        let start: Bitboard = Bitboard(1u64 << index);
+        table[0][index] = start.north_east().0 | start.north_west().0;
+        table[1][index] = start.south_east().0 | start.south_west().0;
+        index += 1;
+    }
+    table
+}
@@ -0,0 +1,234 @@
+use std::fmt;
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+
+use crate::square::Square;
+use crate::utils::*;
+
+/// A set of squares, stored as one bit per square in the same layout as the bitboards of `Fen`.
+///
+/// The shift helpers mask out the squares that would wrap around to the other side of the board,
+/// so for example a knight on the h-file can never end up on the a-file.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Bitboard(pub u64);
+
+impl Bitboard {
+    pub const EMPTY: Bitboard = Bitboard(EMPTY);
+    pub const FULL: Bitboard = Bitboard(!EMPTY);
+
+    pub const fn from_square(square: Square) -> Bitboard {
+        Bitboard(square.bit())
+    }
+
+    pub const fn is_empty(self) -> bool {
+        self.0 == EMPTY
+    }
+
+    pub const fn contains(self, square: Square) -> bool {
+        self.0 & square.bit() != 0
+    }
+
+    /// Returns the number of squares in the set.
+    pub const fn popcount(self) -> u32 {
+        self.0.count_ones()
+    }
+
+    pub fn set(&mut self, square: Square) {
+        self.0 |= square.bit();
+    }
+
+    pub fn clear(&mut self, square: Square) {
+        self.0 &= !square.bit();
+    }
+
+    /// Returns the square with the lowest index, or `None` if the set is empty.
+    pub const fn lsb(self) -> Option<Square> {
+        if self.0 == EMPTY {
+            None
+        } else {
+            Square::from_index(self.0.trailing_zeros() as u8)
+        }
+    }
+
+    /// Removes the square with the lowest index from the set and returns it.
+    pub fn pop_lsb(&mut self) -> Option<Square> {
+        let square: Option<Square> = self.lsb();
+        self.0 &= self.0.wrapping_sub(1);
+        square
+    }
+
+    /// Shifts every square `files` files to the right (towards h) and `ranks` ranks up (towards 8).
+    ///
+    /// Squares that would leave the board, including those that would wrap around to the other side, are removed.
+    pub const fn shift(self, files: i8, ranks: i8) -> Bitboard {
+        let mut board: u64 = self.0;
+
+        // remove the squares that would cross the left or right edge
+        let mut file: usize = 0;
+        while file < 8 {
+            let target: i8 = file as i8 + files;
+            if target < 0 || target > 7 {
+                board &= !FILES[file];
+            }
+            file += 1;
+        }
+
+        // moving right lowers the index by one, moving up raises it by eight
+        let delta: i32 = ranks as i32 * 8 - files as i32;
+        if delta >= 64 || delta <= -64 {
+            return Bitboard::EMPTY;
+        }
+
+        if delta >= 0 {
+            Bitboard(board << delta)
+        } else {
+            Bitboard(board >> -delta)
+        }
+    }
+
+    pub const fn north(self) -> Bitboard {
+        Bitboard(self.0 << 8)
+    }
+
+    pub const fn south(self) -> Bitboard {
+        Bitboard(self.0 >> 8)
+    }
+
+    pub const fn east(self) -> Bitboard {
+        Bitboard((self.0 >> 1) & !FILE_0)
+    }
+
+    pub const fn west(self) -> Bitboard {
+        Bitboard((self.0 << 1) & !FILE_7)
+    }
+
+    pub const fn north_east(self) -> Bitboard {
+        self.east().north()
+    }
+
+    pub const fn north_west(self) -> Bitboard {
+        self.west().north()
+    }
+
+    pub const fn south_east(self) -> Bitboard {
+        self.east().south()
+    }
+
+    pub const fn south_west(self) -> Bitboard {
+        self.west().south()
+    }
+}
+
+impl From<u64> for Bitboard {
+    fn from(board: u64) -> Self {
+        Bitboard(board)
+    }
+}
+
+impl From<Bitboard> for u64 {
+    fn from(board: Bitboard) -> Self {
+        board.0
+    }
+}
+
+impl From<Square> for Bitboard {
+    fn from(square: Square) -> Self {
+        Bitboard::from_square(square)
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Bitboard;
+
+    fn bitand(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 & rhs.0)
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Bitboard;
+
+    fn bitor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 | rhs.0)
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Bitboard;
+
+    fn bitxor(self, rhs: Bitboard) -> Bitboard {
+        Bitboard(self.0 ^ rhs.0)
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Bitboard;
+
+    fn not(self) -> Bitboard {
+        Bitboard(!self.0)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, rhs: Bitboard) {
+        self.0 &= rhs.0;
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, rhs: Bitboard) {
+        self.0 |= rhs.0;
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, rhs: Bitboard) {
+        self.0 ^= rhs.0;
+    }
+}
+
+/// Iterator over the squares of a `Bitboard`, created by `Bitboard::into_iter`.
+pub struct BitboardIter(Bitboard);
+
+/// Yields the squares in the set, from the lowest index to the highest.
+impl Iterator for BitboardIter {
+    type Item = Square;
+
+    fn next(&mut self) -> Option<Square> {
+        self.0.pop_lsb()
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count: usize = self.0.popcount() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for BitboardIter {}
+
+impl IntoIterator for Bitboard {
+    type Item = Square;
+    type IntoIter = BitboardIter;
+
+    fn into_iter(self) -> BitboardIter {
+        BitboardIter(self)
+    }
+}
+
+/// Formats the set as an 8x8 grid seen from white's side, with 'X' for the squares in the set.
+impl fmt::Display for Bitboard {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for rank in (0..8).rev() {
+            write!(f, "{}", rank + 1)?;
+            for file in 0..8 {
+                let square: Option<Square> = Square::new(file, rank);
+                let symbol: char = match square {
+                    Some(square) if self.contains(square) => 'X',
+                    _ => '.',
+                };
+                write!(f, " {}", symbol)?;
+            }
+            writeln!(f)?;
+        }
+        write!(f, "  a b c d e f g h")
+    }
+}
@@ -0,0 +1,175 @@
+use std::fmt;
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::time::Duration;
+
+use crate::Fen;
+use crate::chess_move::Move;
+
+/// The score of checkmating the opponent, a mate in `n` plies scores `MATE_SCORE - n` and being mated in `n` plies `n - MATE_SCORE`.
+pub const MATE_SCORE: i32 = 30_000;
+
+/// The deepest ply a search reaches, so every score beyond `MATE_SCORE - MAX_PLY` is a mate.
+pub const MAX_PLY: u32 = 128;
+
+/// The limits of a single search, as given by the "go" command of UCI.
+///
+/// Every limit that is set ends the search when it is reached, the clocks are turned into a time budget
+/// by the `TimeManager`. Without any limit the search only ends when the bot is stopped.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct SearchLimits {
+    pub depth: Option<u32>,
+    pub nodes: Option<u64>,
+
+    /// Search exactly this long.
+    pub movetime: Option<Duration>,
+
+    /// The time left on the clocks of white and black.
+    pub wtime: Option<Duration>,
+    pub btime: Option<Duration>,
+
+    /// The time added to the clocks of white and black after every move.
+    pub winc: Duration,
+    pub binc: Duration,
+
+    /// The number of moves until the next time control, `None` if the rest of the game has to be played on the clock.
+    pub movestogo: Option<u32>,
+
+    /// Keep searching until stopped, the other limits are ignored.
+    pub infinite: bool,
+
+    /// The time kept in reserve on every move for the communication with the interface.
+    pub move_overhead: Duration,
+}
+
+impl SearchLimits {
+    /// Creates limits that search for exactly `movetime`.
+    pub fn movetime(movetime: Duration) -> SearchLimits {
+        SearchLimits { movetime: Some(movetime), ..SearchLimits::default() }
+    }
+
+    /// Creates limits that search until `depth` is completed.
+    pub fn depth(depth: u32) -> SearchLimits {
+        SearchLimits { depth: Some(depth), ..SearchLimits::default() }
+    }
+
+    /// Checks whether any limit is set, a search without limits only ends when the bot is stopped.
+    pub fn is_limited(&self) -> bool {
+        !self.infinite && (self.depth.is_some() || self.nodes.is_some() || self.movetime.is_some() || self.wtime.is_some() || self.btime.is_some())
+    }
+}
+
+/// What a bot found after completing a depth of its search.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchInfo {
+    pub depth: u32,
+
+    /// The score in centipawns from the view of the side to move, see `MATE_SCORE` for mates.
+    pub score: i32,
+
+    /// The number of positions searched so far.
+    pub nodes: u64,
+
+    /// The best line found, it starts with the best move.
+    pub pv: Vec<Move>,
+
+    /// How full the transposition table of the bot is in permill, `None` if the bot has none.
+    pub hashfull: Option<u32>,
+}
+
+impl SearchInfo {
+    /// Returns the number of moves until mate if the score is a mate, negative if the side to move gets mated.
+    pub fn mate(&self) -> Option<i32> {
+        let plies: i32 = MATE_SCORE - self.score.abs();
+        if plies > MAX_PLY as i32 {
+            return None;
+        }
+
+        let moves: i32 = (plies + 1) / 2;
+        Some(if self.score > 0 { moves } else { -moves })
+    }
+}
+
+/// The outcome of `Bot::choose_move`: the move to play and the last completed depth that led to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SearchResult {
+    /// The move to play, `Move::NULL` if there are no legal moves.
+    pub best_move: Move,
+
+    /// The report of the last completed depth, `None` if the search was stopped before completing one.
+    pub info: Option<SearchInfo>,
+}
+
+/// A setting a bot reports, so front ends can show it to the user, e.g. as a UCI "spin" option.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct BotOption {
+    pub name: &'static str,
+    pub default: i64,
+    pub min: i64,
+    pub max: i64,
+}
+
+/// Errors from changing the settings of a bot with `Bot::set_option`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum OptionError {
+    /// The bot does not have a setting with this name.
+    Unknown(String),
+
+    /// The value is outside the range of the setting.
+    BadValue(String, i64),
+}
+
+impl fmt::Display for OptionError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OptionError::Unknown(name) => write!(f, "unknown option '{}'", name),
+            OptionError::BadValue(name, value) => write!(f, "value {} is out of range for option '{}'", value, name),
+        }
+    }
+}
+
+impl std::error::Error for OptionError {}
+
+/// A chess engine that match runners, protocol front ends and the Python bindings can drive without knowing which bot it is.
+///
+/// The bot keeps its own position: set it with `Bot::set_position` and keep it up to date with `Bot::apply_move`,
+/// `Bot::choose_move` only searches and does not play the move it returns.
+pub trait Bot: Send + Sync {
+    /// The name the bot reports to the user, e.g. "BotV1".
+    fn name(&self) -> &str;
+
+    /// Returns the settings of the bot, see `Bot::set_option`.
+    fn options(&self) -> Vec<BotOption> {
+        Vec::new()
+    }
+
+    /// Changes one of the settings reported by `Bot::options`.
+    fn set_option(&mut self, name: &str, _value: i64) -> Result<(), OptionError> {
+        Err(OptionError::Unknown(name.to_string()))
+    }
+
+    /// Forgets what the bot remembers from earlier searches, e.g. its transposition table, when a new game starts.
+    fn new_game(&mut self) {}
+
+    /// Replaces the position the bot searches.
+    fn set_position(&mut self, fen: Fen);
+
+    /// Plays a move in the position of the bot.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    fn apply_move(&mut self, move1: Move);
+
+    /// Searches the position until one of the limits is reached or the bot is stopped,
+    /// calling `report` after every completed depth. Bots manage their time with a `TimeManager`.
+    fn choose_move(&mut self, limits: &SearchLimits, report: &mut dyn FnMut(&SearchInfo)) -> SearchResult;
+
+    /// Returns the flag that ends a running `Bot::choose_move` when it is set, so it can be stopped from another thread.
+    ///
+    /// **NOTE:** the flag is not cleared when a search starts, clear it before starting the next search
+    fn stop_signal(&self) -> Arc<AtomicBool>;
+
+    /// Makes a running `Bot::choose_move` return the best move it found so far.
+    fn stop(&self) {
+        self.stop_signal().store(true, Ordering::Relaxed);
+    }
+}
@@ -0,0 +1,201 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::FenError;
+use crate::parsing;
+use crate::square::Square;
+use crate::utils::*;
+
+/// A chess move packed into 16 bits.
+///
+/// Layout (least significant bit first):
+/// * bits 0-5: index of the start square
+/// * bits 6-11: index of the end square
+/// * bits 12-15: flags describing the kind of move, see the associated constants
+///
+/// Square indices are the bit positions used by the bitboards in `Fen`, so `1 << index` is the
+/// bitboard of the square (h1 = 0, a1 = 7, h8 = 56, a8 = 63).
+///
+/// The flags follow the encoding from https://www.chessprogramming.org/Encoding_Moves: bit 12 and 13
+/// distinguish the special moves, bit 14 marks a capture and bit 15 marks a promotion.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub struct Move(u16);
+
+impl Move {
+    pub const QUIET: u16 = 0;
+    pub const DOUBLE_PUSH: u16 = 1;
+    pub const KING_CASTLE: u16 = 2;
+    pub const QUEEN_CASTLE: u16 = 3;
+    pub const CAPTURE: u16 = 4;
+    pub const EN_PASSANT: u16 = 5;
+    pub const KNIGHT_PROMOTION: u16 = 8;
+    pub const BISHOP_PROMOTION: u16 = 9;
+    pub const ROOK_PROMOTION: u16 = 10;
+    pub const QUEEN_PROMOTION: u16 = 11;
+    pub const KNIGHT_PROMOTION_CAPTURE: u16 = 12;
+    pub const BISHOP_PROMOTION_CAPTURE: u16 = 13;
+    pub const ROOK_PROMOTION_CAPTURE: u16 = 14;
+    pub const QUEEN_PROMOTION_CAPTURE: u16 = 15;
+
+    /// The null move, it moves from h1 to h1 and is never legal.
+    pub const NULL: Move = Move(0);
+
+    /// Creates a move from a start square index, an end square index and flags.
+    pub const fn new(start: u8, end: u8, flags: u16) -> Self {
+        Self((start as u16 & 0x3F) | ((end as u16 & 0x3F) << 6) | ((flags & 0xF) << 12))
+    }
+
+    /// Creates a move between two squares with the given flags.
+    pub const fn from_squares(start: Square, end: Square, flags: u16) -> Self {
+        Self::new(start.index(), end.index(), flags)
+    }
+
+    /// Creates a move from its 16 bit representation.
+    pub const fn from_raw(raw: u16) -> Self {
+        Self(raw)
+    }
+
+    /// Returns the 16 bit representation of the move.
+    pub const fn raw(self) -> u16 {
+        self.0
+    }
+
+    /// Returns the index of the start square.
+    pub const fn start_index(self) -> u8 {
+        (self.0 & 0x3F) as u8
+    }
+
+    /// Returns the index of the end square.
+    pub const fn end_index(self) -> u8 {
+        ((self.0 >> 6) & 0x3F) as u8
+    }
+
+    /// Returns the start square.
+    pub const fn start_square(self) -> Square {
+        Square::ALL[self.start_index() as usize]
+    }
+
+    /// Returns the end square.
+    pub const fn end_square(self) -> Square {
+        Square::ALL[self.end_index() as usize]
+    }
+
+    /// Returns the bitboard of the start square.
+    pub const fn start(self) -> u64 {
+        1u64 << self.start_index()
+    }
+
+    /// Returns the bitboard of the end square.
+    pub const fn end(self) -> u64 {
+        1u64 << self.end_index()
+    }
+
+    /// Returns the 4 bit flags of the move.
+    pub const fn flags(self) -> u16 {
+        self.0 >> 12
+    }
+
+    pub const fn is_null(self) -> bool {
+        self.0 == Self::NULL.0
+    }
+
+    pub const fn is_capture(self) -> bool {
+        self.flags() & Self::CAPTURE != 0
+    }
+
+    pub const fn is_double_push(self) -> bool {
+        self.flags() == Self::DOUBLE_PUSH
+    }
+
+    pub const fn is_en_passant(self) -> bool {
+        self.flags() == Self::EN_PASSANT
+    }
+
+    pub const fn is_castle(self) -> bool {
+        self.flags() == Self::KING_CASTLE || self.flags() == Self::QUEEN_CASTLE
+    }
+
+    pub const fn is_promotion(self) -> bool {
+        self.flags() & Self::KNIGHT_PROMOTION != 0
+    }
+
+    /// Returns the promotion flag from `utils` (e.g. `QUEEN_PROM`), or `NO_PROM` if the move is not a promotion.
+    pub const fn promotion(self) -> u64 {
+        if !self.is_promotion() {
+            return NO_PROM;
+        }
+
+        match self.flags() & 0x3 {
+            0 => KNIGHT_PROM,
+            1 => BISHOP_PROM,
+            2 => ROOK_PROM,
+            _ => QUEEN_PROM,
+        }
+    }
+
+    /// Returns the flags of a promotion to the given piece, `promoting_to` is a promotion flag from `utils`.
+    pub const fn promotion_flags(promoting_to: u64, capture: bool) -> u16 {
+        let base: u16 = match promoting_to {
+            QUEEN_PROM => Self::QUEEN_PROMOTION,
+            ROOK_PROM => Self::ROOK_PROMOTION,
+            BISHOP_PROM => Self::BISHOP_PROMOTION,
+            KNIGHT_PROM => Self::KNIGHT_PROMOTION,
+            _ => return if capture { Self::CAPTURE } else { Self::QUIET },
+        };
+
+        if capture {
+            base | Self::CAPTURE
+        } else {
+            base
+        }
+    }
+
+    /// Converts a legacy move triple (start bit, end bit, promotion) into a move.
+    ///
+    /// **NOTE:** the triple does not tell whether the move is a capture, double push, en passant
+    /// or castle, so only the promotion is encoded. Use `Fen::move_from_legacy` to get a move with all flags set.
+    pub fn from_legacy(move1: &[u64; 3]) -> Self {
+        let start: u8 = move1[0].trailing_zeros() as u8;
+        let end: u8 = move1[1].trailing_zeros() as u8;
+
+        Self::new(start, end, Self::promotion_flags(move1[2], false))
+    }
+
+    /// Converts the move into a legacy move triple (start bit, end bit, promotion).
+    pub const fn to_legacy(self) -> [u64; 3] {
+        [self.start(), self.end(), self.promotion()]
+    }
+}
+
+impl From<Move> for [u64; 3] {
+    fn from(move1: Move) -> Self {
+        move1.to_legacy()
+    }
+}
+
+/// Formats the move in long algebraic notation (LAN), e.g. "e2e4" or "e7e8q".
+impl fmt::Display for Move {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", self.start_square(), self.end_square())?;
+
+        match self.promotion() {
+            QUEEN_PROM => write!(f, "q"),
+            ROOK_PROM => write!(f, "r"),
+            BISHOP_PROM => write!(f, "b"),
+            KNIGHT_PROM => write!(f, "n"),
+            _ => Ok(()),
+        }
+    }
+}
+
+/// Parses a move in long algebraic notation (LAN).
+///
+/// **NOTE:** without a position only the promotion can be encoded, use `Fen::parse_lan`
+/// to get a move with all flags set.
+impl FromStr for Move {
+    type Err = FenError;
+
+    fn from_str(lan: &str) -> Result<Self, FenError> {
+        Ok(Self::from_legacy(&parsing::lan_to_move(lan)?))
+    }
+}
@@ -0,0 +1,124 @@
+use crate::Fen;
+use crate::attacks;
+use crate::bitboard::Bitboard;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::result::Reason;
+use crate::square::Square;
+
+/// The dark squares of the board, a1 is dark.
+const DARK_SQUARES: u64 = 0x55AA55AA55AA55AA;
+
+impl Fen {
+    /// Returns why the position is a draw, or `None` if it is not.
+    ///
+    /// The fifty move rule and threefold repetition only allow a player to claim a draw,
+    /// they are treated as draws anyway so that games between bots do not go on forever.
+    ///
+    /// **NOTE:** a single position knows nothing about repetitions, use `Game::result` for those
+    pub fn draw_reason(&self) -> Option<Reason> {
+        if self.legal_moves().is_empty() {
+            return if self.in_check() { None } else { Some(Reason::Stalemate) };
+        }
+
+        if self.is_insufficient_material() {
+            Some(Reason::InsufficientMaterial)
+        } else if self.is_dead_position() {
+            Some(Reason::DeadPosition)
+        } else if self.halfmove >= 150 {
+            Some(Reason::SeventyFiveMove)
+        } else if self.halfmove >= 100 {
+            Some(Reason::FiftyMove)
+        } else {
+            None
+        }
+    }
+
+    /// Checks whether neither side can checkmate with the material on the board, following the FIDE rules.
+    ///
+    /// This is the case with only kings left, a king and a single knight or bishop against a king,
+    /// or any number of bishops that all stand on squares of the same color.
+    pub fn is_insufficient_material(&self) -> bool {
+        let heavy: Bitboard = self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN)
+            | self.pieces(Piece::WHITE_ROOK) | self.pieces(Piece::BLACK_ROOK)
+            | self.pieces(Piece::WHITE_QUEEN) | self.pieces(Piece::BLACK_QUEEN);
+
+        if !heavy.is_empty() {
+            return false;
+        }
+
+        let knights: Bitboard = self.pieces(Piece::WHITE_KNIGHT) | self.pieces(Piece::BLACK_KNIGHT);
+        let bishops: Bitboard = self.pieces(Piece::WHITE_BISHOP) | self.pieces(Piece::BLACK_BISHOP);
+
+        if (knights | bishops).popcount() <= 1 {
+            return true;
+        }
+
+        // bishops on squares of one color can never attack a king on the other color
+        let on_dark: Bitboard = bishops & Bitboard(DARK_SQUARES);
+        knights.is_empty() && (on_dark.is_empty() || on_dark == bishops)
+    }
+
+    /// Checks whether no sequence of legal moves can lead to checkmate, for the positions that can be recognized cheaply.
+    ///
+    /// Besides insufficient material, this recognizes positions with only kings and pawns where every pawn is
+    /// blocked by a pawn of the opponent, no pawn can capture, and neither king can reach a pawn it could capture.
+    /// The pawns can then never move, so no check can ever be given.
+    pub fn is_dead_position(&self) -> bool {
+        if self.is_insufficient_material() {
+            return true;
+        }
+
+        let white_pawns: Bitboard = self.pieces(Piece::WHITE_PAWN);
+        let black_pawns: Bitboard = self.pieces(Piece::BLACK_PAWN);
+        let kings: Bitboard = self.pieces(Piece::WHITE_KING) | self.pieces(Piece::BLACK_KING);
+
+        if (white_pawns | black_pawns | kings) != self.occupied() || white_pawns.is_empty() {
+            return false;
+        }
+
+        let blocked: bool = white_pawns.north() == black_pawns;
+        if !blocked {
+            return false;
+        }
+
+        let white_attacks: Bitboard = white_pawns.north_east() | white_pawns.north_west();
+        let black_attacks: Bitboard = black_pawns.south_east() | black_pawns.south_west();
+        if !(white_attacks & black_pawns).is_empty() || !(black_attacks & white_pawns).is_empty() {
+            return false;
+        }
+
+        !self.king_reaches_pawn(Color::White, black_attacks) && !self.king_reaches_pawn(Color::Black, white_attacks)
+    }
+
+    /// Checks whether the king of the given color can walk to a pawn of the opponent without stepping
+    /// onto a square attacked by the opponent's pawns, `attacked` are the squares those pawns attack.
+    fn king_reaches_pawn(&self, color: Color, attacked: Bitboard) -> bool {
+        let king: Square = match self.king_square(color) {
+            Some(king) => king,
+            None => return false,
+        };
+
+        let own_pawns: Bitboard = self.pieces(Piece::new(color, PieceKind::Pawn));
+        let opponent_pawns: Bitboard = self.pieces(Piece::new(!color, PieceKind::Pawn));
+        let passable: Bitboard = !own_pawns & !attacked;
+
+        // flood fill the squares the king can reach
+        let mut reached: Bitboard = Bitboard::from(king);
+        loop {
+            let mut next: Bitboard = reached;
+            for square in reached {
+                next |= attacks::king_attacks(square) & passable;
+            }
+
+            if !(next & opponent_pawns).is_empty() {
+                return true;
+            }
+
+            if next == reached {
+                return false;
+            }
+
+            reached = next;
+        }
+    }
+}
@@ -0,0 +1,378 @@
+use std::fmt;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+use crate::Fen;
+use crate::chess_move::Move;
+use crate::error::FenError;
+
+/// Opcodes whose operands are moves in SAN that are alternatives in the position itself.
+const MOVE_OPCODES: [&str; 4] = ["bm", "am", "pm", "sm"];
+
+/// Opcodes whose operands are numbers.
+const INTEGER_OPCODES: [&str; 9] = ["acd", "acn", "acs", "ce", "dm", "fmvn", "hmvn", "rc", "pvl"];
+
+/// The operands of an EPD opcode, converted according to the opcode.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdValue {
+    /// The moves of "bm", "am", "pm" and "sm", or the line of moves of "pv".
+    Moves(Vec<Move>),
+
+    /// A single number, e.g. for "acd" or "ce".
+    Integer(i64),
+
+    /// A single string, e.g. for "id" or "c0".
+    Text(String),
+
+    /// The operands of any other opcode.
+    Tokens(Vec<String>),
+}
+
+/// Errors from reading an EPD line.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum EpdError {
+    /// The line has fewer than the four fen fields an EPD line starts with.
+    MissingFields,
+
+    /// An operation is not ended by ';' or a quoted operand is not closed.
+    UnterminatedOperation,
+
+    /// The operand does not fit the opcode, e.g. a word where "acd" expects a number.
+    BadOperand { opcode: String, operand: String },
+
+    /// The position or a move in SAN could not be read.
+    Fen(FenError),
+}
+
+impl fmt::Display for EpdError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            EpdError::MissingFields => write!(f, "invalid epd: expected 4 fields"),
+            EpdError::UnterminatedOperation => write!(f, "invalid epd: operation not ended by ';'"),
+            EpdError::BadOperand { opcode, operand } => write!(f, "invalid epd: opcode {} does not accept '{}'", opcode, operand),
+            EpdError::Fen(err) => write!(f, "invalid epd: {}", err),
+        }
+    }
+}
+
+impl std::error::Error for EpdError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            EpdError::Fen(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// A position in extended position description (EPD): the first four fields of a fen notation
+/// followed by opcodes, e.g. `r1b1k2r/... w kq - bm Nxe5; id "WAC.003";`.
+#[derive(Debug, Clone)]
+pub struct Epd {
+    /// The position, the move counters are taken from the "hmvn" and "fmvn" opcodes if they are given.
+    pub fen: Fen,
+
+    /// The opcodes in the order they were read.
+    pub opcodes: Vec<(String, EpdValue)>,
+}
+
+impl Epd {
+    /// Returns the value of the opcode with the given name.
+    pub fn get(&self, opcode: &str) -> Option<&EpdValue> {
+        self.opcodes.iter().find(|(name, _): &&(String, EpdValue)| name == opcode).map(|(_, value): &(String, EpdValue)| value)
+    }
+
+    /// Sets the value of an opcode, replacing the old value if the opcode already exists.
+    pub fn set(&mut self, opcode: &str, value: EpdValue) {
+        match self.opcodes.iter_mut().find(|(name, _): &&mut (String, EpdValue)| name == opcode) {
+            Some((_, old)) => *old = value,
+            None => self.opcodes.push((opcode.to_string(), value)),
+        }
+    }
+
+    /// Returns the moves of an opcode, empty if the opcode is missing or does not hold moves.
+    pub fn moves(&self, opcode: &str) -> &[Move] {
+        match self.get(opcode) {
+            Some(EpdValue::Moves(moves)) => moves,
+            _ => &[],
+        }
+    }
+
+    /// Returns the best moves ("bm").
+    pub fn best_moves(&self) -> &[Move] {
+        self.moves("bm")
+    }
+
+    /// Returns the moves to avoid ("am").
+    pub fn avoid_moves(&self) -> &[Move] {
+        self.moves("am")
+    }
+
+    /// Returns the name of the position ("id").
+    pub fn id(&self) -> Option<&str> {
+        match self.get("id") {
+            Some(EpdValue::Text(id)) => Some(id),
+            _ => None,
+        }
+    }
+
+    /// Checks whether the move solves the position: it is one of the best moves and none of the moves to avoid.
+    ///
+    /// A position without best moves or moves to avoid cannot be solved.
+    pub fn is_solved_by(&self, move1: Move) -> bool {
+        let best: &[Move] = self.best_moves();
+        let avoid: &[Move] = self.avoid_moves();
+
+        if best.is_empty() && avoid.is_empty() {
+            return false;
+        }
+
+        (best.is_empty() || best.contains(&move1)) && !avoid.contains(&move1)
+    }
+}
+
+/// Parses an EPD line.
+///
+/// A full fen notation with move counters is accepted as well, as some test suites are written that way.
+impl FromStr for Epd {
+    type Err = EpdError;
+
+    fn from_str(line: &str) -> Result<Self, EpdError> {
+        let mut rest: &str = line.trim();
+        let mut fields: Vec<&str> = Vec::new();
+        while fields.len() < 4 {
+            let (field, after): (&str, &str) = next_field(rest);
+            if field.is_empty() {
+                return Err(EpdError::MissingFields);
+            }
+            fields.push(field);
+            rest = after;
+        }
+
+        let mut counters: [&str; 2] = ["0", "1"];
+        let (halfmove, after_halfmove): (&str, &str) = next_field(rest);
+        let (fullmove, after_fullmove): (&str, &str) = next_field(after_halfmove);
+        if halfmove.parse::<u16>().is_ok() && fullmove.parse::<u16>().is_ok() {
+            counters = [halfmove, fullmove];
+            rest = after_fullmove;
+        }
+
+        let operations: Vec<(String, Vec<String>)> = split_operations(rest).ok_or(EpdError::UnterminatedOperation)?;
+
+        // the counters in the opcodes take precedence
+        for (opcode, operands) in &operations {
+            match (opcode.as_str(), operands.as_slice()) {
+                ("hmvn", [halfmove]) => counters[0] = halfmove,
+                ("fmvn", [fullmove]) => counters[1] = fullmove,
+                _ => {},
+            }
+        }
+
+        let fen: Fen = format!("{} {} {}", fields.join(" "), counters[0], counters[1]).parse().map_err(EpdError::Fen)?;
+
+        let mut opcodes: Vec<(String, EpdValue)> = Vec::new();
+        for (opcode, operands) in operations {
+            let value: EpdValue = to_value(&fen, &opcode, operands)?;
+            opcodes.push((opcode, value));
+        }
+
+        Ok(Epd { fen, opcodes })
+    }
+}
+
+/// Formats the position as an EPD line, with the moves in SAN.
+impl fmt::Display for Epd {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let fen: String = self.fen.to_string();
+        let fields: Vec<&str> = fen.split(' ').take(4).collect();
+        write!(f, "{}", fields.join(" "))?;
+
+        for (opcode, value) in &self.opcodes {
+            write!(f, " {}", opcode)?;
+
+            match value {
+                EpdValue::Moves(moves) if opcode == "pv" => {
+                    let mut fen: Fen = self.fen.clone();
+                    for move1 in moves {
+                        write!(f, " {}", fen.move_to_san(move1))?;
+                        fen.make_move(*move1);
+                    }
+                },
+                EpdValue::Moves(moves) => {
+                    for move1 in moves {
+                        write!(f, " {}", self.fen.move_to_san(move1))?;
+                    }
+                },
+                EpdValue::Integer(number) => write!(f, " {}", number)?,
+                EpdValue::Text(text) => write!(f, " \"{}\"", text)?,
+                EpdValue::Tokens(tokens) => {
+                    for token in tokens {
+                        write!(f, " {}", token)?;
+                    }
+                },
+            }
+
+            write!(f, ";")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Splits off the first whitespace separated field, returning it and the rest without leading whitespace.
+fn next_field(text: &str) -> (&str, &str) {
+    let (field, rest): (&str, &str) = text.split_once(char::is_whitespace).unwrap_or((text, ""));
+    (field, rest.trim_start())
+}
+
+/// Splits the opcodes of an EPD line into their names and operands, or returns `None` if an operation is not ended by ';'.
+fn split_operations(text: &str) -> Option<Vec<(String, Vec<String>)>> {
+    let mut operations: Vec<(String, Vec<String>)> = Vec::new();
+    let mut chars = text.chars().peekable();
+
+    loop {
+        while chars.next_if(|ch: &char| ch.is_whitespace()).is_some() {}
+
+        let mut opcode: String = String::new();
+        while let Some(ch) = chars.next_if(|ch: &char| !ch.is_whitespace() && *ch != ';') {
+            opcode.push(ch);
+        }
+
+        if opcode.is_empty() {
+            return match chars.peek() {
+                None => Some(operations),
+                Some(_) => None,
+            };
+        }
+
+        let mut operands: Vec<String> = Vec::new();
+        loop {
+            while chars.next_if(|ch: &char| ch.is_whitespace()).is_some() {}
+
+            match chars.next()? {
+                ';' => break,
+                '"' => {
+                    let mut operand: String = String::new();
+                    loop {
+                        match chars.next()? {
+                            '"' => break,
+                            ch => operand.push(ch),
+                        }
+                    }
+                    operands.push(operand);
+                },
+                first => {
+                    let mut operand: String = first.to_string();
+                    while let Some(ch) = chars.next_if(|ch: &char| !ch.is_whitespace() && *ch != ';') {
+                        operand.push(ch);
+                    }
+                    operands.push(operand);
+                },
+            }
+        }
+
+        operations.push((opcode, operands));
+    }
+}
+
+/// Converts the operands of an opcode to its value, moves in SAN are converted using the position.
+fn to_value(fen: &Fen, opcode: &str, operands: Vec<String>) -> Result<EpdValue, EpdError> {
+    if MOVE_OPCODES.contains(&opcode) {
+        let moves: Vec<Move> = operands.iter().map(|san: &String| fen.san_to_move(san)).collect::<Result<Vec<Move>, FenError>>().map_err(EpdError::Fen)?;
+        return Ok(EpdValue::Moves(moves));
+    }
+
+    if opcode == "pv" {
+        let mut fen: Fen = fen.clone();
+        let mut moves: Vec<Move> = Vec::new();
+        for san in &operands {
+            let move1: Move = fen.san_to_move(san).map_err(EpdError::Fen)?;
+            fen.make_move(move1);
+            moves.push(move1);
+        }
+        return Ok(EpdValue::Moves(moves));
+    }
+
+    if let [operand] = operands.as_slice() {
+        if INTEGER_OPCODES.contains(&opcode) {
+            let number: i64 = operand.parse().map_err(|_| EpdError::BadOperand { opcode: opcode.to_string(), operand: operand.clone() })?;
+            return Ok(EpdValue::Integer(number));
+        }
+
+        return Ok(EpdValue::Text(operand.clone()));
+    }
+
+    Ok(EpdValue::Tokens(operands))
+}
+
+/// Parses every position of an EPD file, skipping empty lines and lines starting with '#'.
+pub fn read_epd(text: &str) -> Result<Vec<Epd>, EpdError> {
+    text.lines()
+        .map(str::trim)
+        .filter(|line: &&str| !line.is_empty() && !line.starts_with('#'))
+        .map(str::parse)
+        .collect()
+}
+
+/// The outcome of a single position of a test suite.
+#[derive(Debug, Clone)]
+pub struct SuiteResult {
+    /// The name of the position, or its index in the suite if it has no "id".
+    pub id: String,
+
+    /// The move the bot chose.
+    pub found: Move,
+
+    pub solved: bool,
+    pub time: Duration,
+}
+
+/// The outcomes of running a test suite, see `run_suite`.
+#[derive(Debug, Clone, Default)]
+pub struct SuiteReport {
+    pub results: Vec<SuiteResult>,
+}
+
+impl SuiteReport {
+    /// Returns the number of solved positions.
+    pub fn solved(&self) -> usize {
+        self.results.iter().filter(|result: &&SuiteResult| result.solved).count()
+    }
+
+    pub fn total(&self) -> usize {
+        self.results.len()
+    }
+}
+
+/// Formats the report with a line per position, followed by the number of solved positions.
+impl fmt::Display for SuiteReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for result in &self.results {
+            let verdict: &str = if result.solved { "solved" } else { "failed" };
+            writeln!(f, "{}: {} {} in {:?}", result.id, verdict, result.found, result.time)?;
+        }
+
+        write!(f, "solved {} of {} positions", self.solved(), self.total())
+    }
+}
+
+/// Runs a test suite: `search` gets every position with the time it may use and returns the move it chose.
+pub fn run_suite<F>(positions: &[Epd], time: Duration, mut search: F) -> SuiteReport
+where
+    F: FnMut(&Fen, Duration) -> Move,
+{
+    let mut report: SuiteReport = SuiteReport::default();
+
+    for (index, epd) in positions.iter().enumerate() {
+        let start: Instant = Instant::now();
+        let found: Move = search(&epd.fen, time);
+
+        report.results.push(SuiteResult {
+            id: epd.id().map_or_else(|| (index + 1).to_string(), str::to_string),
+            found,
+            solved: epd.is_solved_by(found),
+            time: start.elapsed(),
+        });
+    }
+
+    report
+}
@@ -0,0 +1,82 @@
+use std::fmt;
+
+use crate::validation::Violation;
+
+/// Errors that can occur while parsing a FEN string, a tile or a move in LAN or SAN.
+///
+/// Every variant carries the offending part of the input, so the message shown to the user
+/// points at what was wrong instead of only saying that parsing failed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    /// The FEN string did not consist of exactly six whitespace separated fields.
+    WrongFieldCount(usize),
+
+    /// The board field did not consist of exactly eight ranks separated by '/'.
+    WrongRankCount(usize),
+
+    /// A rank in the board field did not describe exactly eight files.
+    BadRankLength(String),
+
+    /// A character in the board field is neither a piece nor a digit from 1 to 8.
+    UnknownPiece(char),
+
+    /// The side to move field was not "w" or "b".
+    BadSideToMove(String),
+
+    /// The castling field was not "-" or a combination of "KQkq" without repetitions.
+    BadCastling(String),
+
+    /// The en passant field was not "-" or a tile on rank 3 or 6.
+    BadEnPassant(String),
+
+    /// The halfmove or fullmove counter was not a valid number.
+    BadCounter(String),
+
+    /// A tile was not in algebraic notation (e.g. "e4").
+    BadTile(String),
+
+    /// A move was not in LAN (e.g. "e2e4" or "e7e8q").
+    BadMove(String),
+
+    /// The promotion character of a move was not one of "qrbn".
+    BadPromotion(char),
+
+    /// A move was not in SAN (e.g. "Nf3", "exd8=Q+" or "O-O").
+    BadSan(String),
+
+    /// A move in SAN does not describe any legal move in the position.
+    IllegalSan(String),
+
+    /// A move in SAN describes more than one legal move, the start square is not given precisely enough.
+    AmbiguousSan(String),
+
+    /// The fen notation is valid, but the position it describes cannot occur in a legal game.
+    InvalidPosition(Vec<Violation>),
+}
+
+impl fmt::Display for FenError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            FenError::WrongFieldCount(count) => write!(f, "expected 6 fields in fen notation, found {}", count),
+            FenError::WrongRankCount(count) => write!(f, "expected 8 ranks in fen board, found {}", count),
+            FenError::BadRankLength(rank) => write!(f, "rank '{}' does not describe exactly 8 files", rank),
+            FenError::UnknownPiece(piece) => write!(f, "unknown piece char '{}'", piece),
+            FenError::BadSideToMove(turn) => write!(f, "unknown side to move '{}', expected 'w' or 'b'", turn),
+            FenError::BadCastling(castling) => write!(f, "invalid castling rights '{}'", castling),
+            FenError::BadEnPassant(enpassant) => write!(f, "invalid en passant square '{}'", enpassant),
+            FenError::BadCounter(counter) => write!(f, "invalid move counter '{}'", counter),
+            FenError::BadTile(tile) => write!(f, "invalid tile '{}'", tile),
+            FenError::BadMove(lan) => write!(f, "invalid move '{}', expected LAN such as 'e2e4' or 'e7e8q'", lan),
+            FenError::BadPromotion(piece) => write!(f, "invalid promotion piece '{}'", piece),
+            FenError::BadSan(san) => write!(f, "invalid move '{}', expected SAN such as 'Nf3' or 'exd8=Q'", san),
+            FenError::IllegalSan(san) => write!(f, "move '{}' is not legal in this position", san),
+            FenError::AmbiguousSan(san) => write!(f, "move '{}' is ambiguous in this position", san),
+            FenError::InvalidPosition(violations) => {
+                let reasons: Vec<String> = violations.iter().map(|violation: &Violation| violation.to_string()).collect();
+                write!(f, "invalid position: {}", reasons.join("; "))
+            },
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
@@ -0,0 +1,123 @@
+use crate::Fen;
+use crate::chess_move::Move;
+use crate::error::FenError;
+use crate::piece::Color;
+use crate::result::{GameResult, Reason};
+use crate::undo::Undo;
+
+/// A position together with the moves that led to it.
+///
+/// `Fen` only describes the current position, a game also remembers the hash of every earlier position,
+/// which is needed to detect repetitions, and can take back its moves.
+#[derive(Debug, Clone)]
+pub struct Game {
+    fen: Fen,
+    moves: Vec<(Move, Undo)>,
+
+    /// The hash of every position of the game, the first one is the starting position.
+    keys: Vec<u64>,
+
+    /// The result of a game that ended without a move, e.g. by resignation, see `Game::end`.
+    ended: Option<GameResult>,
+}
+
+impl Default for Game {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Game {
+    /// Creates a game from the default starting position.
+    pub fn new() -> Self {
+        Self::from_fen(Fen::new())
+    }
+
+    /// Creates a game starting from the given position, earlier positions are unknown.
+    pub fn from_fen(fen: Fen) -> Self {
+        let keys: Vec<u64> = vec![fen.hash()];
+        Self { fen, moves: Vec::new(), keys, ended: None }
+    }
+
+    /// Returns the current position.
+    pub fn fen(&self) -> &Fen {
+        &self.fen
+    }
+
+    /// Returns the moves played since the starting position.
+    pub fn moves(&self) -> Vec<Move> {
+        self.moves.iter().map(|(move1, _): &(Move, Undo)| *move1).collect()
+    }
+
+    /// Plays the move and records the new position.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn make_move(&mut self, move1: Move) {
+        let undo: Undo = self.fen.make_move(move1);
+        self.moves.push((move1, undo));
+        self.keys.push(self.fen.hash());
+    }
+
+    /// Plays the move in LAN, see `Game::make_move`.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal, only whether it can be parsed
+    pub fn make_move_lan(&mut self, lan: &str) -> Result<(), FenError> {
+        let move1: Move = self.fen.parse_lan(lan)?;
+        self.make_move(move1);
+        Ok(())
+    }
+
+    /// Takes back the last move and returns it, or `None` if no move has been played.
+    pub fn undo(&mut self) -> Option<Move> {
+        let (move1, undo): (Move, Undo) = self.moves.pop()?;
+        self.fen.unmake_move(move1, undo);
+        self.keys.pop();
+        self.ended = None;
+        Some(move1)
+    }
+
+    /// Returns how often the current position has occurred, including now.
+    ///
+    /// Only the positions since the last capture or pawn move are compared, earlier positions cannot be repeated.
+    pub fn repetitions(&self) -> usize {
+        let hash: u64 = self.fen.hash();
+        let reversible: usize = (self.fen.halfmove as usize).min(self.keys.len() - 1);
+
+        // the same position also needs the same side to move, so only every second position is compared
+        self.keys.iter().rev().take(reversible + 1).step_by(2).filter(|&&key: &&u64| key == hash).count()
+    }
+
+    /// Checks whether the current position has occurred at least three times, which allows a player to claim a draw.
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.repetitions() >= 3
+    }
+
+    /// Checks whether the current position has occurred at least five times, which ends the game in a draw.
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.repetitions() >= 5
+    }
+
+    /// Ends the game with a result that does not follow from the position, like a resignation or a timeout.
+    pub fn end(&mut self, result: GameResult) {
+        self.ended = Some(result);
+    }
+
+    /// Ends the game with the given color resigning.
+    pub fn resign(&mut self, color: Color) {
+        self.end(GameResult::win(!color, Reason::Resignation));
+    }
+
+    /// Returns the result of the game, like `Fen::result` but with repetitions and `Game::end` taken into account.
+    pub fn result(&self) -> GameResult {
+        if let Some(result) = self.ended {
+            return result;
+        }
+
+        let result: GameResult = self.fen.result();
+        if result.is_ongoing() && self.is_threefold_repetition() {
+            GameResult::draw(Reason::Repetition)
+        } else {
+            result
+        }
+    }
+}
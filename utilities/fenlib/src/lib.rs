@@ -1,12 +1,41 @@
+pub mod attacks;
+pub mod bitboard;
+pub mod bot;
+pub mod chess_move;
+pub mod draw;
+pub mod epd;
+pub mod error;
+pub mod game;
+pub mod movegen;
+pub mod ordering;
 pub mod parsing;
-pub mod moves;
+pub mod pgn;
+pub mod perft;
+pub mod piece;
+pub mod result;
+pub mod san;
+pub mod see;
+pub mod square;
+pub mod time_manager;
+pub mod transposition;
+pub mod undo;
 pub mod utils;
+pub mod validation;
+pub mod zobrist;
 
+use std::fmt;
+use std::str::FromStr;
+
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::error::FenError;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
 use crate::utils::*;
 
 pub const MAX_MOVES: usize = 28;
 
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Fen {
     pub boards: [u64; 12],
     pub white_to_move: bool,
@@ -17,39 +46,53 @@ pub struct Fen {
     pub white: u64,
     pub black: u64,
     pub full: u64,
+
+    /// The Zobrist hash of the position, kept up to date by `Fen::move_to_fen`, see `Fen::hash`.
+    hash: u64,
 }
 
-impl Fen {
-    pub fn new() -> Self {
-        Self::from_str(utils::DEFAULT)
+impl Default for Fen {
+    fn default() -> Self {
+        Self::new()
     }
+}
+
+impl FromStr for Fen {
+    type Err = FenError;
 
-    pub fn from_str(fen_str: &str) -> Self {
-        let fen_parts: Vec<&str> = fen_str.trim().split_whitespace().collect();
+    fn from_str(fen_str: &str) -> Result<Self, FenError> {
+        let fen_parts: Vec<&str> = fen_str.split_whitespace().collect();
         if fen_parts.len() != 6 {
-            panic!("Found incorrect fen notation");
+            return Err(FenError::WrongFieldCount(fen_parts.len()));
         }
 
-        let boards: [u64; 12] = parsing::string_to_board(fen_parts[0]);
+        let boards: [u64; 12] = parsing::string_to_board(fen_parts[0])?;
         let white: u64 = get_white(&boards);
         let black: u64 = get_black(&boards);
         let full: u64 = white | black;
 
-        Self {
+        let mut fen: Self = Self {
             boards,
-            white_to_move: parsing::string_to_turn(fen_parts[1]),
-            castling: parsing::string_to_castling(fen_parts[2]),
-            en_passant: parsing::string_to_enpassant(fen_parts[3]),
-            halfmove: fen_parts[4].parse().ok().unwrap(),
-            fullmove: fen_parts[5].parse().ok().unwrap(),
+            white_to_move: parsing::string_to_turn(fen_parts[1])?,
+            castling: parsing::string_to_castling(fen_parts[2])?,
+            en_passant: parsing::string_to_enpassant(fen_parts[3])?,
+            halfmove: parsing::string_to_counter(fen_parts[4])?,
+            fullmove: parsing::string_to_counter(fen_parts[5])?,
             white,
             black,
             full,
-        }
+            hash: 0,
+        };
+
+        fen.hash = fen.compute_hash();
+        Ok(fen)
     }
+}
 
-    pub fn to_string(&self) -> String {
-        format!(
+impl fmt::Display for Fen {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
             "{} {} {} {} {} {}",
             parsing::board_to_string(&self.boards),
             parsing::turn_to_string(self.white_to_move),
@@ -59,223 +102,360 @@ impl Fen {
             self.fullmove
         )
     }
+}
+
+impl Fen {
+    pub fn new() -> Self {
+        Self::from_str(utils::DEFAULT).expect("the default fen notation is valid")
+    }
+
+    /// Returns the color of the side to move.
+    pub fn side_to_move(&self) -> Color {
+        Color::from_white_to_move(self.white_to_move)
+    }
+
+    /// Returns the squares occupied by the given piece.
+    pub fn pieces(&self, piece: Piece) -> Bitboard {
+        Bitboard(self.boards[piece.index()])
+    }
+
+    /// Returns the squares occupied by the pieces of the given color.
+    pub fn occupied_by(&self, color: Color) -> Bitboard {
+        match color {
+            Color::White => Bitboard(self.white),
+            Color::Black => Bitboard(self.black),
+        }
+    }
+
+    /// Returns the squares occupied by any piece.
+    pub fn occupied(&self) -> Bitboard {
+        Bitboard(self.full)
+    }
+
+    /// Returns the piece on the given square, or `None` if the square is empty.
+    pub fn piece_on(&self, square: Square) -> Option<Piece> {
+        let bit: u64 = square.bit();
+        if self.full & bit == 0 {
+            return None;
+        }
+
+        let index: usize = self.boards.iter().position(|board: &u64| board & bit != 0)?;
+        Piece::from_index(index)
+    }
+
+    /// Returns the square of the king of the given color, or `None` if there is no king.
+    pub fn king_square(&self, color: Color) -> Option<Square> {
+        self.pieces(Piece::new(color, PieceKind::King)).lsb()
+    }
 
     pub fn to_visual(&self) -> [[String; 8]; 8] {
         parsing::board_to_visual(&self.boards)
     }
 
+    /// Checks whether the move in LAN is legal, a move that cannot be parsed is never legal.
     pub fn is_legal_move_lan(&self, lan: &str) -> bool {
-        let start: u64 = parsing::tile_to_bit(&lan[0..2]);
-        let end: u64 = parsing::tile_to_bit(&lan[2..4]);
+        match self.parse_lan(lan) {
+            Ok(move1) => self.is_legal_move(&move1),
+            Err(_) => false,
+        }
+    }
 
-        let promoting_to: u64 = parsing::string_to_promotion(lan);
+    /// Checks whether the move is legal.
+    ///
+    /// Besides the move itself, its flags have to match the position (see `Fen::move_from_legacy`)
+    /// and a pawn reaching the last rank has to promote.
+    pub fn is_legal_move(&self, move1: &Move) -> bool {
+        if self.move_from_legacy(&move1.to_legacy()) != *move1 {
+            return false
+        }
+
+        let pawns: Bitboard = self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN);
+        let promoting: bool = move1.start() & pawns.0 != 0 && move1.end() & (RANK_0 | RANK_7) != 0;
+        if promoting != move1.is_promotion() {
+            return false
+        }
 
-        self.is_legal_move(&[start, end, promoting_to])
+        self.is_legal_step(move1)
     }
 
-    pub fn is_legal_move(&self, move1: &[u64; 3]) -> bool {
-        let start: u64 = move1[0];
-        let end: u64 = move1[1];
+    /// Checks whether the piece on the start square can legally move to the end square, ignoring promotion.
+    fn is_legal_step(&self, move1: &Move) -> bool {
+        let start: u64 = move1.start();
+        let end: u64 = move1.end();
 
         if !self.is_pseudo_legal(&start, &end) {
             return false
         }
 
-        let mut new_fen: Self = self.clone();
-    
-        new_fen.move_to_fen(&move1);
-        new_fen.white_to_move = !new_fen.white_to_move;
-    
-        if !new_fen.in_check() {
-            return true;
+        self.leaves_king_safe(move1)
+    }
+
+    /// Checks whether the own king is not attacked after the move, without playing it on a copy of the fen.
+    fn leaves_king_safe(&self, move1: &Move) -> bool {
+        let color: Color = self.side_to_move();
+        let start: Square = move1.start_square();
+        let end: Square = move1.end_square();
+
+        let mut captured: Bitboard = Bitboard::from(end);
+        if move1.is_en_passant() {
+            let behind: i8 = if color.is_white() { -1 } else { 1 };
+            captured |= Bitboard::from(end).shift(0, behind);
         }
-    
-        false
+
+        let occupied: Bitboard = (self.occupied() & !captured & !Bitboard::from(start)) | Bitboard::from(end);
+
+        let king: Square = if self.piece_on(start) == Some(Piece::new(color, PieceKind::King)) {
+            end
+        } else {
+            match self.king_square(color) {
+                Some(king) => king,
+                None => return true,
+            }
+        };
+
+        let attackers: Bitboard = self.attackers_to(king, occupied) & self.occupied_by(!color) & !captured;
+        attackers.is_empty()
     }
 
-    pub fn get_possible_moves_tile(&self, tile: &str) -> Vec<String> {
-        let moves_info: ([[u64; 3]; MAX_MOVES], usize) = self.get_possible_moves(&parsing::tile_to_bit(tile));
-        let moves:[[u64; 3]; MAX_MOVES]  = moves_info.0;
-        let count: usize = moves_info.1;
+    /// Converts a legacy move triple (start bit, end bit, promotion) into a move,
+    /// deriving the capture, double push, en passant and castle flags from the position.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn move_from_legacy(&self, move1: &[u64; 3]) -> Move {
+        let start: u64 = move1[0];
+        let end: u64 = move1[1];
+        let promoting_to: u64 = move1[2];
 
-        let mut vec_moves: Vec<[u64; 3]> = Vec::new();
+        let is_pawn: bool = start & (self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN)).0 != 0;
+        let is_king: bool = start & (self.pieces(Piece::WHITE_KING) | self.pieces(Piece::BLACK_KING)).0 != 0;
+        let capture: bool = end & self.full != 0;
+
+        let flags: u16 = if promoting_to != NO_PROM {
+            Move::promotion_flags(promoting_to, capture)
+        } else if capture {
+            Move::CAPTURE
+        } else if is_pawn && end & self.en_passant != 0 {
+            Move::EN_PASSANT
+        } else if is_pawn && (start << 16 == end || start >> 16 == end) {
+            Move::DOUBLE_PUSH
+        } else if is_king && start >> 2 == end {
+            Move::KING_CASTLE
+        } else if is_king && start << 2 == end {
+            Move::QUEEN_CASTLE
+        } else {
+            Move::QUIET
+        };
 
-        for i in 0..count {
-            vec_moves.push(moves[i]);
-        }
+        Move::new(start.trailing_zeros() as u8, end.trailing_zeros() as u8, flags)
+    }
 
-        parsing::moves_to_lan_list(&vec_moves)
+    /// Parses a move in LAN, deriving the remaining flags of the move from the position.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal, only whether it can be parsed
+    pub fn parse_lan(&self, lan: &str) -> Result<Move, FenError> {
+        let move1: [u64; 3] = parsing::lan_to_move(lan)?;
+        Ok(self.move_from_legacy(&move1))
+    }
+
+    pub fn get_possible_moves_tile(&self, tile: &str) -> Result<Vec<String>, FenError> {
+        let moves_info: ([Move; MAX_MOVES], usize) = self.get_possible_moves(&parsing::tile_to_bit(tile)?);
+        let moves: [Move; MAX_MOVES] = moves_info.0;
+        let count: usize = moves_info.1;
+
+        Ok(parsing::moves_to_lan_list(&moves[..count]))
     }
     
-    pub fn get_possible_moves(&self, start: &u64) -> ([[u64; 3]; MAX_MOVES], usize) {
-        let mut moves: [[u64; 3]; MAX_MOVES] = [[0; 3]; MAX_MOVES];
+    pub fn get_possible_moves(&self, start: &u64) -> ([Move; MAX_MOVES], usize) {
+        let mut moves: [Move; MAX_MOVES] = [Move::NULL; MAX_MOVES];
         let mut count: usize = 0;
 
-        if self.boards[0] & start != 0 {
-            let possible_moves: [u64; moves::PAWN_GUESS] = moves::white_pawn(&start);
-            for i in 0..moves::PAWN_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    if self.boards[0] & start != 0 && RANK_0 & end != 0 {
-                        moves[count + 0] = [*start, end, QUEEN_PROM];
-                        moves[count + 1] = [*start, end, ROOK_PROM];
-                        moves[count + 2] = [*start, end, BISHOP_PROM];
-                        moves[count + 3] = [*start, end, KNIGHT_PROM];
-                        count += 4;
-                    } else {
-                        moves[count] = [*start, end, NO_PROM];
-                        count += 1;
-                    }
-                }
-            }
-        } else if self.boards[6] & start != 0 {
-            let possible_moves: [u64; moves::PAWN_GUESS] = moves::black_pawn(&start);
-            for i in 0..moves::PAWN_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    if self.boards[6] & start != 0 && RANK_7 & end != 0 {
-                        moves[count + 0] = [*start, end, QUEEN_PROM];
-                        moves[count + 1] = [*start, end, ROOK_PROM];
-                        moves[count + 2] = [*start, end, BISHOP_PROM];
-                        moves[count + 3] = [*start, end, KNIGHT_PROM];
-                        count += 4;
-                    } else {
-                        moves[count] = [*start, end, NO_PROM];
-                        count += 1;
-                    }
-                }
-            }
-        } else if self.boards[1] & start != 0 || self.boards[7] & start != 0 {
-            let possible_moves: [u64; moves::KNIGHT_GUESS] = moves::knight(&start);
-            for i in 0..moves::KNIGHT_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    moves[count] = [*start, end, NO_PROM];
-                    count += 1;
-                }
-            }
-        } else if self.boards[5] & start != 0 || self.boards[11] & start != 0 {
-            let possible_moves: [u64; moves::KING_GUESS] = moves::king(&start);
-            for i in 0..moves::KING_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    moves[count] = [*start, end, NO_PROM];
-                    count += 1;
-                }
-            }
-        } else if self.boards[4] & start != 0 || self.boards[10] & start != 0 {
-            let possible_moves: [u64; moves::QUEEN_GUESS] = moves::queen(&start);
-            for i in 0..moves::QUEEN_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    moves[count] = [*start, end, NO_PROM];
-                    count += 1;
-                }
-            }
-        } else if self.boards[3] & start != 0 || self.boards[9] & start != 0 {
-            let possible_moves: [u64; moves::ROOK_GUESS] = moves::rook(&start);
-            for i in 0..moves::ROOK_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    moves[count] = [*start, end, NO_PROM];
-                    count += 1;
-                }
+        let square: Square = match Square::from_bit(*start) {
+            Some(square) => square,
+            None => return (moves, 0),
+        };
+
+        let is_pawn: bool = start & (self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN)).0 != 0;
+
+        for end in self.pseudo_legal_targets(square) {
+            let move1: Move = self.move_from_legacy(&[*start, end.bit(), NO_PROM]);
+            if !self.leaves_king_safe(&move1) {
+                continue;
             }
-        } else if self.boards[2] & start != 0 || self.boards[8] & start != 0 {
-            let possible_moves: [u64; moves::BISHOP_GUESS] = moves::bishop(&start);
-            for i in 0..moves::BISHOP_GUESS {
-                let end: u64 = possible_moves[i];
-                if self.is_legal_move(&[*start, end, NO_PROM]) {
-                    moves[count] = [*start, end, NO_PROM];
+
+            if is_pawn && end.bit() & (RANK_0 | RANK_7) != 0 {
+                for promoting_to in [QUEEN_PROM, ROOK_PROM, BISHOP_PROM, KNIGHT_PROM] {
+                    moves[count] = self.move_from_legacy(&[*start, end.bit(), promoting_to]);
                     count += 1;
                 }
+            } else {
+                moves[count] = move1;
+                count += 1;
             }
-        } else {
-            return (moves, 0);
         }
-    
+
         (moves, count)
     }
-    
-    pub fn in_check(&self) -> bool {
-        let king: u64 = match self.white_to_move {
-            true => self.boards[5],
-            false => self.boards[11]
+
+    /// Returns the squares the piece on `start` can move to, ignoring whether the own king is left in check.
+    ///
+    /// Castling is included, it requires the king not to be in check and the square it passes over to be safe.
+    /// Returns an empty set if there is no piece of the side to move on `start`.
+    pub fn pseudo_legal_targets(&self, start: Square) -> Bitboard {
+        let piece: Piece = match self.piece_on(start) {
+            Some(piece) if piece.color == self.side_to_move() => piece,
+            _ => return Bitboard::EMPTY,
         };
 
-        let opponents: u64= match self.white_to_move {
-            true => self.black,
-            false => self.white
+        let own: Bitboard = self.occupied_by(piece.color);
+        let opponents: Bitboard = self.occupied_by(!piece.color);
+        let occupied: Bitboard = own | opponents;
+
+        match piece.kind {
+            PieceKind::Pawn => {
+                let (forward, double_rank): (i8, u64) = match piece.color {
+                    Color::White => (1, RANK_6),
+                    Color::Black => (-1, RANK_1),
+                };
+
+                let single: Bitboard = Bitboard::from(start).shift(0, forward) & !occupied;
+                let double: Bitboard = if start.bit() & double_rank != 0 {
+                    single.shift(0, forward) & !occupied
+                } else {
+                    Bitboard::EMPTY
+                };
+                let captures: Bitboard = attacks::pawn_attacks(piece.color, start) & (opponents | Bitboard(self.en_passant));
+
+                single | double | captures
+            },
+            PieceKind::Knight => attacks::knight_attacks(start) & !own,
+            PieceKind::Bishop => attacks::bishop_attacks(start, occupied) & !own,
+            PieceKind::Rook => attacks::rook_attacks(start, occupied) & !own,
+            PieceKind::Queen => attacks::queen_attacks(start, occupied) & !own,
+            PieceKind::King => (attacks::king_attacks(start) & !own) | self.castling_targets(start),
+        }
+    }
+
+    /// Returns the squares the king on `start` can castle to.
+    fn castling_targets(&self, start: Square) -> Bitboard {
+        let color: Color = self.side_to_move();
+
+        // (castling info, king square, passed square, destination, squares that have to be empty)
+        let sides: [(u8, Square, Square, Square, u64); 2] = match color {
+            Color::White => [
+                (WHITE_KINGSIDE_INFO, Square::E1, Square::F1, Square::G1, WHITE_KINGSIDE_CLEAR),
+                (WHITE_QUEENSIDE_INFO, Square::E1, Square::D1, Square::C1, WHITE_QUEENSIDE_CLEAR),
+            ],
+            Color::Black => [
+                (BLACK_KINGSIDE_INFO, Square::E8, Square::F8, Square::G8, BLACK_KINGSIDE_CLEAR),
+                (BLACK_QUEENSIDE_INFO, Square::E8, Square::D8, Square::C8, BLACK_QUEENSIDE_CLEAR),
+            ],
         };
 
-        let mut new_fen: Self = self.clone();
+        let mut targets: Bitboard = Bitboard::EMPTY;
 
-        new_fen.white_to_move = !new_fen.white_to_move;
-    
-        for i in 0..64 {
-            let piece: u64 = FIRST >> i;
-            if piece & opponents != 0 && new_fen.is_pseudo_legal(&piece, &king) {
-                return true;
+        for (info, king, passed, destination, clear) in sides {
+            if self.castling & info == 0 || start != king || self.full & clear != 0 {
+                continue;
+            }
+
+            if self.is_square_attacked(king, !color) || self.is_square_attacked(passed, !color) {
+                continue;
             }
+
+            targets.set(destination);
         }
-    
-        false
+
+        targets
     }
 
-    pub fn lan_to_fen(&mut self, lan: &str) {
-        let start: u64 = parsing::tile_to_bit(&lan[0..2]);
-        let end: u64 = parsing::tile_to_bit(&lan[2..4]);
+    /// Returns all pieces of either color that attack `square`, given the occupancy used for sliding pieces.
+    pub fn attackers_to(&self, square: Square, occupancy: Bitboard) -> Bitboard {
+        let pawns: Bitboard = (attacks::pawn_attacks(Color::Black, square) & self.pieces(Piece::WHITE_PAWN))
+            | (attacks::pawn_attacks(Color::White, square) & self.pieces(Piece::BLACK_PAWN));
+        let knights: Bitboard = self.pieces(Piece::WHITE_KNIGHT) | self.pieces(Piece::BLACK_KNIGHT);
+        let kings: Bitboard = self.pieces(Piece::WHITE_KING) | self.pieces(Piece::BLACK_KING);
+        let queens: Bitboard = self.pieces(Piece::WHITE_QUEEN) | self.pieces(Piece::BLACK_QUEEN);
+        let diagonal: Bitboard = self.pieces(Piece::WHITE_BISHOP) | self.pieces(Piece::BLACK_BISHOP) | queens;
+        let straight: Bitboard = self.pieces(Piece::WHITE_ROOK) | self.pieces(Piece::BLACK_ROOK) | queens;
+
+        pawns
+            | (attacks::knight_attacks(square) & knights)
+            | (attacks::king_attacks(square) & kings)
+            | (attacks::bishop_attacks(square, occupancy) & diagonal)
+            | (attacks::rook_attacks(square, occupancy) & straight)
+    }
 
-        let promoting_to: u64 = parsing::string_to_promotion(lan);
+    /// Checks whether a piece of color `by` attacks `square`.
+    pub fn is_square_attacked(&self, square: Square, by: Color) -> bool {
+        !(self.attackers_to(square, self.occupied()) & self.occupied_by(by)).is_empty()
+    }
 
-        self.move_to_fen(&[start, end, promoting_to])
+    /// Checks whether the king of the side to move is attacked, a position without that king is never in check.
+    pub fn in_check(&self) -> bool {
+        let color: Color = self.side_to_move();
+
+        match self.king_square(color) {
+            Some(king) => self.is_square_attacked(king, !color),
+            None => false,
+        }
     }
 
-    pub fn move_to_fen(&mut self, move1: &[u64; 3]) {
-        let start: u64 = move1[0];
-        let end: u64 = move1[1];
-        let promoting_to: u64 = move1[2];
+    /// Applies the move in LAN to the fen.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal, only whether it can be parsed
+    pub fn lan_to_fen(&mut self, lan: &str) -> Result<(), FenError> {
+        let move1: Move = self.parse_lan(lan)?;
+        self.move_to_fen(&move1);
+        Ok(())
+    }
+
+    /// Applies the move to the fen, using the flags of the move to handle castling, en passant and promotion.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn move_to_fen(&mut self, move1: &Move) {
+        let us: Color = self.side_to_move();
+        let start: u64 = move1.start();
+        let end: u64 = move1.end();
+        let promoting_to: u64 = move1.promotion();
 
-        // moving rook in case of castling
-        if self.white_to_move && (start & self.boards[5] != 0) && (end & WHITE_KINGSIDE_BIT != 0) && (self.castling & WHITE_KINGSIDE_INFO != 0) {
-            self.boards[3] &= !(WHITE_KINGSIDE_BIT >> 1);
-            self.boards[3] |= WHITE_KINGSIDE_BIT << 1;
-        } else if self.white_to_move && (start & self.boards[5] != 0) && (end & WHITE_QUEENSIDE_BIT != 0) && (self.castling & WHITE_QUEENSIDE_INFO != 0) {
-            self.boards[3] &= !(WHITE_QUEENSIDE_BIT << 2);
-            self.boards[3] |= WHITE_QUEENSIDE_BIT >> 1;
-        } else if !self.white_to_move && (start & self.boards[11] != 0) && (end & BLACK_KINGSIDE_BIT != 0) && (self.castling & BLACK_KINGSIDE_INFO != 0) {
-            self.boards[9] &= !(BLACK_KINGSIDE_BIT >> 1);
-            self.boards[9] |= BLACK_KINGSIDE_BIT << 1;
-        } else if !self.white_to_move && (start & self.boards[11] != 0) && (end & BLACK_QUEENSIDE_BIT != 0) && (self.castling & BLACK_QUEENSIDE_INFO != 0) {
-            self.boards[9] &= !(BLACK_QUEENSIDE_BIT << 2);
-            self.boards[9] |= BLACK_QUEENSIDE_BIT >> 1;
+        self.hash = self.updated_hash(move1);
+
+        // moving rook in case of castling, the king ends next to the rook's starting square
+        let rook_index: usize = Piece::new(us, PieceKind::Rook).index();
+        if move1.flags() == Move::KING_CASTLE {
+            self.boards[rook_index] &= !(end >> 1);
+            self.boards[rook_index] |= end << 1;
+        } else if move1.flags() == Move::QUEEN_CASTLE {
+            self.boards[rook_index] &= !(end << 2);
+            self.boards[rook_index] |= end >> 1;
         }
 
-        // removing piece in case of enpassant, enpassant is only legal from certain tiles, so we dont have to check whether the pawn is on the correct tile
-        if self.white_to_move && (self.en_passant & end != 0) && (start & self.boards[0] != 0) {
-            self.boards[6] &= !(self.en_passant >> 8)
-        } else if !self.white_to_move && (self.en_passant & end != 0) && (start & self.boards[6] != 0) {
-            self.boards[0] &= !(self.en_passant << 8)
+        // removing the captured pawn in case of en passant, it is directly behind the end square
+        if move1.is_en_passant() && self.white_to_move {
+            self.boards[Piece::BLACK_PAWN.index()] &= !(end >> 8)
+        } else if move1.is_en_passant() {
+            self.boards[Piece::WHITE_PAWN.index()] &= !(end << 8)
         }
     
-        // we check if an en passant is possible, thus if a pawn has moved two squares forward
+        // after a pawn has moved two squares forward, the square it skipped can be captured en passant
         self.en_passant = EMPTY;
-        if self.white_to_move && end & RANK_4 != 0 && start & self.boards[0] & RANK_6 != 0 {
+        if move1.is_double_push() && self.white_to_move {
             self.en_passant = start << 8;
-        } else if !self.white_to_move && end & RANK_3 != 0 && start & self.boards[6] & RANK_1 != 0 {
+        } else if move1.is_double_push() {
             self.en_passant = start >> 8;
         }
     
         // increase halfmove if no pawn is moved and no piece is captured, else set it to 0
-        if end & self.full == 0 && start & self.boards[0] == 0 && start & self.boards[6] == 0 {
-            self.halfmove += 1;
+        let pawns: Bitboard = self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN);
+        if !move1.is_capture() && start & pawns.0 == 0 {
+            self.halfmove = self.halfmove.saturating_add(1);
         } else {
             self.halfmove = 0;
         }
 
         // the fullmove is only increased after a move by black
         if !self.white_to_move {
-            self.fullmove += 1;
+            self.fullmove = self.fullmove.saturating_add(1);
         }
     
         // apply capture to the board
@@ -295,57 +475,44 @@ impl Fen {
     
         // promote to the given piece
         if promoting_to != NO_PROM {
-            if self.white_to_move {
-                self.boards[0] &= !end;
-                if promoting_to & QUEEN_PROM != 0 {
-                    self.boards[4] |= end
-                } else if promoting_to & ROOK_PROM != 0 {
-                    self.boards[3] |= end
-                } else if promoting_to & BISHOP_PROM != 0 {
-                    self.boards[2] |= end
-                } else if promoting_to & KNIGHT_PROM != 0 {
-                    self.boards[1] |= end
-                } else {
-                    panic!("Something went wrong with promoting")
-                }
+            let promoted: PieceKind = if promoting_to & QUEEN_PROM != 0 {
+                PieceKind::Queen
+            } else if promoting_to & ROOK_PROM != 0 {
+                PieceKind::Rook
+            } else if promoting_to & BISHOP_PROM != 0 {
+                PieceKind::Bishop
+            } else if promoting_to & KNIGHT_PROM != 0 {
+                PieceKind::Knight
             } else {
-                self.boards[6] &= !end;
-                if promoting_to & QUEEN_PROM != 0 {
-                    self.boards[10] |= end
-                } else if promoting_to & ROOK_PROM != 0 {
-                    self.boards[9] |= end
-                } else if promoting_to & BISHOP_PROM != 0 {
-                    self.boards[8] |= end
-                } else if promoting_to & KNIGHT_PROM != 0 {
-                    self.boards[7] |= end
-                } else {
-                    panic!("Something went wrong with promoting")
-                }
-            }
+                panic!("Something went wrong with promoting")
+            };
+
+            self.boards[Piece::new(us, PieceKind::Pawn).index()] &= !end;
+            self.boards[Piece::new(us, promoted).index()] |= end;
         }
 
         // if the rooks or kings are not on their starting position, the castling information is changed accordingly
-        if self.boards[5] & WHITE_KING_BIT == 0 {
-            self.castling &= !(WHITE_KINGSIDE_INFO & WHITE_QUEENSIDE_INFO);
+        if self.pieces(Piece::WHITE_KING).0 & WHITE_KING_BIT == 0 {
+            self.castling &= !(WHITE_KINGSIDE_INFO | WHITE_QUEENSIDE_INFO);
         }
 
-        if self.boards[11] & BLACK_KING_BIT == 0 {
-            self.castling &= !(BLACK_KINGSIDE_INFO & BLACK_QUEENSIDE_INFO);
+        if self.pieces(Piece::BLACK_KING).0 & BLACK_KING_BIT == 0 {
+            self.castling &= !(BLACK_KINGSIDE_INFO | BLACK_QUEENSIDE_INFO);
         }
 
-        if self.boards[3] & (WHITE_KINGSIDE_BIT >> 1) == 0 {
+        if self.pieces(Piece::WHITE_ROOK).0 & (WHITE_KINGSIDE_BIT >> 1) == 0 {
             self.castling &= !WHITE_KINGSIDE_INFO;
         }
 
-        if self.boards[3] & (WHITE_QUEENSIDE_BIT << 2) == 0 {
+        if self.pieces(Piece::WHITE_ROOK).0 & (WHITE_QUEENSIDE_BIT << 2) == 0 {
             self.castling &= !WHITE_QUEENSIDE_INFO;
         }
 
-        if self.boards[9] & (BLACK_KINGSIDE_BIT >> 1) == 0 {
+        if self.pieces(Piece::BLACK_ROOK).0 & (BLACK_KINGSIDE_BIT >> 1) == 0 {
             self.castling &= !BLACK_KINGSIDE_INFO;
         }
 
-        if self.boards[9] & (BLACK_QUEENSIDE_BIT << 2) == 0 {
+        if self.pieces(Piece::BLACK_ROOK).0 & (BLACK_QUEENSIDE_BIT << 2) == 0 {
             self.castling &= !BLACK_QUEENSIDE_INFO;
         }
 
@@ -357,568 +524,74 @@ impl Fen {
         self.white = get_white(&self.boards);
         self.black = get_black(&self.boards);
         self.full = self.white | self.black;
-    }
 
-    pub fn get_all_possible_moves_lan(&self) -> Vec<String> {
-        let moves: Vec<[u64; 3]> = self.get_all_possible_moves();
-        parsing::moves_to_lan_list(&moves)
+        // the castling rights and en passant square depend on the position after the move
+        self.hash ^= zobrist::CASTLING_KEYS[self.castling as usize & 0xF] ^ self.en_passant_key();
+        debug_assert_eq!(self.hash, self.compute_hash(), "incremental hash differs after {}", move1);
     }
 
-    pub fn get_all_possible_moves(&self) -> Vec<[u64; 3]> {
-        let mut vec_moves: Vec<[u64; 3]> = Vec::new();
-    
-        let pieces = match self.white_to_move {
-            true => self.white,
-            false => self.black
-        };
-    
-        for i in 0..64 {
-            let piece: u64 = FIRST >> i;
-            
-            if piece & pieces != 0 {
-                let moves_info: ([[u64; 3]; MAX_MOVES], usize) = self.get_possible_moves(&piece);
-                let moves:[[u64; 3]; MAX_MOVES]  = moves_info.0;
-                let count: usize = moves_info.1;
-
-                for i in 0..count {
-                    vec_moves.push(moves[i])
-                }
-            }
-        }
-    
-        vec_moves
-    }
+    /// Returns the hash after the move with the keys of the pieces and the side to move updated,
+    /// and the keys of the castling rights and en passant square before the move removed.
+    fn updated_hash(&self, move1: &Move) -> u64 {
+        let us: Color = self.side_to_move();
+        let start: Square = move1.start_square();
+        let end: Square = move1.end_square();
 
-    pub fn game_ended(&self) -> String {
-        let moves: usize = self.get_all_possible_moves().len();
-        let in_check: bool = self.in_check();
-    
-        if moves == 0 && in_check {
-            if self.white_to_move {
-                "0-1".to_string()
-            } else {
-                "1-0".to_string()
-            }
-        } else if moves == 0 || self.halfmove > 100 {
-            "½-½".to_string()
-        } else {
-            "not ended".to_string()
-        }
-    }
-    
-    pub fn legal_move_white_pawn(&self, start: &u64, end: &u64) -> bool {
-    
-        // if the pawn is on rank 8, it cannot move
-        if start & RANK_0 != 0 {
-            return false;
-        }
-    
-        // if we are not on file 1, the move is diagonally left, and there is a black piece there, the move is legal
-        if (start & FILE_0 == 0) && (end & (start << 9) & self.black != 0) {
-            return true;
-        }
-    
-        // if we are not on file 8, the move is diagonally right, and there is a black piece there, the move is legal
-        if (start & FILE_7 == 0) && (end & (start << 7) & self.black != 0) {
-            return true;
-        }
-    
-        // if the forward square is obstructed, no forward move is legal
-        if start << 8 & self.full != 0 {
-            return false;
-        }
-    
-        // if the move is one forward, and there is no piece there, the move is legal
-        if end & (start << 8) != 0 {
-            return true;
-        }
-    
-        // if we are on rank 2, the move is two forward and there is no piece there, the move is legal
-        if (start & RANK_6 != 0) && (end & (start << 16) & !self.full != 0) {
-            return true;
-        }
-    
-        false
-    }
-    
-    pub fn legal_move_black_pawn(&self, start: &u64, end: &u64) -> bool {
-        
-        // if the pawn is on rank 1, it cannot move
-        if start & RANK_7 != 0 {
-            return false;
-        }
-    
-        // if we are not on file 8, the move is diagonally right, and there is a white piece there, the move is legal
-        if (start & FILE_7 == 0) && (end & (start >> 9) & self.white != 0) {
-            return true;
-        }
-    
-        // if we are not on file 1, the move is diagonally left, and there is a white piece there, the move is legal
-        if (start & FILE_0 == 0) && (end & (start >> 7) & self.white != 0) {
-            return true;
-        }
-    
-        // if the forward square is obstructed, no forward move is legal
-        if start >> 8 & self.full != 0 {
-            return false;
-        }
-    
-        // if the move is one forward, and there is no piece there, the move is legal
-        if end & (start >> 8) != 0 {
-            return true;
-        }
-    
-        // if we are on rank 7, the move is two forward and there is no piece there, the move is legal
-        if (start & RANK_1 != 0) && (end & (start >> 16) & !self.full != 0) {
-            return true;
-        }
-    
-        false
-    }
-    
-    pub fn legal_move_knight(&self, start: &u64, end: &u64) -> bool {
-        let one_left: u64 = start << 1 & !FILE_7;
-        let one_right: u64 = start >> 1 & !FILE_0;
-    
-        // check if end is one left two up from start
-        if end & (one_left << 16) != 0 {
-            return true;
-        }
-    
-        // check if end is one left two down from start
-        if end & (one_left >> 16) != 0 {
-            return true;
-        }
-    
-        // check if end is one right two up from start
-        if end & (one_right << 16) != 0 {
-            return true;
-        }
-    
-        // check if end is one right two down from start
-        if end & (one_right >> 16) != 0 {
-            return true;
-        }
-    
-        let one_up: u64 = start << 8;
-        let one_down: u64 = start >> 8;
-    
-        // check if end is one up two left from start
-        if end & (one_up << 2) & !(FILE_6 | FILE_7) != 0 {
-            return true;
-        }
-    
-        // check if end is one up two right from start
-        if end & (one_up >> 2) & !(FILE_0 | FILE_1) != 0 {
-            return true;
-        }
-    
-        // check if end is one down two left from start
-        if end & (one_down << 2) & !(FILE_6 | FILE_7) != 0 {
-            return true;
-        }
-    
-        // check if end is one down two right from start
-        if end & (one_down >> 2) & !(FILE_0 | FILE_1) != 0 {
-            return true;
-        }
-    
-        false
-    }
-    
-    pub fn legal_move_king(&self, start: &u64, end: &u64) -> bool {
-    
-        // check if end is one up from start
-        if end & (start << 8) != 0 {
-            return true;
-        }
-    
-        // check if end is one down from start
-        if end & (start >> 8) != 0 {
-            return true;
-        }
-    
-        // check if end is one left from start
-        let one_left: u64 = start << 1 & !FILE_7;
-        if end & one_left != 0 {
-            return true;
-        }
-    
-        // check if end is one left one up from start
-        if end & (one_left << 8) != 0 {
-            return true;
-        }
-    
-        // check if end is one left one down from start
-        if end & (one_left >> 8) != 0 {
-            return true;
-        }
-    
-        // check if end is one right from start
-        let one_right: u64 = start >> 1 & !FILE_0;
-        if end & one_right != 0 {
-            return true;
-        }
-    
-        // check if end is one right one up from start
-        if end & (one_right << 8) != 0 {
-            return true;
-        }
-    
-        // check if end is one right one down from start
-        if end & (one_right >> 8) != 0 {
-            return true;
-        }
-    
-        false
-    }
-    
-    pub fn legal_move_rook(&self, start: &u64, end: &u64) -> bool {
-    
-        // check for a move left
-        if start & FILE_0 == 0 {
-            for i in 1..8 {
-                let pos: u64 = start << i;
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 0, we cannot move furthur, thus we break
-                if pos & FILE_0 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move right
-        if start & FILE_7 == 0 {
-            for i in 1..8 {
-                let pos: u64 = start >> i;
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 7, we cannot move furthur, thus we break
-                if pos & FILE_7 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move up
-        if start & RANK_0 == 0 {
-            for i in 1..8 {
-                let pos: u64 = start << (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach rank 0, we cannot move furthur, thus we break
-                if pos & RANK_0 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move down
-        if start & RANK_7 == 0 {
-            for i in 1..8 {
-                let pos: u64 = start >> (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach rank 7, we cannot move furthur, thus we break
-                if pos & RANK_7 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        false
-    }
-    
-    pub fn legal_move_bishop(&self, start: &u64, end: &u64) -> bool {
-    
-        // check for a move left up
-        if start & RANK_0 == 0 && start & FILE_7 == 0 {
-            for i in 1..8 {
-                let pos: u64 = (start >> i) << (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 7, we cannot move furthur, thus we break
-                if pos & FILE_7 != 0 {
-                    break;
-                }
-        
-                // if we reach rank 0, we cannot move furthur, thus we break
-                if pos & RANK_0 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move right up
-        if start & RANK_0 == 0 && start & FILE_0 == 0 {
-            for i in 1..8 {
-                let pos: u64 = (start << i) << (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 0, we cannot move furthur, thus we break
-                if pos & FILE_0 != 0 {
-                    break;
-                }
-        
-                // if we reach rank 0, we cannot move furthur, thus we break
-                if pos & RANK_0 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move left down
-        if start & RANK_7 == 0 && start & FILE_7 == 0 {
-            for i in 1..8 {
-                let pos: u64 = (start >> i) >> (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 7, we cannot move furthur, thus we break
-                if pos & FILE_7 != 0 {
-                    break;
-                }
-        
-                // if we reach rank 7, we cannot move furthur, thus we break
-                if pos & RANK_7 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        // check for a move right down
-        if start & RANK_7 == 0 && start & FILE_0 == 0 {
-            for i in 1..8 {
-                let pos: u64 = (start << i) >> (i * 8);
-        
-                // if we cannot move furthur, we break
-                if pos & self.full & !end != 0 {
-                    break;
-                }
-        
-                // check if end is the pos
-                if pos & end != 0 {
-                    return true;
-                }
-        
-                // if we reach file 0, we cannot move furthur, thus we break
-                if pos & FILE_0 != 0 {
-                    break;
-                }
-        
-                // if we reach rank 7, we cannot move furthur, thus we break
-                if pos & RANK_7 != 0 {
-                    break;
-                }
-            }
-        }
-    
-        false
-    }
-    
-    pub fn check_standard_moves(&self, start: &u64, end: &u64) -> bool {
-        if start & self.full == 0 {
-            return false;
-        }
-    
-        let is_white: bool = start & self.white != 0;
-    
-        if is_white && !self.white_to_move {
-            return false;
-        }
-    
-        if !is_white && self.white_to_move {
-            return false;
-        }
-    
-        if self.white_to_move && (end & self.white != 0) {
-            return false;
-        }
-    
-        if !self.white_to_move && (end & self.black != 0) {
-            return false;
-        }
-    
-        // check white pawns
-        if self.boards[0] & start != 0 {
-            return self.legal_move_white_pawn(&start, &end);
-        }
-    
-        // check black pawns
-        if self.boards[6] & start != 0 {
-            return self.legal_move_black_pawn(&start, &end);
-        }
-    
-        // check knights
-        if self.boards[1] & start != 0 || self.boards[7] & start != 0 {
-            return self.legal_move_knight(&start, &end);
-        }
-    
-        // check kings
-        if self.boards[5] & start != 0 || self.boards[11] & start != 0 {
-            return self.legal_move_king(&start, &end);
-        }
-    
-        let mut is_legal_rook: bool = false;
-        let mut is_legal_bishop: bool = false;
-        let is_queen: bool = self.boards[4] & start != 0 || self.boards[10] & start != 0;
-    
-        // check rooks and queens
-        if is_queen || self.boards[3] & start != 0 || self.boards[9] & start != 0 {
-            is_legal_rook = self.legal_move_rook(&start, &end);
-            if !is_queen {
-                return is_legal_rook;
+        let mut hash: u64 = self.hash ^ zobrist::BLACK_TO_MOVE_KEY;
+        hash ^= zobrist::CASTLING_KEYS[self.castling as usize & 0xF] ^ self.en_passant_key();
+
+        if move1.is_en_passant() {
+            let behind: i8 = if us.is_white() { -1 } else { 1 };
+            if let Some(captured) = end.offset(0, behind) {
+                hash ^= zobrist::piece_key(Piece::new(!us, PieceKind::Pawn), captured);
             }
-        }
-    
-        // check bishops and queens
-        if is_queen || self.boards[2] & start != 0 || self.boards[8] & start != 0 {
-            is_legal_bishop = self.legal_move_bishop(&start, &end);
-            if !is_queen {
-                return is_legal_bishop;
+        } else if let Some(captured) = self.piece_on(end) {
+            hash ^= zobrist::piece_key(captured, end);
+        }
+
+        if let Some(moving) = self.piece_on(start) {
+            let placed: Piece = match move1.promotion() {
+                QUEEN_PROM => Piece::new(us, PieceKind::Queen),
+                ROOK_PROM => Piece::new(us, PieceKind::Rook),
+                BISHOP_PROM => Piece::new(us, PieceKind::Bishop),
+                KNIGHT_PROM => Piece::new(us, PieceKind::Knight),
+                _ => moving,
+            };
+            hash ^= zobrist::piece_key(moving, start) ^ zobrist::piece_key(placed, end);
+        }
+
+        // the rook squares of castling, see `Fen::move_to_fen`
+        let rook_squares: Option<(u64, u64)> = match move1.flags() {
+            Move::KING_CASTLE => Some((end.bit() >> 1, end.bit() << 1)),
+            Move::QUEEN_CASTLE => Some((end.bit() << 2, end.bit() >> 1)),
+            _ => None,
+        };
+
+        if let Some((from, to)) = rook_squares {
+            let rook: Piece = Piece::new(us, PieceKind::Rook);
+            for square in Bitboard(from | to) {
+                hash ^= zobrist::piece_key(rook, square);
             }
         }
-    
-        // final check queens
-        if is_legal_rook || is_legal_bishop {
-            return true;
-        }
-    
-        false
+
+        hash
     }
-    
-    pub fn check_en_passant(&self, start: &u64, end: &u64) -> bool {
-    
-        // check if end is at the correct position for en passant
-        if self.en_passant & end == 0 {
-            return false
-        }
-    
-        // check if the piece is a white pawn and in the right position
-        if self.white_to_move && (start & self.boards[0] != 0) && (start & RANK_3 != 0) && ((start << 7) & end != 0 || (start << 9) & end != 0) {
-            return true
-        }
-    
-        // check if the piece is a black pawn and in the right position
-        if !self.white_to_move && (start & self.boards[6] != 0) && (start & RANK_4 != 0) && ((start >> 7) & end != 0 || (start >> 9) & end != 0) {
-            return true
-        }
-    
-        false
+
+    pub fn get_all_possible_moves_lan(&self) -> Vec<String> {
+        let moves: Vec<Move> = self.get_all_possible_moves();
+        parsing::moves_to_lan_list(&moves)
     }
-    
-    pub fn check_castle(&self, start: &u64, end: &u64) -> bool {
-        
-        // check if the white king wants to move
-        if self.white_to_move && (start & self.boards[5] == 0) {
-            return false
-        }
-    
-        // check if the black king wants to move
-        if !self.white_to_move && (start & self.boards[11] == 0) {
-            return false
-        }
-        
-        // check if white king side position is clear and there are no piece in the way
-        if self.white_to_move && (self.castling & (1u8 << 3) != 0) && (end & WHITE_KINGSIDE_BIT != 0) && (self.full & WHITE_KINGSIDE_CLEAR == 0) {
-            return true
-        }
-    
-        // check if white queen side position is clear and there are no piece in the way
-        if self.white_to_move && (self.castling & (1u8 << 2) != 0) && (end & WHITE_QUEENSIDE_BIT != 0) && (self.full & WHITE_QUEENSIDE_CLEAR == 0) {
-            return true
-        }
-    
-        // check if black king side position is clear and there are no piece in the way
-        if !self.white_to_move && (self.castling & (1u8 << 1) != 0) && (end & BLACK_KINGSIDE_BIT != 0) && (self.full & BLACK_KINGSIDE_CLEAR == 0) {
-            return true
-        }
-    
-        // check if black queen side position is clear and there are no piece in the way
-        if !self.white_to_move && (self.castling & 1u8 != 0) && (end & BLACK_QUEENSIDE_BIT != 0) && (self.full & BLACK_QUEENSIDE_CLEAR == 0) {
-            return true
-        }
-    
-        false
+
+    pub fn get_all_possible_moves(&self) -> Vec<Move> {
+        self.legal_moves().to_vec()
     }
-    
+
+    /// Checks whether the piece on the start square can move to the end square,
+    /// ignoring whether the own king is left in check.
     pub fn is_pseudo_legal(&self, start: &u64, end: &u64) -> bool {
-        if self.check_standard_moves(&start, &end) {
-            return true;
-        }
-    
-        if self.check_en_passant(&start, &end) {
-            return true;
-        }
-    
-        if self.check_castle(&start, &end) {
-            return true;
+        match Square::from_bit(*start) {
+            Some(square) => self.pseudo_legal_targets(square).0 & end != 0,
+            None => false,
         }
-    
-        false
-    
     }
 }
\ No newline at end of file
@@ -0,0 +1,281 @@
+//! Legal move generation.
+//!
+//! Instead of playing every candidate move and testing whether the own king is in check, the generator
+//! computes once per position:
+//! * the checkers, the pieces that attack the own king
+//! * the check mask, the squares a piece other than the king has to move to in order to resolve a check
+//! * the pinned pieces, which may only move along the line between the king and the pinning piece
+//! * the king danger mask, every square the opponent attacks when the king is taken off the board
+//!
+//! With those only legal moves are emitted. En passant is the one exception, removing two pawns from
+//! the same rank can uncover an attack on the king, so it is verified separately.
+
+use std::ops::{Deref, DerefMut};
+
+use crate::Fen;
+use crate::attacks;
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::*;
+
+/// The capacity of a `MoveList`, no position reachable in a game has more than 218 legal moves.
+pub const MAX_LEGAL_MOVES: usize = 256;
+
+/// A list of moves with a fixed capacity that lives on the stack.
+///
+/// Dereferences to a slice of the moves that have been pushed.
+#[derive(Debug, Clone)]
+pub struct MoveList {
+    moves: [Move; MAX_LEGAL_MOVES],
+    len: usize,
+}
+
+impl MoveList {
+    pub const fn new() -> Self {
+        Self { moves: [Move::NULL; MAX_LEGAL_MOVES], len: 0 }
+    }
+
+    /// Adds a move to the end of the list, returns false and drops the move if the list is full.
+    ///
+    /// **NOTE:** the parser accepts positions that cannot arise in a game, those can have more
+    /// than `MAX_LEGAL_MOVES` legal moves, the generator then returns the first `MAX_LEGAL_MOVES`
+    pub fn push(&mut self, move1: Move) -> bool {
+        if self.len == MAX_LEGAL_MOVES {
+            return false;
+        }
+        self.moves[self.len] = move1;
+        self.len += 1;
+        true
+    }
+
+    pub fn clear(&mut self) {
+        self.len = 0;
+    }
+}
+
+impl Default for MoveList {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl Deref for MoveList {
+    type Target = [Move];
+
+    fn deref(&self) -> &[Move] {
+        &self.moves[..self.len]
+    }
+}
+
+impl DerefMut for MoveList {
+    fn deref_mut(&mut self) -> &mut [Move] {
+        &mut self.moves[..self.len]
+    }
+}
+
+impl<'a> IntoIterator for &'a MoveList {
+    type Item = &'a Move;
+    type IntoIter = std::slice::Iter<'a, Move>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl Fen {
+    /// Returns every legal move of the side to move.
+    ///
+    /// Promotions are listed in the order queen, rook, bishop, knight.
+    pub fn legal_moves(&self) -> MoveList {
+        self.generate_moves(false)
+    }
+
+    /// Returns the legal captures and promotions of the side to move, the moves a quiescence search looks at.
+    /// In check every legal move is returned, since the check has to be answered.
+    pub fn tactical_moves(&self) -> MoveList {
+        self.generate_moves(true)
+    }
+
+    fn generate_moves(&self, tactical: bool) -> MoveList {
+        let mut list: MoveList = MoveList::new();
+
+        let us: Color = self.side_to_move();
+        let own: Bitboard = self.occupied_by(us);
+        let opponents: Bitboard = self.occupied_by(!us);
+        let occupied: Bitboard = self.occupied();
+
+        // the squares pieces may move to, a quiet move is only tactical if it promotes
+        let tactical: bool = tactical && self.checkers().is_empty();
+        let target_mask: Bitboard = if tactical { opponents } else { Bitboard::FULL };
+
+        let mut check_mask: Bitboard = Bitboard::FULL;
+        let mut pinned: Bitboard = Bitboard::EMPTY;
+        let king: Option<Square> = self.king_square(us);
+
+        if let Some(king) = king {
+            // the king must not step onto a square that is attacked through the square it stands on
+            let danger: Bitboard = self.attacked_squares(!us, occupied & !Bitboard::from(king));
+
+            let targets: Bitboard = attacks::king_attacks(king) & !own & !danger & target_mask;
+            self.push_moves(&mut list, king, targets);
+
+            let castling: Bitboard = if tactical { Bitboard::EMPTY } else { self.castling_targets(king) };
+            for end in castling & !danger {
+                let flags: u16 = if end.file() > king.file() { Move::KING_CASTLE } else { Move::QUEEN_CASTLE };
+                list.push(Move::from_squares(king, end, flags));
+            }
+
+            let checkers: Bitboard = self.checkers();
+            match checkers.popcount() {
+                0 => {},
+                1 => {
+                    if let Some(checker) = checkers.lsb() {
+                        check_mask = attacks::between(king, checker) | checkers;
+                    }
+                },
+                // in double check only the king can move
+                _ => return list,
+            }
+
+            pinned = self.pinned(king);
+        }
+
+        // restricts the targets of a pinned piece to the line through the king
+        let pin_mask = |start: Square| -> Bitboard {
+            match king {
+                Some(king) if pinned.contains(start) => attacks::line(king, start),
+                _ => Bitboard::FULL,
+            }
+        };
+
+        for kind in [PieceKind::Knight, PieceKind::Bishop, PieceKind::Rook, PieceKind::Queen] {
+            for start in self.pieces(Piece::new(us, kind)) {
+                let attacked: Bitboard = match kind {
+                    PieceKind::Knight => attacks::knight_attacks(start),
+                    PieceKind::Bishop => attacks::bishop_attacks(start, occupied),
+                    PieceKind::Rook => attacks::rook_attacks(start, occupied),
+                    _ => attacks::queen_attacks(start, occupied),
+                };
+
+                self.push_moves(&mut list, start, attacked & !own & target_mask & check_mask & pin_mask(start));
+            }
+        }
+
+        let (forward, double_rank): (i8, u64) = match us {
+            Color::White => (1, RANK_6),
+            Color::Black => (-1, RANK_1),
+        };
+
+        let push_mask: Bitboard = if tactical { Bitboard(RANK_0 | RANK_7) } else { Bitboard::FULL };
+
+        for start in self.pieces(Piece::new(us, PieceKind::Pawn)) {
+            let single: Bitboard = Bitboard::from(start).shift(0, forward) & !occupied & push_mask;
+            let double: Bitboard = if start.bit() & double_rank != 0 {
+                single.shift(0, forward) & !occupied
+            } else {
+                Bitboard::EMPTY
+            };
+            let captures: Bitboard = attacks::pawn_attacks(us, start) & opponents;
+
+            self.push_pawn_moves(&mut list, start, (single | double | captures) & check_mask & pin_mask(start));
+
+            if attacks::pawn_attacks(us, start).0 & self.en_passant != 0 {
+                let end: u8 = self.en_passant.trailing_zeros() as u8;
+                let move1: Move = Move::new(start.index(), end, Move::EN_PASSANT);
+                if self.leaves_king_safe(&move1) {
+                    list.push(move1);
+                }
+            }
+        }
+
+        list
+    }
+
+    /// Returns the pieces of the opponent that attack the king of the side to move.
+    pub fn checkers(&self) -> Bitboard {
+        let us: Color = self.side_to_move();
+
+        match self.king_square(us) {
+            Some(king) => self.attackers_to(king, self.occupied()) & self.occupied_by(!us),
+            None => Bitboard::EMPTY,
+        }
+    }
+
+    /// Returns the pieces of the side to move that are pinned to their king by a sliding piece of the opponent.
+    fn pinned(&self, king: Square) -> Bitboard {
+        let us: Color = self.side_to_move();
+        let them: Color = !us;
+        let queens: Bitboard = self.pieces(Piece::new(them, PieceKind::Queen));
+
+        let snipers: Bitboard = (attacks::rook_attacks(king, Bitboard::EMPTY) & (self.pieces(Piece::new(them, PieceKind::Rook)) | queens))
+            | (attacks::bishop_attacks(king, Bitboard::EMPTY) & (self.pieces(Piece::new(them, PieceKind::Bishop)) | queens));
+
+        let mut pinned: Bitboard = Bitboard::EMPTY;
+        for sniper in snipers {
+            let blockers: Bitboard = attacks::between(king, sniper) & self.occupied();
+            if blockers.popcount() == 1 {
+                pinned |= blockers & self.occupied_by(us);
+            }
+        }
+
+        pinned
+    }
+
+    /// Returns every square a piece of color `by` attacks, with sliding pieces blocked by `occupancy`.
+    pub fn attacked_squares(&self, by: Color, occupancy: Bitboard) -> Bitboard {
+        let mut attacked: Bitboard = Bitboard::EMPTY;
+
+        for square in self.pieces(Piece::new(by, PieceKind::Pawn)) {
+            attacked |= attacks::pawn_attacks(by, square);
+        }
+
+        for square in self.pieces(Piece::new(by, PieceKind::Knight)) {
+            attacked |= attacks::knight_attacks(square);
+        }
+
+        let queens: Bitboard = self.pieces(Piece::new(by, PieceKind::Queen));
+
+        for square in self.pieces(Piece::new(by, PieceKind::Bishop)) | queens {
+            attacked |= attacks::bishop_attacks(square, occupancy);
+        }
+
+        for square in self.pieces(Piece::new(by, PieceKind::Rook)) | queens {
+            attacked |= attacks::rook_attacks(square, occupancy);
+        }
+
+        for square in self.pieces(Piece::new(by, PieceKind::King)) {
+            attacked |= attacks::king_attacks(square);
+        }
+
+        attacked
+    }
+
+    /// Adds a quiet move or capture from `start` to every target.
+    fn push_moves(&self, list: &mut MoveList, start: Square, targets: Bitboard) {
+        for end in targets {
+            let flags: u16 = if self.full & end.bit() != 0 { Move::CAPTURE } else { Move::QUIET };
+            list.push(Move::from_squares(start, end, flags));
+        }
+    }
+
+    /// Adds the pawn moves from `start` to every target, expanding moves to the last rank into the four promotions.
+    fn push_pawn_moves(&self, list: &mut MoveList, start: Square, targets: Bitboard) {
+        for end in targets {
+            let capture: bool = self.full & end.bit() != 0;
+
+            if end.bit() & (RANK_0 | RANK_7) != 0 {
+                for promoting_to in [QUEEN_PROM, ROOK_PROM, BISHOP_PROM, KNIGHT_PROM] {
+                    list.push(Move::from_squares(start, end, Move::promotion_flags(promoting_to, capture)));
+                }
+            } else if capture {
+                list.push(Move::from_squares(start, end, Move::CAPTURE));
+            } else if start.rank().abs_diff(end.rank()) == 2 {
+                list.push(Move::from_squares(start, end, Move::DOUBLE_PUSH));
+            } else {
+                list.push(Move::from_squares(start, end, Move::QUIET));
+            }
+        }
+    }
+}
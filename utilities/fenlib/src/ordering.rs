@@ -0,0 +1,335 @@
+use crate::Fen;
+use crate::bot::MAX_PLY;
+use crate::chess_move::Move;
+use crate::movegen::{MAX_LEGAL_MOVES, MoveList};
+use crate::piece::{Piece, PieceKind};
+use crate::utils::*;
+
+/// The highest score a quiet move can get from the history table, see `MoveOrdering::update`.
+pub const MAX_HISTORY: i32 = 16_384;
+
+/// The scores of the stages are far apart, so every move of an earlier stage comes before the moves of a later one.
+const TT_MOVE_SCORE: i32 = 1 << 30;
+const CAPTURE_SCORE: i32 = 1 << 28;
+const KILLER_SCORE: i32 = 1 << 26;
+const COUNTERMOVE_SCORE: i32 = 1 << 25;
+
+/// Captures that lose material come after all quiet moves, followed by underpromotions,
+/// which are almost never better than promoting to a queen.
+const BAD_SCORE: i32 = -(1 << 26);
+const LOSING_CAPTURE_SCORE: i32 = BAD_SCORE - 64;
+const UNDERPROMOTION_SCORE: i32 = BAD_SCORE - 128;
+
+/// The groups of moves the `MovePicker` returns one after another, from the most to the least promising.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Stage {
+    /// The best move the transposition table remembers for the position.
+    TtMove,
+
+    /// Captures and queen promotions that do not lose material by `Fen::see`,
+    /// the most valuable victim first and among those the least valuable attacker.
+    Captures,
+
+    /// Quiet moves that caused a cutoff in a sibling position at the same ply.
+    Killers,
+
+    /// The quiet move that refuted the previous move of the opponent the last time it was played.
+    Countermove,
+
+    /// The other quiet moves, by how often they caused cutoffs before.
+    Quiets,
+
+    /// Captures that lose material by `Fen::see` and underpromotions.
+    Bad,
+}
+
+/// What the search learns about quiet moves while it runs, used to order the moves of later positions
+/// (https://www.chessprogramming.org/Move_Ordering).
+///
+/// It also counts how often the first move searched caused the cutoff, which is how well the moves are ordered.
+#[derive(Debug, Clone)]
+pub struct MoveOrdering {
+    /// Two quiet moves per ply that caused a cutoff, the latest first.
+    killers: Vec<[Move; 2]>,
+
+    /// The butterfly history: a score per side, start and end square of a quiet move.
+    history: [[[i32; 64]; 64]; 2],
+
+    /// The quiet move that refuted a move, by the piece that moved and its end square.
+    countermoves: [[Move; 64]; 12],
+
+    cutoffs: u64,
+    first_move_cutoffs: u64,
+}
+
+impl Default for MoveOrdering {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl MoveOrdering {
+    pub fn new() -> Self {
+        Self {
+            killers: vec![[Move::NULL; 2]; MAX_PLY as usize + 1],
+            history: [[[0; 64]; 64]; 2],
+            countermoves: [[Move::NULL; 64]; 12],
+            cutoffs: 0,
+            first_move_cutoffs: 0,
+        }
+    }
+
+    /// Forgets everything learned, e.g. when a new game starts.
+    pub fn clear(&mut self) {
+        *self = Self::new();
+    }
+
+    /// Prepares for a new search: the killers are for other positions now and the history counts less than
+    /// what the new search finds, the countermoves are kept.
+    pub fn new_search(&mut self) {
+        self.killers.fill([Move::NULL; 2]);
+        for score in self.history.iter_mut().flatten().flatten() {
+            *score /= 2;
+        }
+        self.cutoffs = 0;
+        self.first_move_cutoffs = 0;
+    }
+
+    /// Returns the killer moves of the ply, `Move::NULL` where there is none.
+    pub fn killers(&self, ply: u32) -> [Move; 2] {
+        self.killers.get(ply as usize).copied().unwrap_or([Move::NULL; 2])
+    }
+
+    /// Returns the history score of a quiet move of the side to move.
+    pub fn history(&self, fen: &Fen, move1: Move) -> i32 {
+        self.history[fen.side_to_move().index()][move1.start_index() as usize][move1.end_index() as usize]
+    }
+
+    /// Returns the quiet move that refuted `previous` before, `Move::NULL` if there is none.
+    /// `fen` is the position after `previous`.
+    pub fn countermove(&self, fen: &Fen, previous: Move) -> Move {
+        match (previous.is_null(), fen.piece_on(previous.end_square())) {
+            (false, Some(piece)) => self.countermoves[piece.index()][previous.end_index() as usize],
+            _ => Move::NULL,
+        }
+    }
+
+    /// Learns from a cutoff by `move1` at `ply` after the moves in `tried` failed to cause one.
+    /// `fen` is the position before `move1` and `previous` the move that led to it.
+    ///
+    /// A quiet move becomes a killer and the countermove of `previous`, its history rises by `depth * depth`
+    /// and the history of the quiet moves tried before falls by as much. The history stays within `MAX_HISTORY`,
+    /// the closer it gets the less it changes.
+    pub fn update(&mut self, fen: &Fen, move1: Move, previous: Move, ply: u32, depth: u32, tried: &[Move]) {
+        self.cutoffs += 1;
+        if tried.is_empty() {
+            self.first_move_cutoffs += 1;
+        }
+
+        if !is_quiet(move1) {
+            return;
+        }
+
+        if let Some(killers) = self.killers.get_mut(ply as usize)
+            && killers[0] != move1
+        {
+            killers[1] = killers[0];
+            killers[0] = move1;
+        }
+
+        if !previous.is_null()
+            && let Some(piece) = fen.piece_on(previous.end_square())
+        {
+            self.countermoves[piece.index()][previous.end_index() as usize] = move1;
+        }
+
+        let bonus: i32 = (depth * depth).min(MAX_HISTORY as u32) as i32;
+        let side: usize = fen.side_to_move().index();
+        self.add_history(side, move1, bonus);
+        for &quiet in tried.iter().filter(|&&tried_move: &&Move| is_quiet(tried_move)) {
+            self.add_history(side, quiet, -bonus);
+        }
+    }
+
+    /// Returns the number of cutoffs since the search started.
+    pub fn cutoffs(&self) -> u64 {
+        self.cutoffs
+    }
+
+    /// Returns the part of the cutoffs that were caused by the first move searched, `None` if there were none.
+    /// Good move ordering keeps this above 90%.
+    pub fn first_move_cutoff_rate(&self) -> Option<f64> {
+        (self.cutoffs > 0).then(|| self.first_move_cutoffs as f64 / self.cutoffs as f64)
+    }
+
+    fn add_history(&mut self, side: usize, move1: Move, bonus: i32) {
+        let score: &mut i32 = &mut self.history[side][move1.start_index() as usize][move1.end_index() as usize];
+        *score += bonus - *score * bonus.abs() / MAX_HISTORY;
+    }
+}
+
+/// Returns the moves of a position one by one in the order of `Stage`, the most promising first.
+///
+/// The moves are scored one stage at a time when the stage is reached: the move from the transposition table
+/// is returned before anything is scored and captures are only checked with `Fen::see` once they are needed,
+/// so a search that is cut off by its first moves does not pay for the rest. Within a stage the next move is
+/// picked by a selection over the scored moves instead of sorting them.
+#[derive(Debug, Clone)]
+pub struct MovePicker {
+    moves: MoveList,
+    scores: [i32; MAX_LEGAL_MOVES],
+
+    /// The moves before `next` have been returned, the moves from `next` up to `scored` have a score.
+    next: usize,
+    scored: usize,
+    step: Step,
+
+    tt_move: Move,
+    killers: [Move; 2],
+    countermove: Move,
+}
+
+/// What `MovePicker::next_move` does next.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum Step {
+    TtMove,
+    ScoreCaptures,
+    GoodCaptures,
+    ScoreQuiets,
+    Rest,
+}
+
+impl MovePicker {
+    /// Orders the legal moves of `fen`, `tt_move` is the move from the transposition table or `Move::NULL`,
+    /// `previous` is the move that led to the position or `Move::NULL` at the root.
+    pub fn new(fen: &Fen, moves: MoveList, tt_move: Move, previous: Move, ply: u32, ordering: &MoveOrdering) -> Self {
+        Self {
+            moves,
+            scores: [0; MAX_LEGAL_MOVES],
+            next: 0,
+            scored: 0,
+            step: Step::TtMove,
+            tt_move,
+            killers: ordering.killers(ply),
+            countermove: ordering.countermove(fen, previous),
+        }
+    }
+
+    /// Returns the stage a score belongs to.
+    pub fn stage_of(score: i32) -> Stage {
+        if score >= TT_MOVE_SCORE {
+            Stage::TtMove
+        } else if score >= CAPTURE_SCORE {
+            Stage::Captures
+        } else if score >= KILLER_SCORE {
+            Stage::Killers
+        } else if score >= COUNTERMOVE_SCORE {
+            Stage::Countermove
+        } else if score > BAD_SCORE {
+            Stage::Quiets
+        } else {
+            Stage::Bad
+        }
+    }
+
+    /// Returns the next move and its stage, `None` when all moves have been returned.
+    /// `fen` and `ordering` have to be the position and the ordering the picker was created with.
+    pub fn next_move(&mut self, fen: &Fen, ordering: &MoveOrdering) -> Option<(Move, Stage)> {
+        loop {
+            match self.step {
+                Step::TtMove => {
+                    self.step = Step::ScoreCaptures;
+
+                    if let Some(index) = self.moves.iter().position(|&move1: &Move| move1 == self.tt_move) {
+                        // keep the other moves in the order they were generated
+                        self.moves[..=index].rotate_right(1);
+                        self.next = 1;
+                        self.scored = 1;
+                        return Some((self.tt_move, Stage::TtMove));
+                    }
+                },
+                Step::ScoreCaptures => {
+                    self.step = Step::GoodCaptures;
+
+                    // move the captures and promotions in front of the quiet moves, both in the order they were generated
+                    for index in self.next..self.moves.len() {
+                        if !is_quiet(self.moves[index]) {
+                            self.moves[self.scored..=index].rotate_right(1);
+                            self.scored += 1;
+                        }
+                    }
+
+                    for index in self.next..self.scored {
+                        let move1: Move = self.moves[index];
+                        self.scores[index] = if move1.is_promotion() && move1.promotion() != QUEEN_PROM {
+                            UNDERPROMOTION_SCORE
+                        } else if fen.see_ge(move1, 0) {
+                            CAPTURE_SCORE + mvv_lva(fen, move1)
+                        } else {
+                            LOSING_CAPTURE_SCORE + mvv_lva(fen, move1)
+                        };
+                    }
+                },
+                Step::GoodCaptures => match self.pick(CAPTURE_SCORE) {
+                    Some(picked) => return Some(picked),
+                    None => self.step = Step::ScoreQuiets,
+                },
+                Step::ScoreQuiets => {
+                    self.step = Step::Rest;
+
+                    for index in self.scored..self.moves.len() {
+                        let move1: Move = self.moves[index];
+                        self.scores[index] = if move1 == self.killers[0] {
+                            KILLER_SCORE + 1
+                        } else if move1 == self.killers[1] {
+                            KILLER_SCORE
+                        } else if move1 == self.countermove {
+                            COUNTERMOVE_SCORE
+                        } else {
+                            ordering.history(fen, move1)
+                        };
+                    }
+                    self.scored = self.moves.len();
+                },
+                Step::Rest => return self.pick(i32::MIN),
+            }
+        }
+    }
+
+    /// Returns the scored move with the highest score if it is at least `min_score`.
+    fn pick(&mut self, min_score: i32) -> Option<(Move, Stage)> {
+        let best: usize = (self.next..self.scored).max_by_key(|&index: &usize| (self.scores[index], std::cmp::Reverse(index)))?;
+        if self.scores[best] < min_score {
+            return None;
+        }
+
+        self.moves.swap(self.next, best);
+        self.scores.swap(self.next, best);
+
+        let picked: (Move, Stage) = (self.moves[self.next], Self::stage_of(self.scores[self.next]));
+        self.next += 1;
+
+        Some(picked)
+    }
+}
+
+/// Checks whether a move neither captures nor promotes, the moves killers and the history are about.
+pub fn is_quiet(move1: Move) -> bool {
+    !move1.is_capture() && !move1.is_promotion()
+}
+
+/// Scores a capture by Most Valuable Victim - Least Valuable Attacker, a queen promotion counts as capturing a queen.
+pub fn mvv_lva(fen: &Fen, move1: Move) -> i32 {
+    let victim: PieceKind = if move1.is_en_passant() {
+        PieceKind::Pawn
+    } else {
+        match fen.piece_on(move1.end_square()) {
+            Some(Piece { kind, .. }) => kind,
+            None if move1.promotion() == QUEEN_PROM => PieceKind::Queen,
+            None => return 0,
+        }
+    };
+    let attacker: PieceKind = fen.piece_on(move1.start_square()).map_or(PieceKind::Pawn, |piece: Piece| piece.kind);
+
+    victim.index() as i32 * 8 - attacker.index() as i32
+}
@@ -1,3 +1,5 @@
+use crate::chess_move::Move;
+use crate::error::FenError;
 use crate::utils::*;
 
 /// Converts a tile in algebraic notation (e.g., "e4") to a bitboard representation.
@@ -8,18 +10,17 @@ use crate::utils::*;
 /// * `tile` - A string slice representing the tile.
 ///
 /// # Returns
-/// * `u64` - Bitboard representation of the tile.
-pub fn tile_to_bit(tile: &str) -> u64 {
+/// * `Result<u64, FenError>` - Bitboard representation of the tile, or `FenError::BadTile` if the tile is malformed.
+pub fn tile_to_bit(tile: &str) -> Result<u64, FenError> {
     if tile == "-" {
-        return EMPTY;
-    } 
-    
-    if tile.len() != 2 {
-        panic!("Found string of wrong length when attempting to parse tile");
+        return Ok(EMPTY);
     }
 
-    let file_char: char = tile.chars().nth(0).unwrap();
-    let rank_char: char = tile.chars().nth(1).unwrap();
+    let mut chars = tile.chars();
+    let (file_char, rank_char) = match (chars.next(), chars.next(), chars.next()) {
+        (Some(file_char), Some(rank_char), None) => (file_char, rank_char),
+        _ => return Err(FenError::BadTile(tile.to_string())),
+    };
 
     let rank: usize = match rank_char {
         '8' => 0,
@@ -30,7 +31,7 @@ pub fn tile_to_bit(tile: &str) -> u64 {
         '3' => 5,
         '2' => 6,
         '1' => 7,
-        _ => panic!("Found unknown char when attempting to parse tile rank"),
+        _ => return Err(FenError::BadTile(tile.to_string())),
     };
 
     let file: usize = match file_char {
@@ -42,10 +43,10 @@ pub fn tile_to_bit(tile: &str) -> u64 {
         'f' => 5,
         'g' => 6,
         'h' => 7,
-        _ => panic!("Found unknown char when attempting to parse tile file"),
+        _ => return Err(FenError::BadTile(tile.to_string())),
     };
 
-    (FIRST >> (rank * 8)) >> file
+    Ok((FIRST >> (rank * 8)) >> file)
 }
 
 /// Converts a single-bit bitboard to its algebraic tile notation (e.g., 0b1 -> "h1").
@@ -56,8 +57,7 @@ pub fn tile_to_bit(tile: &str) -> u64 {
 /// # Returns
 /// * `String` - Tile in algebraic notation.
 pub fn bit_to_tile(bit: &u64) -> String {
-    let ones: u32 = bit.count_ones();
-    if ones > 1 || ones == 0 {
+    if bit.count_ones() != 1 {
         panic!("Found wrong format when attempting to parse bit")
     }
 
@@ -65,11 +65,11 @@ pub fn bit_to_tile(bit: &u64) -> String {
     let mut file: usize = 0;
     for i in 0..8 {
         if bit & RANKS[i] != 0 {
-            rank = i as usize;
+            rank = i;
         }
 
         if bit & FILES[i] != 0 {
-            file = i as usize;
+            file = i;
         }
     }
 
@@ -114,22 +114,22 @@ pub fn bit_to_tile(bit: &u64) -> String {
 /// * `piece` - A character representing the piece.
 ///
 /// # Returns
-/// * `usize` - Index corresponding to the piece.
-pub fn piece_to_index(piece: char) -> usize {
+/// * `Result<usize, FenError>` - Index corresponding to the piece, or `FenError::UnknownPiece`.
+pub fn piece_to_index(piece: char) -> Result<usize, FenError> {
     match piece {
-        'P' => 0,
-        'N' => 1,
-        'B' => 2,
-        'R' => 3,
-        'Q' => 4,
-        'K' => 5,
-        'p' => 6,
-        'n' => 7,
-        'b' => 8,
-        'r' => 9,
-        'q' => 10,
-        'k' => 11,
-        _ => panic!("Found unknown char when attempting to parse piece char"),
+        'P' => Ok(0),
+        'N' => Ok(1),
+        'B' => Ok(2),
+        'R' => Ok(3),
+        'Q' => Ok(4),
+        'K' => Ok(5),
+        'p' => Ok(6),
+        'n' => Ok(7),
+        'b' => Ok(8),
+        'r' => Ok(9),
+        'q' => Ok(10),
+        'k' => Ok(11),
+        _ => Err(FenError::UnknownPiece(piece)),
     }
 }
 
@@ -166,24 +166,39 @@ pub fn index_to_piece(index: usize) -> String {
 /// * `board` - FEN board representation string.
 ///
 /// # Returns
-/// * `[u64; 12]` - Array of bitboards for all pieces.
-pub fn string_to_board(board: &str) -> [u64; 12] {
+/// * `Result<[u64; 12], FenError>` - Array of bitboards for all pieces, or the reason the board is malformed.
+pub fn string_to_board(board: &str) -> Result<[u64; 12], FenError> {
     let mut boards: [u64; 12] = [0; 12];
     let rows: Vec<&str> = board.split('/').collect();
 
+    if rows.len() != 8 {
+        return Err(FenError::WrongRankCount(rows.len()));
+    }
+
     for (rank, row) in rows.iter().enumerate() {
         let mut file: usize = 0;
         for ch in row.chars() {
-            if ch.is_digit(10) {
-                file += ch.to_digit(10).unwrap() as usize;
+            if let Some(empty) = ch.to_digit(10) {
+                if empty == 0 || empty > 8 {
+                    return Err(FenError::UnknownPiece(ch));
+                }
+                file += empty as usize;
             } else {
-                let index: usize = piece_to_index(ch);
+                let index: usize = piece_to_index(ch)?;
+                if file >= 8 {
+                    return Err(FenError::BadRankLength(row.to_string()));
+                }
                 boards[index] |= FIRST >> (rank * 8 + file);
                 file += 1;
             }
         }
+
+        if file != 8 {
+            return Err(FenError::BadRankLength(row.to_string()));
+        }
     }
-    boards
+
+    Ok(boards)
 }
 
 /// Parses the turn string from FEN ("w" or "b").
@@ -192,16 +207,13 @@ pub fn string_to_board(board: &str) -> [u64; 12] {
 /// * `turn` - "w" if white to move, "b" if black.
 ///
 /// # Returns
-/// * `bool` - `true` if white to move, `false` if black.
-pub fn string_to_turn(turn: &str) -> bool {
-    let result: bool;
+/// * `Result<bool, FenError>` - `true` if white to move, `false` if black.
+pub fn string_to_turn(turn: &str) -> Result<bool, FenError> {
     match turn {
-        "w" => result = true,
-        "b" => result = false,
-        _ => panic!("Found unknown string when attempting to parse turn string")
+        "w" => Ok(true),
+        "b" => Ok(false),
+        _ => Err(FenError::BadSideToMove(turn.to_string())),
     }
-
-    result
 }
 
 /// Parses the castling rights string from FEN format.
@@ -218,27 +230,36 @@ pub fn string_to_turn(turn: &str) -> bool {
 /// * `castling` - Castling string.
 ///
 /// # Returns
-/// * `u8` - Bitmask representing castling rights.
-pub fn string_to_castling(castling: &str) -> u8 {
+/// * `Result<u8, FenError>` - Bitmask representing castling rights.
+pub fn string_to_castling(castling: &str) -> Result<u8, FenError> {
     let mut result: u8 = 0x0;
-    
-    if castling.contains("K") {
-        result |= WHITE_KINGSIDE_INFO;
-    }
 
-    if castling.contains("Q") {
-        result |= WHITE_QUEENSIDE_INFO;
+    if castling == "-" {
+        return Ok(result);
     }
 
-    if castling.contains("k") {
-        result |= BLACK_KINGSIDE_INFO;
+    if castling.is_empty() {
+        return Err(FenError::BadCastling(castling.to_string()));
     }
 
-    if castling.contains("q") {
-        result |= BLACK_QUEENSIDE_INFO;
+    for ch in castling.chars() {
+        let info: u8 = match ch {
+            'K' => WHITE_KINGSIDE_INFO,
+            'Q' => WHITE_QUEENSIDE_INFO,
+            'k' => BLACK_KINGSIDE_INFO,
+            'q' => BLACK_QUEENSIDE_INFO,
+            _ => return Err(FenError::BadCastling(castling.to_string())),
+        };
+
+        // every right can only be given once
+        if result & info != 0 {
+            return Err(FenError::BadCastling(castling.to_string()));
+        }
+
+        result |= info;
     }
 
-    result
+    Ok(result)
 }
 
 /// Parses the en passant tile from FEN format.
@@ -247,9 +268,26 @@ pub fn string_to_castling(castling: &str) -> u8 {
 /// * `enpassant` - Tile in algebraic notation or "-" for none.
 ///
 /// # Returns
-/// * `u64` - Bitboard representation of en passant square.
-pub fn string_to_enpassant(enpassant: &str) -> u64 {
-    tile_to_bit(enpassant)
+/// * `Result<u64, FenError>` - Bitboard representation of en passant square, which must be on rank 3 or 6.
+pub fn string_to_enpassant(enpassant: &str) -> Result<u64, FenError> {
+    let bit: u64 = tile_to_bit(enpassant).map_err(|_| FenError::BadEnPassant(enpassant.to_string()))?;
+
+    if bit != EMPTY && bit & (RANK_2 | RANK_5) == 0 {
+        return Err(FenError::BadEnPassant(enpassant.to_string()));
+    }
+
+    Ok(bit)
+}
+
+/// Parses the halfmove or fullmove counter from FEN format.
+///
+/// # Arguments
+/// * `counter` - The counter as a decimal number.
+///
+/// # Returns
+/// * `Result<u16, FenError>` - The parsed counter.
+pub fn string_to_counter(counter: &str) -> Result<u16, FenError> {
+    counter.parse().map_err(|_| FenError::BadCounter(counter.to_string()))
 }
 
 /// Parses the promotion information from a LAN move string (e.g., "e7e8q").
@@ -258,52 +296,57 @@ pub fn string_to_enpassant(enpassant: &str) -> u64 {
 /// * `lan` - LAN string with optional promotion character at the end.
 ///
 /// # Returns
-/// * `u64` - Bitboard constant representing promotion piece.
-pub fn string_to_promotion(lan: &str) -> u64 {
-    if lan.len() == 5 {
-        match &lan[4..5] {
-            "q" | "Q" => QUEEN_PROM,
-            "r" | "R" => ROOK_PROM,
-            "b" | "B" => BISHOP_PROM,
-            "n" | "N" => KNIGHT_PROM,
-            _ => panic!("Found unknown char when attempting to parse promotion info")
-        }
-    } else {
-        NO_PROM
+/// * `Result<u64, FenError>` - Bitboard constant representing promotion piece.
+pub fn string_to_promotion(lan: &str) -> Result<u64, FenError> {
+    match lan.chars().nth(4) {
+        Some('q') | Some('Q') => Ok(QUEEN_PROM),
+        Some('r') | Some('R') => Ok(ROOK_PROM),
+        Some('b') | Some('B') => Ok(BISHOP_PROM),
+        Some('n') | Some('N') => Ok(KNIGHT_PROM),
+        Some(piece) => Err(FenError::BadPromotion(piece)),
+        None => Ok(NO_PROM),
     }
 }
 
-/// Converts a move (start bit, end bit, promotion) to a long algebraic notation (LAN) string.
-/// 
+/// Parses a move in long algebraic notation (LAN) into a move triple.
+///
 /// **NOTE:** this function does not check whether the move is legal
-/// 
+///
 /// # Arguments
-/// * `start` - Bitboard with starting position.
-/// * `end` - Bitboard with ending position.
-/// * `promoting_to` - Bitboard mask for promotion piece.
+/// * `lan` - Move in LAN (e.g., "e2e4" or "e7e8q").
 ///
 /// # Returns
-/// * `String` - Move in LAN (e.g., "e7e8q").
-pub fn move_to_lan(move1: &[u64; 3]) -> String {
+/// * `Result<[u64; 3], FenError>` - The move as start bit, end bit and promotion.
+pub fn lan_to_move(lan: &str) -> Result<[u64; 3], FenError> {
+    let length: usize = lan.chars().count();
+    if !lan.is_ascii() || !(4..=5).contains(&length) {
+        return Err(FenError::BadMove(lan.to_string()));
+    }
 
-    let mut result: String = "".to_string();
+    let start: u64 = tile_to_bit(&lan[0..2]).map_err(|_| FenError::BadMove(lan.to_string()))?;
+    let end: u64 = tile_to_bit(&lan[2..4]).map_err(|_| FenError::BadMove(lan.to_string()))?;
 
-    result += &bit_to_tile(&move1[0]);
-    result += &bit_to_tile(&move1[1]);
+    // "-" is accepted by tile_to_bit, but it is not a valid part of a move
+    if start == EMPTY || end == EMPTY {
+        return Err(FenError::BadMove(lan.to_string()));
+    }
 
-    let promoting_to: u64 = move1[2];
+    let promoting_to: u64 = string_to_promotion(lan)?;
 
-    if promoting_to & QUEEN_PROM != 0 {
-        result += "q"
-    } else if promoting_to & ROOK_PROM != 0 {
-        result += "r"
-    } else if promoting_to & BISHOP_PROM != 0 {
-        result += "b"
-    } else if promoting_to & KNIGHT_PROM != 0 {
-        result += "n"
-    }
-    
-    result
+    Ok([start, end, promoting_to])
+}
+
+/// Converts a move to a long algebraic notation (LAN) string.
+///
+/// **NOTE:** this function does not check whether the move is legal
+///
+/// # Arguments
+/// * `move1` - The move to convert.
+///
+/// # Returns
+/// * `String` - Move in LAN (e.g., "e7e8q").
+pub fn move_to_lan(move1: &Move) -> String {
+    move1.to_string()
 }
 
 /// Converts an array of 12 bitboards into a visual 8x8 board of piece strings.
@@ -318,12 +361,12 @@ pub fn board_to_visual(boards: &[u64; 12]) -> [[String; 8]; 8] {
         std::array::from_fn(|_| "-".to_string())
     });
 
-    for rank in 0..8 {
-        for file in 0..8 {
+    for (rank, row) in board.iter_mut().enumerate() {
+        for (file, tile) in row.iter_mut().enumerate() {
             let bit: u64 = (FIRST >> (rank * 8)) >> file;
-            for i in 0..12 {
-                if boards[i] & bit != 0 {
-                    board[rank][file] = index_to_piece(i);
+            for (i, piece_board) in boards.iter().enumerate() {
+                if piece_board & bit != 0 {
+                    *tile = index_to_piece(i);
                 }
             }
         }
@@ -413,7 +456,7 @@ pub fn castling_to_string(castling: &u8) -> String {
         result += "q"
     }
 
-    if result.len() == 0 {
+    if result.is_empty() {
         result = "-".to_string()
     }
 
@@ -435,13 +478,13 @@ pub fn enpassant_to_string(enpassant: &u64) -> String {
     }
 }
 
-/// Converts a vector of moves in [start, end, promotion] bitboard format into a list of LAN strings.
+/// Converts a list of moves into a list of LAN strings.
 ///
 /// # Arguments
-/// * `moves` - Vector of moves, each represented by `start`, `end`, `promotion`.
+/// * `moves` - Slice of moves.
 ///
 /// # Returns
 /// * `Vec<String>` - Vector of moves in LAN format.
-pub fn moves_to_lan_list(moves: &Vec<[u64; 3]>) -> Vec<String> {
-    moves.iter().map(|move1: &[u64; 3]| move_to_lan(move1)).collect()
+pub fn moves_to_lan_list(moves: &[Move]) -> Vec<String> {
+    moves.iter().map(move_to_lan).collect()
 }
@@ -0,0 +1,59 @@
+//! Performance test (perft), counting the leaf nodes of the legal move tree to a fixed depth.
+//!
+//! The counts for well-known positions are published (https://www.chessprogramming.org/Perft_Results),
+//! so comparing against them verifies the move generator. `Fen::perft_divide` splits the count by the
+//! first move, which narrows a mismatch down to the move whose subtree is wrong.
+
+use crate::Fen;
+use crate::chess_move::Move;
+use crate::movegen::MoveList;
+use crate::undo::Undo;
+
+impl Fen {
+    /// Returns the number of leaf nodes of the legal move tree of the given depth.
+    pub fn perft(&self, depth: u32) -> u64 {
+        let mut fen: Fen = self.clone();
+        fen.perft_recursive(depth)
+    }
+
+    /// Returns the perft count of the given depth for every legal move, the sum of the counts is `Fen::perft`.
+    ///
+    /// The counts are of the subtrees after each move, so they are of depth `depth - 1`.
+    pub fn perft_divide(&self, depth: u32) -> Vec<(Move, u64)> {
+        if depth == 0 {
+            return Vec::new();
+        }
+
+        let mut fen: Fen = self.clone();
+        let moves: MoveList = fen.legal_moves();
+
+        moves.iter().map(|&move1: &Move| {
+            let undo: Undo = fen.make_move(move1);
+            let nodes: u64 = fen.perft_recursive(depth - 1);
+            fen.unmake_move(move1, undo);
+            (move1, nodes)
+        }).collect()
+    }
+
+    fn perft_recursive(&mut self, depth: u32) -> u64 {
+        if depth == 0 {
+            return 1;
+        }
+
+        let moves: MoveList = self.legal_moves();
+
+        // the leaves do not have to be played, counting them is enough
+        if depth == 1 {
+            return moves.len() as u64;
+        }
+
+        let mut nodes: u64 = 0;
+        for &move1 in &moves {
+            let undo: Undo = self.make_move(move1);
+            nodes += self.perft_recursive(depth - 1);
+            self.unmake_move(move1, undo);
+        }
+
+        nodes
+    }
+}
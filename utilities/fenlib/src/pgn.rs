@@ -0,0 +1,619 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Fen;
+use crate::chess_move::Move;
+use crate::error::FenError;
+use crate::game::Game;
+use crate::result::Outcome;
+use crate::utils::DEFAULT;
+
+/// The tags every PGN game has, in the order they are written.
+pub const SEVEN_TAG_ROSTER: [&str; 7] = ["Event", "Site", "Date", "Round", "White", "Black", "Result"];
+
+/// Lines of exported movetext are wrapped before they get longer than this.
+const LINE_LENGTH: usize = 80;
+
+/// A move of a PGN game together with its annotations and the alternatives to it.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnMove {
+    pub move1: Move,
+
+    /// Numeric annotation glyphs, e.g. 1 for "!" and 2 for "?".
+    pub nags: Vec<u8>,
+
+    /// A comment written before the move, only used for the first move of a game or variation.
+    pub comment_before: Option<String>,
+
+    /// A comment written after the move.
+    pub comment: Option<String>,
+
+    /// Lines that could have been played instead of this move, starting from the position before it.
+    pub variations: Vec<Vec<PgnMove>>,
+}
+
+impl PgnMove {
+    /// Creates a move without annotations.
+    pub fn new(move1: Move) -> Self {
+        Self { move1, nags: Vec::new(), comment_before: None, comment: None, variations: Vec::new() }
+    }
+}
+
+/// A game as stored in PGN: its tags, the position it started from and a tree of moves.
+#[derive(Debug, Clone)]
+pub struct PgnGame {
+    /// The tags in the order they were read, including the seven tag roster.
+    pub tags: Vec<(String, String)>,
+
+    /// The starting position, taken from the "FEN" tag if there is one.
+    pub start: Fen,
+
+    /// The main line of the game.
+    pub moves: Vec<PgnMove>,
+
+    pub result: Outcome,
+}
+
+impl Default for PgnGame {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl PgnGame {
+    /// Creates a game without tags or moves from the default starting position.
+    pub fn new() -> Self {
+        Self { tags: Vec::new(), start: Fen::new(), moves: Vec::new(), result: Outcome::Ongoing }
+    }
+
+    /// Creates a game with the moves and result of a `Game`, the starting position is stored in the "FEN" tag if needed.
+    pub fn from_game(game: &Game) -> Self {
+        let mut start: Game = game.clone();
+        while start.undo().is_some() {}
+
+        let mut pgn: PgnGame = Self::new();
+        if start.fen().to_string() != DEFAULT {
+            pgn.set_tag("SetUp", "1");
+            pgn.set_tag("FEN", &start.fen().to_string());
+        }
+
+        pgn.start = start.fen().clone();
+        pgn.moves = game.moves().into_iter().map(PgnMove::new).collect();
+        pgn.result = game.result().outcome;
+        pgn
+    }
+
+    /// Returns the value of the tag with the given name.
+    pub fn tag(&self, name: &str) -> Option<&str> {
+        self.tags.iter().find(|(tag, _): &&(String, String)| tag == name).map(|(_, value): &(String, String)| value.as_str())
+    }
+
+    /// Sets the value of a tag, replacing the old value if the tag already exists.
+    pub fn set_tag(&mut self, name: &str, value: &str) {
+        match self.tags.iter_mut().find(|(tag, _): &&mut (String, String)| tag == name) {
+            Some((_, old)) => *old = value.to_string(),
+            None => self.tags.push((name.to_string(), value.to_string())),
+        }
+    }
+
+    /// Returns the moves of the main line.
+    pub fn mainline(&self) -> Vec<Move> {
+        self.moves.iter().map(|pgn_move: &PgnMove| pgn_move.move1).collect()
+    }
+
+    /// Replays the main line from the starting position.
+    pub fn to_game(&self) -> Game {
+        let mut game: Game = Game::from_fen(self.start.clone());
+        for move1 in self.mainline() {
+            game.make_move(move1);
+        }
+        game
+    }
+}
+
+/// Errors from reading a PGN file, with the game and move they were found in.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PgnError {
+    /// The number of the game in the file, starting at 1, or 0 if the error is not about a single game.
+    pub game: usize,
+
+    /// The move number of the move that could not be read, `None` if the error is not in a move.
+    pub move_number: Option<u16>,
+
+    pub kind: PgnErrorKind,
+}
+
+/// What was wrong in a PGN file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PgnErrorKind {
+    /// A move in SAN or the position of the "FEN" tag could not be read.
+    Fen(FenError),
+
+    /// A comment in braces was not closed.
+    UnterminatedComment,
+
+    /// A tag pair was not closed, the text after the opening bracket is given.
+    UnterminatedTag(String),
+
+    /// A tag pair was not a name followed by a quoted value.
+    MalformedTag(String),
+
+    /// A "$" annotation was not a number up to 255, or a suffix annotation was not one of "!", "?", "!!", "??", "!?" and "?!".
+    BadAnnotation(String),
+
+    /// A character that cannot start a token.
+    UnexpectedCharacter(char),
+
+    /// A variation was opened before any move it could replace.
+    VariationBeforeFirstMove,
+
+    /// A variation was closed that was never opened.
+    UnmatchedParenthesis,
+
+    /// A variation was still open at the end of the game.
+    UnterminatedVariation,
+
+    /// A single game was expected, the number of games found is given.
+    WrongGameCount(usize),
+}
+
+impl PgnError {
+    fn new(game: usize, kind: PgnErrorKind) -> Self {
+        Self { game, move_number: None, kind }
+    }
+}
+
+impl fmt::Display for PgnError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "invalid pgn: ")?;
+        if self.game > 0 {
+            write!(f, "game {}: ", self.game)?;
+        }
+        if let Some(number) = self.move_number {
+            write!(f, "move {}: ", number)?;
+        }
+
+        match &self.kind {
+            PgnErrorKind::Fen(err) => write!(f, "{}", err),
+            PgnErrorKind::UnterminatedComment => write!(f, "unterminated comment"),
+            PgnErrorKind::UnterminatedTag(tag) => write!(f, "unterminated tag '[{}'", tag),
+            PgnErrorKind::MalformedTag(tag) => write!(f, "malformed tag '[{}]'", tag),
+            PgnErrorKind::BadAnnotation(annotation) => write!(f, "invalid annotation '{}'", annotation),
+            PgnErrorKind::UnexpectedCharacter(ch) => write!(f, "unexpected character '{}'", ch),
+            PgnErrorKind::VariationBeforeFirstMove => write!(f, "variation before the first move"),
+            PgnErrorKind::UnmatchedParenthesis => write!(f, "unmatched ')'"),
+            PgnErrorKind::UnterminatedVariation => write!(f, "unterminated variation"),
+            PgnErrorKind::WrongGameCount(count) => write!(f, "expected a single game, found {}", count),
+        }
+    }
+}
+
+impl std::error::Error for PgnError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match &self.kind {
+            PgnErrorKind::Fen(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+/// Parses every game of a PGN file, replaying all moves and variations to check that they are legal.
+pub fn read_pgn(text: &str) -> Result<Vec<PgnGame>, PgnError> {
+    let tokens: Vec<Token> = tokenize(text)?;
+
+    let mut games: Vec<PgnGame> = Vec::new();
+    let mut tags: Vec<(String, String)> = Vec::new();
+    let mut movetext: Vec<Token> = Vec::new();
+    let mut depth: usize = 0;
+
+    for token in tokens {
+        match token {
+            Token::Tag(name, value) => {
+                if !movetext.is_empty() {
+                    games.push(parse_game(std::mem::take(&mut tags), &movetext, None, games.len())?);
+                    movetext.clear();
+                }
+                tags.push((name, value));
+            },
+            Token::Result(outcome) if depth == 0 => {
+                games.push(parse_game(std::mem::take(&mut tags), &movetext, Some(outcome), games.len())?);
+                movetext.clear();
+            },
+            token => {
+                match token {
+                    Token::Open => depth += 1,
+                    Token::Close => depth = depth.saturating_sub(1),
+                    _ => {},
+                }
+                movetext.push(token);
+            },
+        }
+    }
+
+    if !tags.is_empty() || !movetext.is_empty() {
+        games.push(parse_game(tags, &movetext, None, games.len())?);
+    }
+
+    Ok(games)
+}
+
+/// Writes the games as a PGN file, separated by empty lines.
+pub fn write_pgn(games: &[PgnGame]) -> String {
+    games.iter().map(|game: &PgnGame| game.to_string()).collect::<Vec<String>>().join("\n")
+}
+
+/// Parses a single game in PGN.
+impl FromStr for PgnGame {
+    type Err = PgnError;
+
+    fn from_str(text: &str) -> Result<Self, PgnError> {
+        let mut games: Vec<PgnGame> = read_pgn(text)?;
+        if games.len() != 1 {
+            return Err(PgnError::new(0, PgnErrorKind::WrongGameCount(games.len())));
+        }
+
+        Ok(games.remove(0))
+    }
+}
+
+/// Formats the game in PGN: the seven tag roster, the other tags, and the movetext wrapped at 80 characters.
+impl fmt::Display for PgnGame {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        for name in SEVEN_TAG_ROSTER {
+            let value: &str = match name {
+                "Result" => self.result.pgn(),
+                "Date" => self.tag(name).unwrap_or("????.??.??"),
+                _ => self.tag(name).unwrap_or("?"),
+            };
+            writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+        }
+
+        for (name, value) in &self.tags {
+            if !SEVEN_TAG_ROSTER.contains(&name.as_str()) {
+                writeln!(f, "[{} \"{}\"]", name, escape(value))?;
+            }
+        }
+        writeln!(f)?;
+
+        let mut words: Vec<String> = Vec::new();
+        write_line(&mut words, &self.start, &self.moves);
+        words.push(self.result.pgn().to_string());
+
+        let mut line: String = String::new();
+        for word in words {
+            if !line.is_empty() && line.len() + 1 + word.len() > LINE_LENGTH {
+                writeln!(f, "{}", line)?;
+                line.clear();
+            }
+
+            if !line.is_empty() {
+                line.push(' ');
+            }
+            line.push_str(&word);
+        }
+        writeln!(f, "{}", line)
+    }
+}
+
+/// Appends the words of a line of moves in SAN, with move numbers, annotations and variations.
+fn write_line(words: &mut Vec<String>, start: &Fen, line: &[PgnMove]) {
+    let mut fen: Fen = start.clone();
+
+    // black's moves only get a number at the start of a line or after a comment or variation
+    let mut number_needed: bool = true;
+
+    for pgn_move in line {
+        if let Some(comment) = &pgn_move.comment_before {
+            push_comment(words, comment);
+            number_needed = true;
+        }
+
+        if fen.white_to_move {
+            words.push(format!("{}.", fen.fullmove));
+        } else if number_needed {
+            words.push(format!("{}...", fen.fullmove));
+        }
+
+        words.push(fen.move_to_san(&pgn_move.move1));
+        number_needed = false;
+
+        for nag in &pgn_move.nags {
+            words.push(format!("${}", nag));
+        }
+
+        if let Some(comment) = &pgn_move.comment {
+            push_comment(words, comment);
+            number_needed = true;
+        }
+
+        for variation in &pgn_move.variations {
+            let mut variation_words: Vec<String> = Vec::new();
+            write_line(&mut variation_words, &fen, variation);
+
+            if let Some(first) = variation_words.first_mut() {
+                first.insert(0, '(');
+            }
+            if let Some(last) = variation_words.last_mut() {
+                last.push(')');
+                number_needed = true;
+            }
+            words.append(&mut variation_words);
+        }
+
+        fen.make_move(pgn_move.move1);
+    }
+}
+
+/// Appends a comment in braces, split into words so that long comments can be wrapped.
+fn push_comment(words: &mut Vec<String>, comment: &str) {
+    // a closing brace would end the comment early
+    let comment: String = comment.replace('}', ")");
+    let mut comment_words: Vec<&str> = comment.split_whitespace().collect();
+    if comment_words.is_empty() {
+        comment_words.push("");
+    }
+
+    let length: usize = comment_words.len();
+    for (i, word) in comment_words.into_iter().enumerate() {
+        let open: &str = if i == 0 { "{" } else { "" };
+        let close: &str = if i == length - 1 { "}" } else { "" };
+        words.push(format!("{}{}{}", open, word, close));
+    }
+}
+
+fn escape(value: &str) -> String {
+    value.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// A token of a PGN file.
+#[derive(Debug, Clone, PartialEq, Eq)]
+enum Token {
+    Tag(String, String),
+    Comment(String),
+    Open,
+    Close,
+    Nag(u8),
+    Result(Outcome),
+    San(String),
+}
+
+/// Splits a PGN file into tokens, skipping move numbers and escaped lines.
+fn tokenize(text: &str) -> Result<Vec<Token>, PgnError> {
+    let chars: Vec<char> = text.chars().collect();
+    let mut tokens: Vec<Token> = Vec::new();
+    let mut i: usize = 0;
+
+    let error = |tokens: &[Token], kind: PgnErrorKind| PgnError::new(game_number(tokens), kind);
+
+    while i < chars.len() {
+        let ch: char = chars[i];
+        let line_start: bool = i == 0 || chars[i - 1] == '\n';
+        i += 1;
+
+        match ch {
+            _ if ch.is_whitespace() || ch == '.' => {},
+            '%' if line_start => {
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+            },
+            ';' => {
+                let start: usize = i;
+                while i < chars.len() && chars[i] != '\n' {
+                    i += 1;
+                }
+                tokens.push(Token::Comment(chars[start..i].iter().collect::<String>().trim().to_string()));
+            },
+            '{' => {
+                let start: usize = i;
+                while i < chars.len() && chars[i] != '}' {
+                    i += 1;
+                }
+                if i == chars.len() {
+                    return Err(error(&tokens, PgnErrorKind::UnterminatedComment));
+                }
+                let comment: String = chars[start..i].iter().collect();
+                tokens.push(Token::Comment(comment.split_whitespace().collect::<Vec<&str>>().join(" ")));
+                i += 1;
+            },
+            '[' => {
+                // the tag ends at the first bracket outside of the quoted value
+                let start: usize = i;
+                let mut quoted: bool = false;
+                while i < chars.len() && (quoted || chars[i] != ']') {
+                    match chars[i] {
+                        '"' => quoted = !quoted,
+                        '\\' if quoted => i += 1,
+                        _ => {},
+                    }
+                    i += 1;
+                }
+                let tag: String = chars[start..i.min(chars.len())].iter().collect();
+                if i >= chars.len() {
+                    return Err(error(&tokens, PgnErrorKind::UnterminatedTag(tag)));
+                }
+                tokens.push(parse_tag(&tag).ok_or_else(|| error(&tokens, PgnErrorKind::MalformedTag(tag)))?);
+                i += 1;
+            },
+            '(' => tokens.push(Token::Open),
+            ')' => tokens.push(Token::Close),
+            '*' => tokens.push(Token::Result(Outcome::Ongoing)),
+            '$' => {
+                let start: usize = i;
+                while i < chars.len() && chars[i].is_ascii_digit() {
+                    i += 1;
+                }
+                let nag: String = chars[start..i].iter().collect();
+                let nag: u8 = nag.parse().map_err(|_| error(&tokens, PgnErrorKind::BadAnnotation(format!("${}", nag))))?;
+                tokens.push(Token::Nag(nag));
+            },
+            '!' | '?' => {
+                let start: usize = i - 1;
+                while i < chars.len() && (chars[i] == '!' || chars[i] == '?') {
+                    i += 1;
+                }
+                let suffix: String = chars[start..i].iter().collect();
+                let nag: u8 = match suffix.as_str() {
+                    "!" => 1,
+                    "?" => 2,
+                    "!!" => 3,
+                    "??" => 4,
+                    "!?" => 5,
+                    "?!" => 6,
+                    _ => return Err(error(&tokens, PgnErrorKind::BadAnnotation(suffix))),
+                };
+                tokens.push(Token::Nag(nag));
+            },
+            _ => {
+                let start: usize = i - 1;
+                while i < chars.len() && (chars[i].is_alphanumeric() || "+#=:-/½".contains(chars[i])) {
+                    i += 1;
+                }
+                let symbol: String = chars[start..i].iter().collect();
+
+                if let Some(outcome) = Outcome::from_pgn(&symbol) {
+                    tokens.push(Token::Result(outcome));
+                } else if symbol.chars().all(|digit: char| digit.is_ascii_digit()) {
+                    // a move number, the dots after it are skipped on their own
+                } else if symbol.chars().next().is_some_and(|first: char| first.is_alphanumeric()) {
+                    tokens.push(Token::San(symbol));
+                } else {
+                    return Err(error(&tokens, PgnErrorKind::UnexpectedCharacter(ch)));
+                }
+            },
+        }
+    }
+
+    Ok(tokens)
+}
+
+/// Returns the number of the game the next token belongs to, splitting the tokens into games like `read_pgn`.
+fn game_number(tokens: &[Token]) -> usize {
+    let mut games: usize = 0;
+    let mut in_movetext: bool = false;
+    let mut depth: usize = 0;
+
+    for token in tokens {
+        match token {
+            Token::Tag(_, _) if in_movetext => {
+                games += 1;
+                in_movetext = false;
+            },
+            Token::Tag(_, _) => {},
+            Token::Result(_) if depth == 0 => {
+                games += 1;
+                in_movetext = false;
+            },
+            Token::Open => depth += 1,
+            Token::Close => depth = depth.saturating_sub(1),
+            _ => in_movetext = true,
+        }
+    }
+
+    games + 1
+}
+
+/// Parses the part of a tag pair between the brackets, e.g. `Event "Casual game"`, `None` if it is malformed.
+fn parse_tag(tag: &str) -> Option<Token> {
+    let (name, rest): (&str, &str) = tag.trim_start().split_once(char::is_whitespace)?;
+    let rest: &str = rest.trim_start().strip_prefix('"')?;
+
+    let mut value: String = String::new();
+    let mut chars = rest.chars();
+    loop {
+        match chars.next()? {
+            '\\' => value.push(chars.next()?),
+            '"' => break,
+            ch => value.push(ch),
+        }
+    }
+
+    if !chars.as_str().trim().is_empty() {
+        return None;
+    }
+
+    Some(Token::Tag(name.to_string(), value))
+}
+
+/// Builds a game from its tags and movetext, `result` is the result token that ended the movetext, if any.
+fn parse_game(tags: Vec<(String, String)>, movetext: &[Token], result: Option<Outcome>, index: usize) -> Result<PgnGame, PgnError> {
+    let mut game: PgnGame = PgnGame::new();
+    game.tags = tags;
+
+    if let Some(fen_str) = game.tag("FEN") {
+        game.start = fen_str.parse().map_err(|err: FenError| PgnError::new(index + 1, PgnErrorKind::Fen(err)))?;
+    }
+
+    let mut position: usize = 0;
+    game.moves = parse_line(movetext, &mut position, &game.start, false, index + 1)?;
+
+    game.result = match result {
+        Some(outcome) => outcome,
+        None => game.tag("Result").and_then(Outcome::from_pgn).unwrap_or(Outcome::Ongoing),
+    };
+
+    Ok(game)
+}
+
+/// Parses a line of moves starting from `start`, until the end of the movetext or, for a variation, the closing parenthesis.
+fn parse_line(tokens: &[Token], position: &mut usize, start: &Fen, nested: bool, game: usize) -> Result<Vec<PgnMove>, PgnError> {
+    let mut fen: Fen = start.clone();
+    let mut before: Fen = start.clone();
+    let mut line: Vec<PgnMove> = Vec::new();
+    let mut pending_comment: Option<String> = None;
+
+    while *position < tokens.len() {
+        let token: &Token = &tokens[*position];
+        *position += 1;
+
+        match token {
+            Token::San(san) => {
+                let move1: Move = fen
+                    .san_to_move(san)
+                    .map_err(|err: FenError| PgnError { game, move_number: Some(fen.fullmove), kind: PgnErrorKind::Fen(err) })?;
+
+                before = fen.clone();
+                fen.make_move(move1);
+
+                let mut pgn_move: PgnMove = PgnMove::new(move1);
+                pgn_move.comment_before = pending_comment.take();
+                line.push(pgn_move);
+            },
+            Token::Nag(nag) => {
+                if let Some(last) = line.last_mut() {
+                    last.nags.push(*nag);
+                }
+            },
+            Token::Comment(comment) => {
+                let target: &mut Option<String> = match line.last_mut() {
+                    Some(last) => &mut last.comment,
+                    None => &mut pending_comment,
+                };
+                *target = Some(match target.take() {
+                    Some(earlier) => format!("{} {}", earlier, comment),
+                    None => comment.clone(),
+                });
+            },
+            Token::Open => {
+                if line.is_empty() {
+                    return Err(PgnError::new(game, PgnErrorKind::VariationBeforeFirstMove));
+                }
+                let variation: Vec<PgnMove> = parse_line(tokens, position, &before, true, game)?;
+                if let Some(last) = line.last_mut() {
+                    last.variations.push(variation);
+                }
+            },
+            Token::Close if nested => return Ok(line),
+            Token::Close => return Err(PgnError::new(game, PgnErrorKind::UnmatchedParenthesis)),
+
+            // results inside variations carry no information for the game
+            Token::Result(_) | Token::Tag(_, _) => {},
+        }
+    }
+
+    if nested {
+        return Err(PgnError::new(game, PgnErrorKind::UnterminatedVariation));
+    }
+
+    Ok(line)
+}
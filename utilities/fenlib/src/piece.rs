@@ -0,0 +1,162 @@
+use std::fmt;
+use std::ops::Not;
+
+/// The color of a side or a piece.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Color {
+    White,
+    Black,
+}
+
+impl Color {
+    /// Returns the color to move given the `white_to_move` field of `Fen`.
+    pub const fn from_white_to_move(white_to_move: bool) -> Color {
+        if white_to_move {
+            Color::White
+        } else {
+            Color::Black
+        }
+    }
+
+    pub const fn is_white(self) -> bool {
+        matches!(self, Color::White)
+    }
+
+    /// Returns 0 for white and 1 for black, which is the offset of the color's pieces in `Fen::boards` divided by 6.
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    pub const fn opposite(self) -> Color {
+        match self {
+            Color::White => Color::Black,
+            Color::Black => Color::White,
+        }
+    }
+}
+
+impl Not for Color {
+    type Output = Color;
+
+    fn not(self) -> Color {
+        self.opposite()
+    }
+}
+
+/// The kind of a piece, regardless of its color.
+///
+/// The discriminant is the index of the white piece in `Fen::boards`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub enum PieceKind {
+    Pawn,
+    Knight,
+    Bishop,
+    Rook,
+    Queen,
+    King,
+}
+
+impl PieceKind {
+    /// All piece kinds in order of their index.
+    pub const ALL: [PieceKind; 6] = [
+        PieceKind::Pawn,
+        PieceKind::Knight,
+        PieceKind::Bishop,
+        PieceKind::Rook,
+        PieceKind::Queen,
+        PieceKind::King,
+    ];
+
+    pub const fn index(self) -> usize {
+        self as usize
+    }
+
+    /// Returns the lowercase character used for the piece in fen notation.
+    pub const fn to_char(self) -> char {
+        match self {
+            PieceKind::Pawn => 'p',
+            PieceKind::Knight => 'n',
+            PieceKind::Bishop => 'b',
+            PieceKind::Rook => 'r',
+            PieceKind::Queen => 'q',
+            PieceKind::King => 'k',
+        }
+    }
+
+    /// Parses the character used for the piece in fen notation, in either case.
+    pub const fn from_char(piece: char) -> Option<PieceKind> {
+        match piece.to_ascii_lowercase() {
+            'p' => Some(PieceKind::Pawn),
+            'n' => Some(PieceKind::Knight),
+            'b' => Some(PieceKind::Bishop),
+            'r' => Some(PieceKind::Rook),
+            'q' => Some(PieceKind::Queen),
+            'k' => Some(PieceKind::King),
+            _ => None,
+        }
+    }
+}
+
+/// A piece of a given color.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Piece {
+    pub color: Color,
+    pub kind: PieceKind,
+}
+
+impl Piece {
+    pub const WHITE_PAWN: Piece = Piece::new(Color::White, PieceKind::Pawn);
+    pub const WHITE_KNIGHT: Piece = Piece::new(Color::White, PieceKind::Knight);
+    pub const WHITE_BISHOP: Piece = Piece::new(Color::White, PieceKind::Bishop);
+    pub const WHITE_ROOK: Piece = Piece::new(Color::White, PieceKind::Rook);
+    pub const WHITE_QUEEN: Piece = Piece::new(Color::White, PieceKind::Queen);
+    pub const WHITE_KING: Piece = Piece::new(Color::White, PieceKind::King);
+    pub const BLACK_PAWN: Piece = Piece::new(Color::Black, PieceKind::Pawn);
+    pub const BLACK_KNIGHT: Piece = Piece::new(Color::Black, PieceKind::Knight);
+    pub const BLACK_BISHOP: Piece = Piece::new(Color::Black, PieceKind::Bishop);
+    pub const BLACK_ROOK: Piece = Piece::new(Color::Black, PieceKind::Rook);
+    pub const BLACK_QUEEN: Piece = Piece::new(Color::Black, PieceKind::Queen);
+    pub const BLACK_KING: Piece = Piece::new(Color::Black, PieceKind::King);
+
+    pub const fn new(color: Color, kind: PieceKind) -> Piece {
+        Piece { color, kind }
+    }
+
+    /// Returns the index of the piece in `Fen::boards`, see `parsing::piece_to_index` for the mapping.
+    pub const fn index(self) -> usize {
+        self.color.index() * 6 + self.kind.index()
+    }
+
+    /// Returns the piece stored at the given index of `Fen::boards`, or `None` if the index is not below 12.
+    pub const fn from_index(index: usize) -> Option<Piece> {
+        if index >= 12 {
+            return None;
+        }
+
+        let color: Color = if index < 6 { Color::White } else { Color::Black };
+        Some(Piece::new(color, PieceKind::ALL[index % 6]))
+    }
+
+    /// Returns the character used for the piece in fen notation, uppercase for white and lowercase for black.
+    pub const fn to_char(self) -> char {
+        match self.color {
+            Color::White => self.kind.to_char().to_ascii_uppercase(),
+            Color::Black => self.kind.to_char(),
+        }
+    }
+
+    /// Parses the character used for the piece in fen notation.
+    pub const fn from_char(piece: char) -> Option<Piece> {
+        let color: Color = if piece.is_ascii_uppercase() { Color::White } else { Color::Black };
+        match PieceKind::from_char(piece) {
+            Some(kind) => Some(Piece::new(color, kind)),
+            None => None,
+        }
+    }
+}
+
+impl fmt::Display for Piece {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.to_char())
+    }
+}
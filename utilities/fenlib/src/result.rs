@@ -0,0 +1,159 @@
+use std::fmt;
+
+use crate::Fen;
+use crate::piece::Color;
+
+/// Who won the game, if it has ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Outcome {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Ongoing,
+}
+
+impl Outcome {
+    /// Returns the result as written in PGN: "1-0", "0-1", "1/2-1/2" or "*" for a game that has not ended.
+    pub const fn pgn(self) -> &'static str {
+        match self {
+            Outcome::WhiteWins => "1-0",
+            Outcome::BlackWins => "0-1",
+            Outcome::Draw => "1/2-1/2",
+            Outcome::Ongoing => "*",
+        }
+    }
+
+    /// Parses a result as written in PGN, "½-½" is accepted for a draw as well.
+    pub fn from_pgn(result: &str) -> Option<Outcome> {
+        match result {
+            "1-0" => Some(Outcome::WhiteWins),
+            "0-1" => Some(Outcome::BlackWins),
+            "1/2-1/2" | "½-½" => Some(Outcome::Draw),
+            "*" => Some(Outcome::Ongoing),
+            _ => None,
+        }
+    }
+}
+
+/// Why the game ended.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Reason {
+    Checkmate,
+
+    /// The side to move has no legal moves and is not in check.
+    Stalemate,
+
+    /// 50 moves by each side without a capture or pawn move, a draw either player can claim.
+    FiftyMove,
+
+    /// 75 moves by each side without a capture or pawn move, which ends the game in a draw.
+    SeventyFiveMove,
+
+    /// The same position occurred three times, see `Game::is_threefold_repetition`.
+    Repetition,
+
+    /// Neither side has enough material left to checkmate, see `Fen::is_insufficient_material`.
+    InsufficientMaterial,
+
+    /// No sequence of legal moves can lead to checkmate although there is material left, see `Fen::is_dead_position`.
+    DeadPosition,
+
+    Resignation,
+    Timeout,
+
+    /// The game was stopped and scored by an arbiter or a match runner.
+    Adjudication,
+}
+
+impl fmt::Display for Reason {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let reason: &str = match self {
+            Reason::Checkmate => "checkmate",
+            Reason::Stalemate => "stalemate",
+            Reason::FiftyMove => "fifty move rule",
+            Reason::SeventyFiveMove => "seventy-five move rule",
+            Reason::Repetition => "threefold repetition",
+            Reason::InsufficientMaterial => "insufficient material",
+            Reason::DeadPosition => "dead position",
+            Reason::Resignation => "resignation",
+            Reason::Timeout => "timeout",
+            Reason::Adjudication => "adjudication",
+        };
+        write!(f, "{}", reason)
+    }
+}
+
+/// The result of a game together with the reason it ended, the reason is `None` while the game is ongoing.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct GameResult {
+    pub outcome: Outcome,
+    pub reason: Option<Reason>,
+}
+
+impl GameResult {
+    pub const ONGOING: GameResult = GameResult { outcome: Outcome::Ongoing, reason: None };
+
+    /// Creates the result of a game won by `winner`.
+    pub const fn win(winner: Color, reason: Reason) -> GameResult {
+        let outcome: Outcome = match winner {
+            Color::White => Outcome::WhiteWins,
+            Color::Black => Outcome::BlackWins,
+        };
+        GameResult { outcome, reason: Some(reason) }
+    }
+
+    pub const fn draw(reason: Reason) -> GameResult {
+        GameResult { outcome: Outcome::Draw, reason: Some(reason) }
+    }
+
+    pub const fn is_ongoing(self) -> bool {
+        matches!(self.outcome, Outcome::Ongoing)
+    }
+
+    /// Returns the color that won, or `None` for a draw or a game that has not ended.
+    pub const fn winner(self) -> Option<Color> {
+        match self.outcome {
+            Outcome::WhiteWins => Some(Color::White),
+            Outcome::BlackWins => Some(Color::Black),
+            _ => None,
+        }
+    }
+
+    /// Returns the result as written in PGN, see `Outcome::pgn`.
+    pub const fn pgn(self) -> &'static str {
+        self.outcome.pgn()
+    }
+}
+
+/// Formats the result for people, e.g. "white wins by checkmate" or "draw by stalemate".
+impl fmt::Display for GameResult {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let outcome: &str = match self.outcome {
+            Outcome::WhiteWins => "white wins",
+            Outcome::BlackWins => "black wins",
+            Outcome::Draw => "draw",
+            Outcome::Ongoing => "ongoing",
+        };
+
+        match self.reason {
+            Some(reason) => write!(f, "{} by {}", outcome, reason),
+            None => write!(f, "{}", outcome),
+        }
+    }
+}
+
+impl Fen {
+    /// Returns the result of the position: checkmate, one of the draws in `Fen::draw_reason`, or ongoing.
+    ///
+    /// **NOTE:** a single position knows nothing about repetitions, use `Game::result` for those
+    pub fn result(&self) -> GameResult {
+        if self.legal_moves().is_empty() && self.in_check() {
+            return GameResult::win(!self.side_to_move(), Reason::Checkmate);
+        }
+
+        match self.draw_reason() {
+            Some(reason) => GameResult::draw(reason),
+            None => GameResult::ONGOING,
+        }
+    }
+}
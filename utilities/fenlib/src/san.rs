@@ -0,0 +1,214 @@
+use crate::Fen;
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::error::FenError;
+use crate::piece::{Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::*;
+
+/// The parts of a move in SAN that are needed to find it among the legal moves.
+struct SanParts {
+    /// The moving piece, `None` if the move names its start square and may be any piece, like in LAN.
+    kind: Option<PieceKind>,
+    file: Option<u8>,
+    rank: Option<u8>,
+    end: Square,
+    promoting_to: u64,
+}
+
+impl Fen {
+    /// Converts a legal move into standard algebraic notation (SAN), e.g. "Nbd7", "exd8=Q+" or "O-O-O#".
+    ///
+    /// The start square is only given as far as needed to tell the move apart from the other legal moves,
+    /// a check is marked with "+" and a checkmate with "#".
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn move_to_san(&self, move1: &Move) -> String {
+        let mut san: String = if move1.flags() == Move::KING_CASTLE {
+            "O-O".to_string()
+        } else if move1.flags() == Move::QUEEN_CASTLE {
+            "O-O-O".to_string()
+        } else {
+            self.move_to_san_body(move1)
+        };
+
+        let mut after: Fen = self.clone();
+        after.make_move(*move1);
+        if after.in_check() {
+            san.push(if after.legal_moves().is_empty() { '#' } else { '+' });
+        }
+
+        san
+    }
+
+    /// Converts a legal move into SAN without the castling notation and the check suffix.
+    fn move_to_san_body(&self, move1: &Move) -> String {
+        let start: Square = move1.start_square();
+        let end: Square = move1.end_square();
+        let kind: PieceKind = match self.piece_on(start) {
+            Some(piece) => piece.kind,
+            None => PieceKind::Pawn,
+        };
+
+        let mut san: String = String::new();
+        if kind == PieceKind::Pawn {
+            // a pawn capture is always written with the file it comes from
+            if move1.is_capture() {
+                san.push(file_char(start));
+            }
+        } else {
+            san.push(kind.to_char().to_ascii_uppercase());
+            san.push_str(&self.disambiguation(move1, kind));
+        }
+
+        if move1.is_capture() {
+            san.push('x');
+        }
+        san.push_str(&end.to_string());
+
+        match move1.promotion() {
+            QUEEN_PROM => san.push_str("=Q"),
+            ROOK_PROM => san.push_str("=R"),
+            BISHOP_PROM => san.push_str("=B"),
+            KNIGHT_PROM => san.push_str("=N"),
+            _ => {},
+        }
+
+        san
+    }
+
+    /// Returns the part of the start square needed to tell the move apart from legal moves of
+    /// other pieces of the same kind to the same square: nothing, the file, the rank or the whole square.
+    fn disambiguation(&self, move1: &Move, kind: PieceKind) -> String {
+        let start: Square = move1.start_square();
+        let piece: Piece = Piece::new(self.side_to_move(), kind);
+
+        let mut others: Bitboard = Bitboard::EMPTY;
+        for other in &self.legal_moves() {
+            if other.end_square() == move1.end_square() && other.start_square() != start && self.piece_on(other.start_square()) == Some(piece) {
+                others |= Bitboard::from(other.start_square());
+            }
+        }
+
+        if others.is_empty() {
+            String::new()
+        } else if others.into_iter().all(|other: Square| other.file() != start.file()) {
+            file_char(start).to_string()
+        } else if others.into_iter().all(|other: Square| other.rank() != start.rank()) {
+            rank_char(start).to_string()
+        } else {
+            start.to_string()
+        }
+    }
+
+    /// Parses a move in SAN and returns the legal move it describes.
+    ///
+    /// Common variants are accepted as well: castling with zeros ("0-0"), missing or superfluous check
+    /// and capture marks, annotations like "!?", a promotion without "=" ("e8Q") and a fully given
+    /// start square ("Ng1f3", "e2e4").
+    pub fn san_to_move(&self, san: &str) -> Result<Move, FenError> {
+        let trimmed: &str = san.trim().trim_end_matches(['+', '#', '!', '?']);
+        let trimmed: &str = trimmed.strip_suffix("e.p.").unwrap_or(trimmed).trim_end();
+
+        if let Some(flags) = castling_flags(trimmed) {
+            return self.legal_moves().iter().copied()
+                .find(|move1: &Move| move1.flags() == flags)
+                .ok_or(FenError::IllegalSan(san.to_string()));
+        }
+
+        let parts: SanParts = parse_san(trimmed).ok_or(FenError::BadSan(san.to_string()))?;
+
+        let mut found: Option<Move> = None;
+        for &move1 in &self.legal_moves() {
+            let start: Square = move1.start_square();
+            let kind: Option<PieceKind> = self.piece_on(start).map(|piece: Piece| piece.kind);
+
+            let matches: bool = move1.end_square() == parts.end
+                && parts.kind.is_none_or(|expected: PieceKind| kind == Some(expected))
+                && parts.file.is_none_or(|file: u8| start.file() == file)
+                && parts.rank.is_none_or(|rank: u8| start.rank() == rank)
+                && move1.promotion() == parts.promoting_to;
+
+            if matches {
+                if found.is_some() {
+                    return Err(FenError::AmbiguousSan(san.to_string()));
+                }
+                found = Some(move1);
+            }
+        }
+
+        found.ok_or(FenError::IllegalSan(san.to_string()))
+    }
+}
+
+/// Returns the flags of the castle written as "O-O" or "O-O-O", with the letter O or zeros.
+fn castling_flags(san: &str) -> Option<u16> {
+    match san {
+        "O-O" | "0-0" | "o-o" => Some(Move::KING_CASTLE),
+        "O-O-O" | "0-0-0" | "o-o-o" => Some(Move::QUEEN_CASTLE),
+        _ => None,
+    }
+}
+
+/// Splits a move in SAN that is not a castle into its parts, or returns `None` if it is malformed.
+fn parse_san(san: &str) -> Option<SanParts> {
+    if !san.is_ascii() {
+        return None;
+    }
+
+    let mut rest: &str = san;
+
+    // the piece letter is uppercase, so a lowercase "b" is always the b-file
+    let first: char = rest.chars().next()?;
+    let mut kind: Option<PieceKind> = if matches!(first, 'N' | 'B' | 'R' | 'Q' | 'K') {
+        rest = &rest[1..];
+        PieceKind::from_char(first)
+    } else {
+        Some(PieceKind::Pawn)
+    };
+
+    let mut promoting_to: u64 = NO_PROM;
+    if let Some(last) = rest.chars().last().filter(|last: &char| last.is_ascii_alphabetic()) {
+        promoting_to = match last.to_ascii_uppercase() {
+            'Q' => QUEEN_PROM,
+            'R' => ROOK_PROM,
+            'B' => BISHOP_PROM,
+            'N' => KNIGHT_PROM,
+            _ => return None,
+        };
+        rest = &rest[..rest.len() - 1];
+        rest = rest.strip_suffix(['=', '/']).unwrap_or(rest);
+    }
+
+    if rest.len() < 2 {
+        return None;
+    }
+
+    let end: Square = rest[rest.len() - 2..].parse().ok()?;
+
+    let mut file: Option<u8> = None;
+    let mut rank: Option<u8> = None;
+    for ch in rest[..rest.len() - 2].chars() {
+        match ch {
+            'a'..='h' if file.is_none() && rank.is_none() => file = Some(ch as u8 - b'a'),
+            '1'..='8' if rank.is_none() => rank = Some(ch as u8 - b'1'),
+            'x' | ':' | '-' => {},
+            _ => return None,
+        }
+    }
+
+    // a move that names its whole start square without a piece letter is LAN, it can be any piece
+    if kind == Some(PieceKind::Pawn) && file.is_some() && rank.is_some() {
+        kind = None;
+    }
+
+    Some(SanParts { kind, file, rank, end, promoting_to })
+}
+
+fn file_char(square: Square) -> char {
+    (b'a' + square.file()) as char
+}
+
+fn rank_char(square: Square) -> char {
+    (b'1' + square.rank()) as char
+}
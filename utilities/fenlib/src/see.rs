@@ -0,0 +1,179 @@
+//! Static Exchange Evaluation (https://www.chessprogramming.org/Static_Exchange_Evaluation).
+//!
+//! Plays out every capture on the end square of a move, each side always capturing with its least valuable
+//! piece and free to stop when going on would lose material. Sliding pieces behind the capturing pieces join
+//! in once the way is clear (x-rays). Pins and checks are ignored, so the result is an estimate.
+
+use crate::Fen;
+use crate::bitboard::Bitboard;
+use crate::chess_move::Move;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::*;
+
+/// The values of the pieces in centipawns, indexed by `PieceKind::index`, from
+/// https://www.chessprogramming.org/Simplified_Evaluation_Function. The king can never be captured,
+/// its value only has to be larger than everything else.
+pub const SEE_VALUES: [i32; 6] = [100, 320, 330, 500, 900, 20_000];
+
+/// Returns the value of the piece kind in centipawns, see `SEE_VALUES`.
+pub fn see_value(kind: PieceKind) -> i32 {
+    SEE_VALUES[kind.index()]
+}
+
+impl Fen {
+    /// Returns the material the side to move wins, or loses if negative, when both sides keep capturing
+    /// on the end square of the move for as long as it pays off.
+    ///
+    /// **NOTE:** the move has to be a move of the side to move, castling is scored as 0
+    pub fn see(&self, move1: Move) -> i32 {
+        let start: Square = move1.start_square();
+        let end: Square = move1.end_square();
+        let Some(moving) = self.piece_on(start) else {
+            return 0;
+        };
+        if move1.is_castle() {
+            return 0;
+        }
+
+        let (mut occupancy, captured): (Bitboard, i32) = self.first_capture(move1, moving);
+        let mut on_square: PieceKind = promotion_kind(move1).unwrap_or(moving.kind);
+
+        // gains[n] is what the side making the n-th capture wins if the exchange stops right after it
+        let mut gains: [i32; 32] = [0; 32];
+        gains[0] = captured + see_value(on_square) - see_value(moving.kind);
+
+        let mut attackers: Bitboard = self.attackers_to(end, occupancy) & occupancy;
+        let mut side: Color = moving.color;
+        let mut depth: usize = 0;
+
+        while depth + 1 < gains.len() {
+            side = !side;
+
+            let Some((square, kind)) = self.least_valuable_attacker(attackers & self.occupied_by(side)) else {
+                break;
+            };
+
+            // recomputing the attackers without the capturing piece adds the sliders behind it
+            occupancy &= !Bitboard::from(square);
+            attackers = self.attackers_to(end, occupancy) & occupancy;
+
+            // the king may only capture if the opponent has nothing left to take back with
+            if kind == PieceKind::King && !(attackers & self.occupied_by(!side)).is_empty() {
+                break;
+            }
+
+            depth += 1;
+            gains[depth] = see_value(on_square) - gains[depth - 1];
+            on_square = kind;
+        }
+
+        // each side only keeps capturing if it does better than stopping
+        while depth > 0 {
+            gains[depth - 1] = -(-gains[depth - 1]).max(gains[depth]);
+            depth -= 1;
+        }
+
+        gains[0]
+    }
+
+    /// Checks whether the move wins at least `threshold` centipawns in the exchange on its end square, see `Fen::see`.
+    /// `fen.see_ge(move1, 0)` tells whether a capture does not lose material.
+    ///
+    /// Cheaper than `Fen::see`: instead of playing out the whole exchange it stops as soon as one side
+    /// comes out on the right side of the threshold whatever the other side does.
+    pub fn see_ge(&self, move1: Move, threshold: i32) -> bool {
+        let end: Square = move1.end_square();
+        let Some(moving) = self.piece_on(move1.start_square()) else {
+            return threshold <= 0;
+        };
+        if move1.is_castle() {
+            return threshold <= 0;
+        }
+
+        let (mut occupancy, captured): (Bitboard, i32) = self.first_capture(move1, moving);
+        let on_square: PieceKind = promotion_kind(move1).unwrap_or(moving.kind);
+
+        // the move fails if stopping right after it is already below the threshold,
+        // and succeeds if losing the piece on the square still leaves the side at or above it
+        let mut swap: i32 = captured + see_value(on_square) - see_value(moving.kind) - threshold;
+        if swap < 0 {
+            return false;
+        }
+        swap = see_value(on_square) - swap;
+        if swap <= 0 {
+            return true;
+        }
+
+        let mut attackers: Bitboard = self.attackers_to(end, occupancy) & occupancy;
+        let mut side: Color = moving.color;
+
+        // whether the side to move reaches the threshold if the exchange stops after the last capture
+        let mut reached: bool = true;
+
+        loop {
+            side = !side;
+
+            let Some((square, kind)) = self.least_valuable_attacker(attackers & self.occupied_by(side)) else {
+                break;
+            };
+
+            occupancy &= !Bitboard::from(square);
+            attackers = self.attackers_to(end, occupancy) & occupancy;
+
+            if kind == PieceKind::King && !(attackers & self.occupied_by(!side)).is_empty() {
+                break;
+            }
+
+            // `swap` is what the side that just captured is ahead of the threshold, from its own view,
+            // if it loses the piece it captured with; while that is not negative it does not need to go on
+            reached = !reached;
+            swap = see_value(kind) - swap;
+            if swap < i32::from(reached) {
+                break;
+            }
+        }
+
+        reached
+    }
+
+    /// Returns the occupancy after the move left its start square and the value of the piece it captures.
+    fn first_capture(&self, move1: Move, moving: Piece) -> (Bitboard, i32) {
+        let end: Square = move1.end_square();
+        let mut occupancy: Bitboard = self.occupied() & !Bitboard::from(move1.start_square());
+
+        if move1.is_en_passant() {
+            // the captured pawn is behind the end square and no longer blocks a slider
+            occupancy &= !Bitboard(match moving.color {
+                Color::White => end.bit() >> 8,
+                Color::Black => end.bit() << 8,
+            });
+            return (occupancy, see_value(PieceKind::Pawn));
+        }
+
+        (occupancy, self.piece_on(end).map_or(0, |piece: Piece| see_value(piece.kind)))
+    }
+
+    /// Returns the square and kind of the least valuable piece among `attackers`.
+    fn least_valuable_attacker(&self, attackers: Bitboard) -> Option<(Square, PieceKind)> {
+        if attackers.is_empty() {
+            return None;
+        }
+
+        PieceKind::ALL.iter().find_map(|&kind: &PieceKind| {
+            let pieces: Bitboard = attackers & (self.pieces(Piece::new(Color::White, kind)) | self.pieces(Piece::new(Color::Black, kind)));
+            pieces.lsb().map(|square: Square| (square, kind))
+        })
+    }
+}
+
+/// Returns the kind of piece the move promotes to, `None` if it is not a promotion.
+fn promotion_kind(move1: Move) -> Option<PieceKind> {
+    match move1.promotion() {
+        QUEEN_PROM => Some(PieceKind::Queen),
+        ROOK_PROM => Some(PieceKind::Rook),
+        BISHOP_PROM => Some(PieceKind::Bishop),
+        KNIGHT_PROM => Some(PieceKind::Knight),
+        _ => None,
+    }
+}
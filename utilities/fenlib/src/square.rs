@@ -0,0 +1,121 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::error::FenError;
+
+/// A square on the chess board.
+///
+/// The discriminant of every square is its bit index in the bitboards of `Fen`, so the board is numbered
+/// from h1 = 0 to a8 = 63, with the files running from h to a within a rank.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[repr(u8)]
+pub enum Square {
+    H1 = 0, G1 = 1, F1 = 2, E1 = 3, D1 = 4, C1 = 5, B1 = 6, A1 = 7,
+    H2 = 8, G2 = 9, F2 = 10, E2 = 11, D2 = 12, C2 = 13, B2 = 14, A2 = 15,
+    H3 = 16, G3 = 17, F3 = 18, E3 = 19, D3 = 20, C3 = 21, B3 = 22, A3 = 23,
+    H4 = 24, G4 = 25, F4 = 26, E4 = 27, D4 = 28, C4 = 29, B4 = 30, A4 = 31,
+    H5 = 32, G5 = 33, F5 = 34, E5 = 35, D5 = 36, C5 = 37, B5 = 38, A5 = 39,
+    H6 = 40, G6 = 41, F6 = 42, E6 = 43, D6 = 44, C6 = 45, B6 = 46, A6 = 47,
+    H7 = 48, G7 = 49, F7 = 50, E7 = 51, D7 = 52, C7 = 53, B7 = 54, A7 = 55,
+    H8 = 56, G8 = 57, F8 = 58, E8 = 59, D8 = 60, C8 = 61, B8 = 62, A8 = 63,
+}
+
+impl Square {
+    /// All squares in order of their index.
+    pub const ALL: [Square; 64] = [
+        Square::H1, Square::G1, Square::F1, Square::E1, Square::D1, Square::C1, Square::B1, Square::A1,
+        Square::H2, Square::G2, Square::F2, Square::E2, Square::D2, Square::C2, Square::B2, Square::A2,
+        Square::H3, Square::G3, Square::F3, Square::E3, Square::D3, Square::C3, Square::B3, Square::A3,
+        Square::H4, Square::G4, Square::F4, Square::E4, Square::D4, Square::C4, Square::B4, Square::A4,
+        Square::H5, Square::G5, Square::F5, Square::E5, Square::D5, Square::C5, Square::B5, Square::A5,
+        Square::H6, Square::G6, Square::F6, Square::E6, Square::D6, Square::C6, Square::B6, Square::A6,
+        Square::H7, Square::G7, Square::F7, Square::E7, Square::D7, Square::C7, Square::B7, Square::A7,
+        Square::H8, Square::G8, Square::F8, Square::E8, Square::D8, Square::C8, Square::B8, Square::A8,
+    ];
+
+    /// Returns the square with the given bit index, or `None` if the index is not below 64.
+    pub const fn from_index(index: u8) -> Option<Square> {
+        if index < 64 {
+            Some(Self::ALL[index as usize])
+        } else {
+            None
+        }
+    }
+
+    /// Returns the square on the given file (0 = a) and rank (0 = rank 1), or `None` if either is out of range.
+    pub const fn new(file: u8, rank: u8) -> Option<Square> {
+        if file < 8 && rank < 8 {
+            Self::from_index(rank * 8 + (7 - file))
+        } else {
+            None
+        }
+    }
+
+    /// Returns the square of a bitboard with exactly one bit set, or `None` otherwise.
+    pub const fn from_bit(bit: u64) -> Option<Square> {
+        if bit.count_ones() == 1 {
+            Self::from_index(bit.trailing_zeros() as u8)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the bit index of the square.
+    pub const fn index(self) -> u8 {
+        self as u8
+    }
+
+    /// Returns the file of the square, 0 is the a-file and 7 the h-file.
+    pub const fn file(self) -> u8 {
+        7 - (self as u8 % 8)
+    }
+
+    /// Returns the rank of the square, 0 is rank 1 and 7 is rank 8.
+    pub const fn rank(self) -> u8 {
+        self as u8 / 8
+    }
+
+    /// Returns the bitboard with only this square set.
+    pub const fn bit(self) -> u64 {
+        1u64 << self as u8
+    }
+
+    /// Returns the square `files` files to the right (towards h) and `ranks` ranks up (towards 8),
+    /// or `None` if that square is not on the board. Unlike a raw bit shift, this never wraps around the board.
+    pub const fn offset(self, files: i8, ranks: i8) -> Option<Square> {
+        let file: i8 = self.file() as i8 + files;
+        let rank: i8 = self.rank() as i8 + ranks;
+
+        if file < 0 || rank < 0 {
+            return None;
+        }
+
+        Self::new(file as u8, rank as u8)
+    }
+
+    /// Returns the same square seen from the other side of the board (e.g. e2 becomes e7).
+    pub const fn flip(self) -> Square {
+        Self::ALL[(self as u8 ^ 56) as usize]
+    }
+}
+
+/// Formats the square in algebraic notation, e.g. "e4".
+impl fmt::Display for Square {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}{}", (b'a' + self.file()) as char, (b'1' + self.rank()) as char)
+    }
+}
+
+/// Parses a square in algebraic notation, e.g. "e4".
+impl FromStr for Square {
+    type Err = FenError;
+
+    fn from_str(tile: &str) -> Result<Self, FenError> {
+        let bytes: &[u8] = tile.as_bytes();
+        if bytes.len() != 2 || !(b'a'..=b'h').contains(&bytes[0]) || !(b'1'..=b'8').contains(&bytes[1]) {
+            return Err(FenError::BadTile(tile.to_string()));
+        }
+
+        Self::new(bytes[0] - b'a', bytes[1] - b'1').ok_or(FenError::BadTile(tile.to_string()))
+    }
+}
@@ -0,0 +1,159 @@
+use std::time::{Duration, Instant};
+
+use crate::Fen;
+use crate::bot::SearchLimits;
+use crate::chess_move::Move;
+use crate::piece::{Color, Piece, PieceKind};
+
+/// The number of moves the clock is divided over at the start of the game, when all pieces are on the board.
+const OPENING_MOVES_TO_GO: u32 = 40;
+
+/// The number of moves the clock is divided over in an endgame without pieces, only pawns and kings.
+const ENDGAME_MOVES_TO_GO: u32 = 20;
+
+/// The non pawn material at the start of the game, counting 1 for a knight or bishop, 2 for a rook and 4 for a queen.
+const MAX_PHASE: u32 = 24;
+
+/// How many times the planned time the search may use at most, when the best move keeps changing.
+const HARD_FACTOR: u32 = 4;
+
+/// The part of the planned time in percent that is used, by the number of iterations the best move has not changed.
+const STABILITY_PERCENT: [u32; 5] = [140, 100, 80, 65, 50];
+
+/// Decides how long a bot searches: it turns the limits of a search into a soft and a hard limit on the time.
+///
+/// The soft limit is checked after every iteration: a new iteration is only started before it is reached.
+/// It shrinks when the best move stays the same over several iterations and grows when the best move changes,
+/// unless the search was given a movetime, which is always used in full.
+/// The hard limit, like the node limit, aborts the search immediately, even in the middle of an iteration.
+#[derive(Debug, Clone)]
+pub struct TimeManager {
+    start: Instant,
+    soft: Option<Duration>,
+    hard: Option<Duration>,
+    max_depth: Option<u32>,
+    max_nodes: Option<u64>,
+
+    /// Whether there is only one legal move, then there is no need to search deeper than the first iteration.
+    forced: bool,
+
+    /// Whether the time limits come from a movetime instead of the clock, then they are not adjusted.
+    fixed: bool,
+
+    best_move: Move,
+    stable_iterations: usize,
+}
+
+impl TimeManager {
+    /// Starts the clock of a search in the position.
+    pub fn new(limits: &SearchLimits, fen: &Fen) -> Self {
+        let (soft, hard): (Option<Duration>, Option<Duration>) = if limits.infinite {
+            (None, None)
+        } else {
+            allocate(limits, fen)
+        };
+
+        Self {
+            start: Instant::now(),
+            soft,
+            hard,
+            max_depth: if limits.infinite { None } else { limits.depth },
+            max_nodes: if limits.infinite { None } else { limits.nodes },
+            forced: fen.legal_moves().len() == 1,
+            fixed: !limits.infinite && limits.movetime.is_some(),
+            best_move: Move::NULL,
+            stable_iterations: 0,
+        }
+    }
+
+    /// Returns the time since the search started.
+    pub fn elapsed(&self) -> Duration {
+        self.start.elapsed()
+    }
+
+    /// Returns the time after which no new iteration is started, before it is adjusted for the stability of the best move.
+    pub fn soft_limit(&self) -> Option<Duration> {
+        self.soft
+    }
+
+    /// Returns the time after which the search is aborted.
+    pub fn hard_limit(&self) -> Option<Duration> {
+        self.hard
+    }
+
+    /// Checks whether the search has to be aborted right away, because the hard time limit or the node limit is reached.
+    pub fn is_out_of_time(&self, nodes: u64) -> bool {
+        self.hard.is_some_and(|hard: Duration| self.elapsed() >= hard) || self.max_nodes.is_some_and(|max_nodes: u64| nodes >= max_nodes)
+    }
+
+    /// Records that the search completed `depth` with `best_move` and checks whether it should search one depth deeper.
+    pub fn next_iteration(&mut self, depth: u32, best_move: Move) -> bool {
+        if best_move == self.best_move {
+            self.stable_iterations += 1;
+        } else {
+            self.stable_iterations = 0;
+            self.best_move = best_move;
+        }
+
+        if self.max_depth.is_some_and(|max_depth: u32| depth >= max_depth) {
+            return false;
+        }
+
+        // with a single legal move the time is better saved for later on the clock, a movetime cannot be saved
+        if self.forced && self.soft.is_some() && !self.fixed {
+            return false;
+        }
+
+        self.adjusted_soft_limit().is_none_or(|soft: Duration| self.elapsed() < soft)
+    }
+
+    /// Returns the soft limit, shrunk when the best move has been stable and grown when it just changed.
+    pub fn adjusted_soft_limit(&self) -> Option<Duration> {
+        if self.fixed {
+            return self.soft;
+        }
+
+        let percent: u32 = STABILITY_PERCENT[self.stable_iterations.min(STABILITY_PERCENT.len() - 1)];
+        let soft: Duration = self.soft? * percent / 100;
+
+        Some(self.hard.map_or(soft, |hard: Duration| soft.min(hard)))
+    }
+}
+
+/// Returns the soft and hard time limit for the side to move, `None` if the limits do not restrict the time.
+fn allocate(limits: &SearchLimits, fen: &Fen) -> (Option<Duration>, Option<Duration>) {
+    if let Some(movetime) = limits.movetime {
+        let movetime: Duration = movetime.saturating_sub(limits.move_overhead).max(Duration::from_millis(1));
+        return (Some(movetime), Some(movetime));
+    }
+
+    let (clock, increment): (Option<Duration>, Duration) = match fen.side_to_move() {
+        Color::White => (limits.wtime, limits.winc),
+        Color::Black => (limits.btime, limits.binc),
+    };
+    let Some(clock) = clock else {
+        return (None, None);
+    };
+
+    let available: Duration = clock.saturating_sub(limits.move_overhead);
+    let moves_to_go: u32 = limits.movestogo.filter(|&moves: &u32| moves > 0).unwrap_or_else(|| expected_moves_to_go(fen));
+
+    let hard: Duration = (available / moves_to_go * HARD_FACTOR + increment).min(available * 3 / 4).max(Duration::from_millis(1));
+    let soft: Duration = (available / moves_to_go + increment * 3 / 4).min(hard);
+
+    (Some(soft), Some(hard))
+}
+
+/// Estimates how many more moves the side to move has to play on its clock from the material left on the board:
+/// fewer pieces means the game is closer to its end, so every move gets a larger part of the clock.
+pub fn expected_moves_to_go(fen: &Fen) -> u32 {
+    let phase: u32 = [(PieceKind::Knight, 1), (PieceKind::Bishop, 1), (PieceKind::Rook, 2), (PieceKind::Queen, 4)]
+        .iter()
+        .map(|&(kind, weight): &(PieceKind, u32)| {
+            weight * (fen.pieces(Piece::new(Color::White, kind)).popcount() + fen.pieces(Piece::new(Color::Black, kind)).popcount())
+        })
+        .sum::<u32>()
+        .min(MAX_PHASE);
+
+    ENDGAME_MOVES_TO_GO + (OPENING_MOVES_TO_GO - ENDGAME_MOVES_TO_GO) * phase / MAX_PHASE
+}
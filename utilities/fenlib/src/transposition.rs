@@ -0,0 +1,201 @@
+use std::mem;
+
+use crate::bot::{MATE_SCORE, MAX_PLY};
+use crate::chess_move::Move;
+
+/// The size of a transposition table in MB when the bot does not set one.
+pub const DEFAULT_HASH_MB: usize = 16;
+
+/// The number of entries `TranspositionTable::hashfull` samples.
+const HASHFULL_SAMPLE: usize = 1000;
+
+/// How a stored score relates to the real score of the position, alpha-beta does not always find the exact score.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Bound {
+    /// The slot is empty.
+    #[default]
+    None,
+
+    /// The score is the exact score of the position.
+    Exact,
+
+    /// The search failed high: the real score is at least the stored score.
+    Lower,
+
+    /// The search failed low: the real score is at most the stored score.
+    Upper,
+}
+
+/// What the search found out about a position, packed into 16 bytes.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Entry {
+    /// The full Zobrist hash of the position, to tell it apart from others sharing the slot.
+    key: u64,
+    best_move: Move,
+    score: i16,
+    depth: u8,
+    bound: Bound,
+
+    /// The search that stored the entry, see `TranspositionTable::new_search`.
+    age: u8,
+}
+
+impl Entry {
+    /// Returns the move that was best or caused the cutoff, `Move::NULL` if the search did not find one.
+    pub fn best_move(&self) -> Move {
+        self.best_move
+    }
+
+    /// Returns the depth the position was searched to.
+    pub fn depth(&self) -> u32 {
+        self.depth as u32
+    }
+
+    pub fn bound(&self) -> Bound {
+        self.bound
+    }
+
+    /// Returns the stored score with mates counted from the root again, see `TranspositionTable::store`.
+    pub fn score(&self, ply: u32) -> i32 {
+        score_from_table(self.score as i32, ply)
+    }
+
+    /// Returns the score if it decides the search of the position at `depth` with the window `alpha`..`beta`:
+    /// the entry has to be searched at least as deep, and its bound has to be exact or beyond the window.
+    pub fn cutoff(&self, depth: u32, ply: u32, alpha: i32, beta: i32) -> Option<i32> {
+        if self.depth() < depth {
+            return None;
+        }
+
+        let score: i32 = self.score(ply);
+        match self.bound {
+            Bound::Exact => Some(score),
+            Bound::Lower if score >= beta => Some(score),
+            Bound::Upper if score <= alpha => Some(score),
+            _ => None,
+        }
+    }
+}
+
+/// A hash table of searched positions (https://www.chessprogramming.org/Transposition_Table),
+/// so a position reached again, by another move order or in the next iteration, does not have to be searched again.
+///
+/// The table has a fixed size, every position has a single slot picked by its Zobrist hash.
+/// When two positions want the same slot the entry of the current search that was searched deeper wins,
+/// entries of earlier searches are always replaced.
+#[derive(Debug, Clone)]
+pub struct TranspositionTable {
+    entries: Vec<Entry>,
+    age: u8,
+}
+
+impl Default for TranspositionTable {
+    fn default() -> Self {
+        Self::new(DEFAULT_HASH_MB)
+    }
+}
+
+impl TranspositionTable {
+    /// Creates an empty table that takes up `mb` MB, at least one entry.
+    pub fn new(mb: usize) -> Self {
+        let len: usize = (mb * 1024 * 1024 / mem::size_of::<Entry>()).max(1);
+
+        Self { entries: vec![Entry::default(); len], age: 0 }
+    }
+
+    /// Returns the number of entries the table can hold.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Changes the size of the table to `mb` MB, this clears it.
+    pub fn resize(&mut self, mb: usize) {
+        *self = Self::new(mb);
+    }
+
+    /// Removes all entries, e.g. when a new game starts.
+    pub fn clear(&mut self) {
+        self.entries.fill(Entry::default());
+        self.age = 0;
+    }
+
+    /// Marks the start of a new search, the entries of earlier searches are replaced first.
+    pub fn new_search(&mut self) {
+        self.age = self.age.wrapping_add(1);
+    }
+
+    /// Returns the entry of the position with the hash, `None` if the table does not know it.
+    pub fn probe(&self, hash: u64) -> Option<Entry> {
+        let entry: Entry = self.entries[self.index(hash)];
+
+        (entry.bound != Bound::None && entry.key == hash).then_some(entry)
+    }
+
+    /// Stores what the search found about the position with the hash at `ply` from the root.
+    ///
+    /// Mate scores are stored as the distance to mate from the position instead of from the root,
+    /// so they are still right when the position is reached at another ply.
+    pub fn store(&mut self, hash: u64, depth: u32, ply: u32, score: i32, bound: Bound, best_move: Move) {
+        let index: usize = self.index(hash);
+        let old: Entry = self.entries[index];
+
+        let replace: bool = old.bound == Bound::None
+            || old.key == hash
+            || old.age != self.age
+            || bound == Bound::Exact
+            || depth >= old.depth();
+        if !replace {
+            return;
+        }
+
+        // a search that found no best move keeps the one found earlier for the same position
+        let best_move: Move = if best_move.is_null() && old.key == hash { old.best_move } else { best_move };
+
+        self.entries[index] = Entry {
+            key: hash,
+            best_move,
+            score: score_to_table(score, ply) as i16,
+            depth: depth.min(u8::MAX as u32) as u8,
+            bound,
+            age: self.age,
+        };
+    }
+
+    /// Returns how full the table is in permill, counting only the entries of the current search, as UCI reports it.
+    pub fn hashfull(&self) -> u32 {
+        let sample: &[Entry] = &self.entries[..self.entries.len().min(HASHFULL_SAMPLE)];
+        let used: usize = sample.iter().filter(|entry: &&Entry| entry.bound != Bound::None && entry.age == self.age).count();
+
+        (used * 1000 / sample.len()) as u32
+    }
+
+    fn index(&self, hash: u64) -> usize {
+        (hash % self.entries.len() as u64) as usize
+    }
+}
+
+/// Turns a mate score counted from the root into one counted from the position at `ply`.
+fn score_to_table(score: i32, ply: u32) -> i32 {
+    if score > MATE_SCORE - MAX_PLY as i32 {
+        score + ply as i32
+    } else if score < MAX_PLY as i32 - MATE_SCORE {
+        score - ply as i32
+    } else {
+        score
+    }
+}
+
+/// Turns a mate score counted from the position at `ply` back into one counted from the root.
+fn score_from_table(score: i32, ply: u32) -> i32 {
+    if score > MATE_SCORE - MAX_PLY as i32 {
+        score - ply as i32
+    } else if score < MAX_PLY as i32 - MATE_SCORE {
+        score + ply as i32
+    } else {
+        score
+    }
+}
@@ -0,0 +1,136 @@
+use crate::Fen;
+use crate::chess_move::Move;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::*;
+use crate::zobrist;
+
+/// The state `Fen::make_move` cannot recover from the position after the move, needed by `Fen::unmake_move`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Undo {
+    /// The piece that was captured, for en passant this is the pawn behind the end square.
+    pub captured: Option<Piece>,
+    pub castling: u8,
+    pub en_passant: u64,
+    pub halfmove: u16,
+
+    /// The counters stop at `u16::MAX`, so the fullmove counter is restored instead of counted back.
+    pub fullmove: u16,
+
+    pub hash: u64,
+}
+
+impl Fen {
+    /// Applies the move like `Fen::move_to_fen` and returns what is needed to take it back with `Fen::unmake_move`.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn make_move(&mut self, move1: Move) -> Undo {
+        let captured: Option<Piece> = if move1.is_en_passant() {
+            Some(Piece::new(!self.side_to_move(), PieceKind::Pawn))
+        } else {
+            self.piece_on(move1.end_square())
+        };
+
+        let undo: Undo = Undo {
+            captured,
+            castling: self.castling,
+            en_passant: self.en_passant,
+            halfmove: self.halfmove,
+            fullmove: self.fullmove,
+            hash: self.hash(),
+        };
+
+        self.move_to_fen(&move1);
+
+        undo
+    }
+
+    /// Passes the turn to the opponent without moving, as the null move pruning of a search does,
+    /// and returns what is needed to take it back with `Fen::unmake_null_move`.
+    ///
+    /// **NOTE:** passing is not a legal move, the side to move must not be in check
+    pub fn make_null_move(&mut self) -> Undo {
+        let undo: Undo = Undo {
+            captured: None,
+            castling: self.castling,
+            en_passant: self.en_passant,
+            halfmove: self.halfmove,
+            fullmove: self.fullmove,
+            hash: self.hash(),
+        };
+
+        // the en passant square is gone after the pass, then flip the side to move
+        self.hash ^= self.en_passant_key() ^ zobrist::BLACK_TO_MOVE_KEY;
+        self.en_passant = EMPTY;
+        self.halfmove = self.halfmove.saturating_add(1);
+        if !self.white_to_move {
+            self.fullmove = self.fullmove.saturating_add(1);
+        }
+        self.white_to_move = !self.white_to_move;
+
+        debug_assert_eq!(self.hash, self.compute_hash(), "hash differs after a null move");
+
+        undo
+    }
+
+    /// Takes back a pass made with `Fen::make_null_move`.
+    pub fn unmake_null_move(&mut self, undo: Undo) {
+        self.white_to_move = !self.white_to_move;
+
+        self.en_passant = undo.en_passant;
+        self.halfmove = undo.halfmove;
+        self.fullmove = undo.fullmove;
+        self.hash = undo.hash;
+    }
+
+    /// Takes back a move played with `Fen::make_move`, restoring the position exactly as it was before.
+    ///
+    /// **NOTE:** `move1` has to be the last move that was made and `undo` the record it returned
+    pub fn unmake_move(&mut self, move1: Move, undo: Undo) {
+        self.white_to_move = !self.white_to_move;
+        let us: Color = self.side_to_move();
+        self.fullmove = undo.fullmove;
+
+        let start: Square = move1.start_square();
+        let end: Square = move1.end_square();
+
+        // move the piece back, a promoted piece turns back into a pawn
+        if let Some(piece) = self.piece_on(end) {
+            self.boards[piece.index()] &= !end.bit();
+
+            let moved: Piece = if move1.is_promotion() { Piece::new(us, PieceKind::Pawn) } else { piece };
+            self.boards[moved.index()] |= start.bit();
+        }
+
+        // put back the captured piece, for en passant it is directly behind the end square
+        if let Some(captured) = undo.captured {
+            let square: u64 = match (move1.is_en_passant(), us) {
+                (true, Color::White) => end.bit() >> 8,
+                (true, Color::Black) => end.bit() << 8,
+                (false, _) => end.bit(),
+            };
+            self.boards[captured.index()] |= square;
+        }
+
+        // move the rook back in case of castling, see `Fen::move_to_fen`
+        let rook_index: usize = Piece::new(us, PieceKind::Rook).index();
+        if move1.flags() == Move::KING_CASTLE {
+            self.boards[rook_index] &= !(end.bit() << 1);
+            self.boards[rook_index] |= end.bit() >> 1;
+        } else if move1.flags() == Move::QUEEN_CASTLE {
+            self.boards[rook_index] &= !(end.bit() >> 1);
+            self.boards[rook_index] |= end.bit() << 2;
+        }
+
+        self.castling = undo.castling;
+        self.en_passant = undo.en_passant;
+        self.halfmove = undo.halfmove;
+        self.hash = undo.hash;
+
+        self.white = get_white(&self.boards);
+        self.black = get_black(&self.boards);
+        self.full = self.white | self.black;
+
+        debug_assert_eq!(self.hash, self.compute_hash(), "hash differs after taking back {}", move1);
+    }
+}
@@ -10,16 +10,16 @@ pub const RANK: u64 = 0xFF00000000000000;
 /// Bitboard with the leftmost (A-file) set.
 pub const FILE: u64 = 0x8080808080808080;
 
-pub const RANK_0: u64 = RANK >> 0 * 8; // Rank 1
-pub const RANK_1: u64 = RANK >> 1 * 8; // Rank 2
-pub const RANK_2: u64 = RANK >> 2 * 8; // Rank 3
-pub const RANK_3: u64 = RANK >> 3 * 8; // Rank 4
-pub const RANK_4: u64 = RANK >> 4 * 8; // Rank 5
-pub const RANK_5: u64 = RANK >> 5 * 8; // Rank 6
-pub const RANK_6: u64 = RANK >> 6 * 8; // Rank 7
-pub const RANK_7: u64 = RANK >> 7 * 8; // Rank 8
-
-pub const FILE_0: u64 = FILE >> 0; // File a
+pub const RANK_0: u64 = RANK; // Rank 8
+pub const RANK_1: u64 = RANK >> 8; // Rank 7
+pub const RANK_2: u64 = RANK >> (2 * 8); // Rank 6
+pub const RANK_3: u64 = RANK >> (3 * 8); // Rank 5
+pub const RANK_4: u64 = RANK >> (4 * 8); // Rank 4
+pub const RANK_5: u64 = RANK >> (5 * 8); // Rank 3
+pub const RANK_6: u64 = RANK >> (6 * 8); // Rank 2
+pub const RANK_7: u64 = RANK >> (7 * 8); // Rank 1
+
+pub const FILE_0: u64 = FILE; // File a
 pub const FILE_1: u64 = FILE >> 1; // File b
 pub const FILE_2: u64 = FILE >> 2; // File c
 pub const FILE_3: u64 = FILE >> 3; // File d
@@ -108,7 +108,7 @@ pub const DEFAULT: &str = "rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -
 ///
 /// # Arguments
 /// * `boards` - Array of 12 bitboards representing piece positions.
-///               The first 6 entries are white pieces.
+///   The first 6 entries are white pieces.
 pub fn get_white(boards: &[u64; 12]) -> u64 {
     boards[0] | boards[1] | boards[2] | boards[3] | boards[4] | boards[5]
 }
@@ -117,7 +117,7 @@ pub fn get_white(boards: &[u64; 12]) -> u64 {
 ///
 /// # Arguments
 /// * `boards` - Array of 12 bitboards representing piece positions.
-///               The last 6 entries are black pieces.
+///   The last 6 entries are black pieces.
 pub fn get_black(boards: &[u64; 12]) -> u64 {
     boards[6] | boards[7] | boards[8] | boards[9] | boards[10] | boards[11]
 }
\ No newline at end of file
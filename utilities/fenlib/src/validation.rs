@@ -0,0 +1,139 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::Fen;
+use crate::bitboard::Bitboard;
+use crate::error::FenError;
+use crate::parsing;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+use crate::utils::*;
+
+/// A reason why a syntactically valid fen describes a position that cannot occur in a legal game.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// A side does not have exactly one king, `count` is the number of kings found.
+    WrongKingCount { white: bool, count: u32 },
+
+    /// There are pawns on rank 1 or rank 8, the bitboard contains all of them.
+    PawnsOnBackRank(u64),
+
+    /// The side that is not to move is in check, so the king could be captured.
+    OpponentInCheck,
+
+    /// The en passant square is set, but no pawn can have just moved two squares past it.
+    InvalidEnPassant(u64),
+
+    /// A castling right is set while the king or the rook is not on its starting square.
+    /// The value is one of the castling info flags from `utils`.
+    InvalidCastling(u8),
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Violation::WrongKingCount { white, count } => {
+                let color: &str = if *white { "white" } else { "black" };
+                write!(f, "{} has {} kings, expected exactly 1", color, count)
+            },
+            Violation::PawnsOnBackRank(pawns) => {
+                let tiles: Vec<String> = Bitboard(*pawns).into_iter().map(|square: Square| square.to_string()).collect();
+                write!(f, "pawns on the first or last rank: {}", tiles.join(", "))
+            },
+            Violation::OpponentInCheck => write!(f, "the side that is not to move is in check"),
+            Violation::InvalidEnPassant(enpassant) => {
+                write!(f, "no pawn can be captured en passant on {}", parsing::enpassant_to_string(enpassant))
+            },
+            Violation::InvalidCastling(info) => {
+                write!(f, "castling right '{}' is set, but the king or rook has moved", parsing::castling_to_string(info))
+            },
+        }
+    }
+}
+
+impl Fen {
+    /// Parses a fen notation and rejects it if it describes an impossible position.
+    ///
+    /// `Fen::from_str` only checks the syntax, this constructor also runs `Fen::validate`
+    /// and returns `FenError::InvalidPosition` if any violation was found.
+    pub fn from_str_strict(fen_str: &str) -> Result<Self, FenError> {
+        let fen: Self = Self::from_str(fen_str)?;
+        let violations: Vec<Violation> = fen.validate();
+
+        if violations.is_empty() {
+            Ok(fen)
+        } else {
+            Err(FenError::InvalidPosition(violations))
+        }
+    }
+
+    /// Checks whether the position can occur in a legal game and returns every violation found.
+    ///
+    /// An empty list means the position is valid. The checks are:
+    /// * both sides have exactly one king
+    /// * there are no pawns on rank 1 or rank 8
+    /// * the side that is not to move is not in check
+    /// * the en passant square is behind a pawn that has just moved two squares forward
+    /// * every castling right has its king and rook on their starting squares
+    pub fn validate(&self) -> Vec<Violation> {
+        let mut violations: Vec<Violation> = Vec::new();
+
+        let white_kings: u32 = self.pieces(Piece::WHITE_KING).popcount();
+        if white_kings != 1 {
+            violations.push(Violation::WrongKingCount { white: true, count: white_kings });
+        }
+
+        let black_kings: u32 = self.pieces(Piece::BLACK_KING).popcount();
+        if black_kings != 1 {
+            violations.push(Violation::WrongKingCount { white: false, count: black_kings });
+        }
+
+        let pawns: Bitboard = self.pieces(Piece::WHITE_PAWN) | self.pieces(Piece::BLACK_PAWN);
+        let back_rank_pawns: Bitboard = pawns & Bitboard(RANK_0 | RANK_7);
+        if !back_rank_pawns.is_empty() {
+            violations.push(Violation::PawnsOnBackRank(back_rank_pawns.0));
+        }
+
+        // the opponent is in check if their king would be in check were it their turn
+        let mut opponent: Self = self.clone();
+        opponent.white_to_move = !opponent.white_to_move;
+        if opponent.in_check() {
+            violations.push(Violation::OpponentInCheck);
+        }
+
+        if self.en_passant != EMPTY && !self.valid_en_passant() {
+            violations.push(Violation::InvalidEnPassant(self.en_passant));
+        }
+
+        let castling_homes: [(u8, Color, Square, Square); 4] = [
+            (WHITE_KINGSIDE_INFO, Color::White, Square::E1, Square::H1),
+            (WHITE_QUEENSIDE_INFO, Color::White, Square::E1, Square::A1),
+            (BLACK_KINGSIDE_INFO, Color::Black, Square::E8, Square::H8),
+            (BLACK_QUEENSIDE_INFO, Color::Black, Square::E8, Square::A8),
+        ];
+
+        for (info, color, king_home, rook_home) in castling_homes {
+            let king_home_ok: bool = self.piece_on(king_home) == Some(Piece::new(color, PieceKind::King));
+            let rook_home_ok: bool = self.piece_on(rook_home) == Some(Piece::new(color, PieceKind::Rook));
+            if self.castling & info != 0 && (!king_home_ok || !rook_home_ok) {
+                violations.push(Violation::InvalidCastling(info));
+            }
+        }
+
+        violations
+    }
+
+    /// Checks whether the en passant square is directly behind a pawn of the side that just moved,
+    /// on the correct rank, with the square it came from and the square itself empty.
+    fn valid_en_passant(&self) -> bool {
+        let en_passant: Bitboard = Bitboard(self.en_passant);
+
+        // the pawn of the side that just moved stands one rank past the square, coming from one rank before it
+        let (pawn, from, rank, pawns): (Bitboard, Bitboard, u64, Bitboard) = match self.white_to_move {
+            true => (en_passant.south(), en_passant.north(), RANK_2, self.pieces(Piece::BLACK_PAWN)),
+            false => (en_passant.north(), en_passant.south(), RANK_5, self.pieces(Piece::WHITE_PAWN)),
+        };
+
+        self.en_passant & rank != 0 && !(pawn & pawns).is_empty() && ((en_passant | from) & self.occupied()).is_empty()
+    }
+}
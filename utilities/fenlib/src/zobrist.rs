@@ -0,0 +1,117 @@
+//! Zobrist hashing (https://www.chessprogramming.org/Zobrist_Hashing).
+//!
+//! Every piece on every square, the side to move, every combination of castling rights and every
+//! en passant file has a random 64 bit key. The hash of a position is the xor of the keys that apply to it,
+//! so a move only has to xor in and out the keys it changes. `Fen::move_to_fen` does that and keeps
+//! `Fen::hash` up to date.
+//!
+//! The en passant file is only part of the hash if a pawn of the side to move can capture en passant,
+//! otherwise the position is the same as without the en passant square.
+
+use crate::Fen;
+use crate::attacks;
+use crate::bitboard::Bitboard;
+use crate::piece::{Color, Piece, PieceKind};
+use crate::square::Square;
+
+/// Keys for every piece on every square, indexed by `Piece::index` and square index.
+pub const PIECE_KEYS: [[u64; 64]; 12] = piece_keys();
+
+/// Key that is part of the hash when black is to move.
+pub const BLACK_TO_MOVE_KEY: u64 = random_key(12 * 64);
+
+/// Keys for every combination of castling rights, indexed by the castling info of `Fen`.
+pub const CASTLING_KEYS: [u64; 16] = castling_keys();
+
+/// Keys for the file of the en passant square, indexed by `Square::file`.
+pub const EN_PASSANT_KEYS: [u64; 8] = en_passant_keys();
+
+/// Returns the key of the piece on the square.
+pub fn piece_key(piece: Piece, square: Square) -> u64 {
+    PIECE_KEYS[piece.index()][square.index() as usize]
+}
+
+impl Fen {
+    /// Returns the Zobrist hash of the position, two positions with the same pieces, side to move,
+    /// castling rights and en passant possibility have the same hash.
+    pub fn hash(&self) -> u64 {
+        self.hash
+    }
+
+    /// Computes the Zobrist hash of the position from scratch, `Fen::hash` returns the same value.
+    pub fn compute_hash(&self) -> u64 {
+        let mut hash: u64 = 0;
+
+        for (index, &board) in self.boards.iter().enumerate() {
+            for square in Bitboard(board) {
+                hash ^= PIECE_KEYS[index][square.index() as usize];
+            }
+        }
+
+        if !self.white_to_move {
+            hash ^= BLACK_TO_MOVE_KEY;
+        }
+
+        hash ^ CASTLING_KEYS[self.castling as usize & 0xF] ^ self.en_passant_key()
+    }
+
+    /// Returns the key of the en passant file, or 0 if no pawn of the side to move can capture en passant.
+    pub(crate) fn en_passant_key(&self) -> u64 {
+        let square: Square = match Square::from_bit(self.en_passant) {
+            Some(square) => square,
+            None => return 0,
+        };
+
+        // the pawns that can capture are the ones a pawn of the other color on the square would attack
+        let us: Color = self.side_to_move();
+        let capturers: u64 = attacks::pawn_attacks(!us, square).0 & self.pieces(Piece::new(us, PieceKind::Pawn)).0;
+
+        if capturers != 0 {
+            EN_PASSANT_KEYS[square.file() as usize]
+        } else {
+            0
+        }
+    }
+}
+
+/// Returns the key with the given index, generated with splitmix64 so the keys are the same in every build.
+const fn random_key(index: u64) -> u64 {
+    let mut z: u64 = (index + 1).wrapping_mul(0x9E3779B97F4A7C15);
+    z = (z ^ (z >> 30)).wrapping_mul(0xBF58476D1CE4E5B9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94D049BB133111EB);
+    z ^ (z >> 31)
+}
+
+const fn piece_keys() -> [[u64; 64]; 12] {
+    let mut keys: [[u64; 64]; 12] = [[0; 64]; 12];
+    let mut piece: usize = 0;
+    while piece < 12 {
+        let mut square: usize = 0;
+        while square < 64 {
+            keys[piece][square] = random_key((piece * 64 + square) as u64);
+            square += 1;
+        }
+        piece += 1;
+    }
+    keys
+}
+
+const fn castling_keys() -> [u64; 16] {
+    let mut keys: [u64; 16] = [0; 16];
+    let mut index: usize = 0;
+    while index < 16 {
+        keys[index] = random_key((12 * 64 + 1 + index) as u64);
+        index += 1;
+    }
+    keys
+}
+
+const fn en_passant_keys() -> [u64; 8] {
+    let mut keys: [u64; 8] = [0; 8];
+    let mut file: usize = 0;
+    while file < 8 {
+        keys[file] = random_key((12 * 64 + 17 + file) as u64);
+        file += 1;
+    }
+    keys
+}
@@ -0,0 +1,89 @@
+use fenlib::bitboard::Bitboard;
+use fenlib::error::FenError;
+use fenlib::square::Square;
+use fenlib::utils::*;
+
+fn board(squares: &[Square]) -> Bitboard {
+    let mut board: Bitboard = Bitboard::EMPTY;
+    for &square in squares {
+        board.set(square);
+    }
+    board
+}
+
+#[test]
+fn shifts_do_not_wrap_around() {
+    let h_file: Bitboard = Bitboard(FILE_7);
+    let a_file: Bitboard = Bitboard(FILE_0);
+
+    // moving off the side of the board removes the squares instead of wrapping to the other edge
+    assert_eq!(h_file.east(), Bitboard::EMPTY);
+    assert_eq!(h_file.shift(1, 0), Bitboard::EMPTY);
+    assert_eq!(h_file.north_east(), Bitboard::EMPTY);
+    assert_eq!(a_file.west(), Bitboard::EMPTY);
+    assert_eq!(a_file.shift(-1, 0), Bitboard::EMPTY);
+    assert_eq!(a_file.south_west(), Bitboard::EMPTY);
+
+    // the other direction keeps them
+    assert_eq!(h_file.west(), Bitboard(FILE_6));
+    assert_eq!(a_file.shift(1, 0), Bitboard(FILE_1));
+
+    // knight jumps from the edge only land on the board
+    assert_eq!(board(&[Square::G1]).shift(2, 1), Bitboard::EMPTY);
+    assert_eq!(board(&[Square::H4]).shift(-2, 1), board(&[Square::F5]));
+    assert_eq!(board(&[Square::B8]).shift(-2, -1), Bitboard::EMPTY);
+
+    // off the top and the bottom
+    assert_eq!(Bitboard(RANK_0).north(), Bitboard::EMPTY);
+    assert_eq!(Bitboard(RANK_7).south(), Bitboard::EMPTY);
+    assert_eq!(Bitboard::FULL.shift(0, 8), Bitboard::EMPTY);
+}
+
+#[test]
+fn pop_lsb_and_iteration() {
+    let mut squares: Bitboard = board(&[Square::A8, Square::E4, Square::H1]);
+    assert_eq!(squares.popcount(), 3);
+    assert_eq!(squares.lsb(), Some(Square::H1));
+
+    assert_eq!(squares.into_iter().collect::<Vec<Square>>(), vec![Square::H1, Square::E4, Square::A8]);
+    assert_eq!(squares.into_iter().len(), 3);
+
+    assert_eq!(squares.pop_lsb(), Some(Square::H1));
+    assert_eq!(squares.pop_lsb(), Some(Square::E4));
+    assert_eq!(squares.pop_lsb(), Some(Square::A8));
+    assert_eq!(squares.pop_lsb(), None);
+    assert!(squares.is_empty());
+}
+
+#[test]
+fn display() {
+    let grid: String = board(&[Square::A8, Square::E4, Square::H1]).to_string();
+    let expected: &str = "\
+8 X . . . . . . .
+7 . . . . . . . .
+6 . . . . . . . .
+5 . . . . . . . .
+4 . . . . X . . .
+3 . . . . . . . .
+2 . . . . . . . .
+1 . . . . . . . X
+  a b c d e f g h";
+
+    assert_eq!(grid, expected);
+}
+
+#[test]
+fn square_notation() {
+    for square in Square::ALL {
+        assert_eq!(square.to_string().parse::<Square>(), Ok(square));
+        assert_eq!(Square::new(square.file(), square.rank()), Some(square));
+    }
+
+    assert_eq!(Square::E4.to_string(), "e4");
+    assert_eq!("a8".parse::<Square>(), Ok(Square::A8));
+    assert_eq!(Square::A8.bit(), FIRST);
+
+    for tile in ["", "e", "e9", "i4", "E4", "e44"] {
+        assert_eq!(tile.parse::<Square>(), Err(FenError::BadTile(tile.to_string())));
+    }
+}
@@ -0,0 +1,94 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::error::FenError;
+use fenlib::square::Square;
+use fenlib::utils::*;
+
+#[test]
+fn encoding_round_trip() {
+    for flags in 0..16 {
+        let move1: Move = Move::new(Square::A7.index(), Square::B8.index(), flags);
+        assert_eq!(move1.start_square(), Square::A7);
+        assert_eq!(move1.end_square(), Square::B8);
+        assert_eq!(move1.flags(), flags);
+        assert_eq!(Move::from_raw(move1.raw()), move1);
+    }
+
+    // the corners use every bit of the square indices
+    let move1: Move = Move::from_squares(Square::A8, Square::H1, Move::QUEEN_PROMOTION_CAPTURE);
+    assert_eq!((move1.start_index(), move1.end_index()), (63, 0));
+    assert_eq!(move1.raw(), 0xF03F);
+    assert_eq!(move1.promotion(), QUEEN_PROM);
+}
+
+#[test]
+fn predicates() {
+    let quiet: Move = Move::from_squares(Square::G1, Square::F3, Move::QUIET);
+    assert!(!quiet.is_capture() && !quiet.is_promotion() && !quiet.is_castle() && !quiet.is_en_passant());
+    assert_eq!(quiet.promotion(), NO_PROM);
+
+    assert!(Move::from_squares(Square::E2, Square::E4, Move::DOUBLE_PUSH).is_double_push());
+    assert!(Move::from_squares(Square::E4, Square::D5, Move::CAPTURE).is_capture());
+
+    let en_passant: Move = Move::from_squares(Square::E5, Square::D6, Move::EN_PASSANT);
+    assert!(en_passant.is_en_passant() && en_passant.is_capture());
+
+    assert!(Move::from_squares(Square::E1, Square::G1, Move::KING_CASTLE).is_castle());
+    assert!(Move::from_squares(Square::E1, Square::C1, Move::QUEEN_CASTLE).is_castle());
+
+    let promotion: Move = Move::from_squares(Square::B7, Square::A8, Move::KNIGHT_PROMOTION_CAPTURE);
+    assert!(promotion.is_promotion() && promotion.is_capture() && !promotion.is_castle());
+    assert_eq!(promotion.promotion(), KNIGHT_PROM);
+
+    for (flags, promoting_to) in [
+        (Move::KNIGHT_PROMOTION, KNIGHT_PROM),
+        (Move::BISHOP_PROMOTION, BISHOP_PROM),
+        (Move::ROOK_PROMOTION, ROOK_PROM),
+        (Move::QUEEN_PROMOTION, QUEEN_PROM),
+    ] {
+        assert_eq!(Move::promotion_flags(promoting_to, false), flags);
+        assert_eq!(Move::promotion_flags(promoting_to, true), flags | Move::CAPTURE);
+    }
+
+    assert!(Move::NULL.is_null());
+    assert!(!quiet.is_null());
+}
+
+#[test]
+fn lan() {
+    let move1: Move = "e2e4".parse().unwrap();
+    assert_eq!((move1.start_square(), move1.end_square()), (Square::E2, Square::E4));
+    assert_eq!(move1.to_string(), "e2e4");
+
+    let promotion: Move = "e7e8q".parse().unwrap();
+    assert_eq!(promotion.promotion(), QUEEN_PROM);
+    assert_eq!(promotion.to_string(), "e7e8q");
+    assert_eq!("a2a1N".parse::<Move>().unwrap().to_string(), "a2a1n");
+
+    assert_eq!("e2".parse::<Move>(), Err(FenError::BadMove("e2".to_string())));
+    assert_eq!("e2e9".parse::<Move>(), Err(FenError::BadMove("e2e9".to_string())));
+    assert_eq!("e7e8k".parse::<Move>(), Err(FenError::BadPromotion('k')));
+}
+
+#[test]
+fn legacy_triples() {
+    let e2: u64 = Square::E2.bit();
+    let e4: u64 = Square::E4.bit();
+
+    let normal: Move = Move::from_legacy(&[e2, e4, NO_PROM]);
+    assert_eq!(normal.to_string(), "e2e4");
+    assert_eq!(normal.to_legacy(), [e2, e4, NO_PROM]);
+
+    let promotion: Move = Move::from_legacy(&[Square::B7.bit(), Square::B8.bit(), ROOK_PROM]);
+    assert_eq!(promotion.flags(), Move::ROOK_PROMOTION);
+    assert_eq!(<[u64; 3]>::from(promotion), [Square::B7.bit(), Square::B8.bit(), ROOK_PROM]);
+
+    // the triple cannot tell that a king move castles, the position can
+    let castle: [u64; 3] = [Square::E1.bit(), Square::G1.bit(), NO_PROM];
+    assert!(!Move::from_legacy(&castle).is_castle());
+
+    let fen: Fen = "r3k2r/8/8/8/8/8/8/R3K2R w KQkq - 0 1".parse().unwrap();
+    let move1: Move = fen.move_from_legacy(&castle);
+    assert_eq!(move1.flags(), Move::KING_CASTLE);
+    assert!(fen.is_legal_move(&move1));
+}
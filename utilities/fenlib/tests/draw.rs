@@ -0,0 +1,71 @@
+use fenlib::Fen;
+use fenlib::game::Game;
+use fenlib::result::{GameResult, Reason};
+
+fn draw_reason(fen_str: &str) -> Option<Reason> {
+    fen_str.parse::<Fen>().unwrap().draw_reason()
+}
+
+#[test]
+fn insufficient_material() {
+    for fen_str in [
+        "4k3/8/8/8/8/8/8/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1N2K3 b - - 0 1",
+        "2b1k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1B1BKB2 w - - 0 1",
+    ] {
+        assert_eq!(draw_reason(fen_str), Some(Reason::InsufficientMaterial), "{}", fen_str);
+    }
+}
+
+#[test]
+fn sufficient_material() {
+    for fen_str in [
+        // bishops on different colors, two knights and knight against knight can all mate with help
+        "1b2k3/8/8/8/8/8/8/4KB2 w - - 0 1",
+        "4k3/8/8/8/8/8/8/1NN1K3 w - - 0 1",
+        "4kn2/8/8/8/8/8/8/1N2K3 w - - 0 1",
+        "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1",
+        "4k3/8/8/8/8/8/8/R3K3 w - - 0 1",
+    ] {
+        assert_eq!(draw_reason(fen_str), None, "{}", fen_str);
+    }
+}
+
+#[test]
+fn locked_pawns_are_a_dead_position() {
+    assert_eq!(draw_reason("8/8/4k3/1p1p1p1p/1P1P1P1P/4K3/8/8 w - - 0 1"), Some(Reason::DeadPosition));
+
+    // the white king can walk through g4 and capture the pawn on e5
+    assert_eq!(draw_reason("8/8/4k3/p1p1p3/P1P1P3/4K3/8/8 w - - 0 1"), None);
+
+    // the pawns can capture each other
+    assert_eq!(draw_reason("8/8/4k3/2pp4/2PP4/4K3/8/8 w - - 0 1"), None);
+    assert_eq!(draw_reason("8/8/4k3/3p4/2P5/4K3/8/8 w - - 0 1"), None);
+}
+
+#[test]
+fn stalemate_and_fifty_moves() {
+    assert_eq!(draw_reason("7k/5Q2/6K1/8/8/8/8/8 b - - 0 1"), Some(Reason::Stalemate));
+    assert_eq!(draw_reason("4k3/8/8/8/8/8/8/R3K3 w - - 100 80"), Some(Reason::FiftyMove));
+    assert_eq!(draw_reason("4k3/8/8/8/8/8/8/R3K3 w - - 99 80"), None);
+    assert_eq!(draw_reason("4k3/8/8/8/8/8/8/R3K3 w - - 150 100"), Some(Reason::SeventyFiveMove));
+
+    // checkmate is not a draw, even with the halfmove counter past the limit
+    assert_eq!(draw_reason("7k/6Q1/6K1/8/8/8/8/8 b - - 100 80"), None);
+}
+
+#[test]
+fn result_reports_a_draw() {
+    let fen: Fen = "4k3/8/8/8/8/8/8/2B1K3 w - - 0 1".parse().unwrap();
+    assert_eq!(fen.result(), GameResult::draw(Reason::InsufficientMaterial));
+
+    let mut game: Game = Game::new();
+    for _ in 0..2 {
+        for lan in ["g1f3", "g8f6", "f3g1", "f6g8"] {
+            game.make_move_lan(lan).unwrap();
+        }
+    }
+    assert_eq!(game.result(), GameResult::draw(Reason::Repetition));
+}
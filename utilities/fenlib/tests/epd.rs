@@ -0,0 +1,103 @@
+use std::time::Duration;
+
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::epd::{self, Epd, EpdError, EpdValue, SuiteReport};
+use fenlib::error::FenError;
+
+const WAC_001: &str = r#"2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - bm Qg6; id "WAC.001";"#;
+const WAC_002: &str = r#"8/7p/5k2/5p2/p1p2P2/Pr1pPK2/1P1R3P/8 b - - bm Rxb2; id "WAC.002";"#;
+
+fn lan(moves: &[Move]) -> Vec<String> {
+    moves.iter().map(|move1: &Move| move1.to_string()).collect()
+}
+
+#[test]
+fn read_opcodes() {
+    let epd: Epd = WAC_001.parse().unwrap();
+
+    assert_eq!(epd.fen.to_string(), "2rr3k/pp3pp1/1nnqbN1p/3pN3/2pP4/2P3Q1/PPB4P/R4RK1 w - - 0 1");
+    assert_eq!(lan(epd.best_moves()), vec!["g3g6"]);
+    assert!(epd.avoid_moves().is_empty());
+    assert_eq!(epd.id(), Some("WAC.001"));
+}
+
+#[test]
+fn typed_values() {
+    let line: &str = r#"4k3/8/8/8/8/8/4P3/4K3 w - - am e3 Kd2; bm e4; hmvn 12; fmvn 40; acd 7; pv e4 Kd7 e5; c0 "a comment with spaces"; noop;"#;
+    let epd: Epd = line.parse().unwrap();
+
+    assert_eq!(epd.fen.halfmove, 12);
+    assert_eq!(epd.fen.fullmove, 40);
+    assert_eq!(lan(epd.avoid_moves()), vec!["e2e3", "e1d2"]);
+    assert_eq!(lan(epd.moves("pv")), vec!["e2e4", "e8d7", "e4e5"]);
+    assert_eq!(epd.get("acd"), Some(&EpdValue::Integer(7)));
+    assert_eq!(epd.get("c0"), Some(&EpdValue::Text("a comment with spaces".to_string())));
+    assert_eq!(epd.get("noop"), Some(&EpdValue::Tokens(Vec::new())));
+    assert_eq!(epd.get("id"), None);
+
+    assert_eq!(epd.to_string(), r#"4k3/8/8/8/8/8/4P3/4K3 w - - am e3 Kd2; bm e4; hmvn 12; fmvn 40; acd 7; pv e4 Kd7 e5; c0 "a comment with spaces"; noop;"#);
+}
+
+#[test]
+fn full_fen_with_counters() {
+    let epd: Epd = "4k3/8/8/8/8/8/4P3/4K3 w - - 3 17 bm e4;".parse().unwrap();
+    assert_eq!(epd.fen.halfmove, 3);
+    assert_eq!(epd.fen.fullmove, 17);
+    assert_eq!(lan(epd.best_moves()), vec!["e2e4"]);
+}
+
+#[test]
+fn solved_positions() {
+    let epd: Epd = r#"4k3/8/8/8/8/8/4P3/4K3 w - - bm e4 e3; am Kd2;"#.parse().unwrap();
+    let fen: &Fen = &epd.fen;
+
+    assert!(epd.is_solved_by(fen.parse_lan("e2e4").unwrap()));
+    assert!(epd.is_solved_by(fen.parse_lan("e2e3").unwrap()));
+    assert!(!epd.is_solved_by(fen.parse_lan("e1d2").unwrap()));
+    assert!(!epd.is_solved_by(fen.parse_lan("e1f2").unwrap()));
+
+    let no_answer: Epd = r#"4k3/8/8/8/8/8/4P3/4K3 w - - id "nothing";"#.parse().unwrap();
+    assert!(!no_answer.is_solved_by(fen.parse_lan("e2e4").unwrap()));
+}
+
+#[test]
+fn run_suite() {
+    let text: String = format!("# two positions of Win at Chess\n\n{}\n{}\n", WAC_001, WAC_002);
+    let positions: Vec<Epd> = epd::read_epd(&text).unwrap();
+    assert_eq!(positions.len(), 2);
+
+    // a "bot" that always plays the first legal move
+    let report: SuiteReport = epd::run_suite(&positions, Duration::from_millis(10), |fen: &Fen, _: Duration| {
+        fen.legal_moves()[0]
+    });
+    assert_eq!(report.total(), 2);
+
+    let solved: usize = report.results.iter().filter(|result| result.solved).count();
+    assert_eq!(report.solved(), solved);
+    assert_eq!(report.results[0].id, "WAC.001");
+    assert!(report.to_string().ends_with(&format!("solved {} of 2 positions", solved)));
+
+    // a bot that knows the answers
+    let report: SuiteReport = epd::run_suite(&positions, Duration::from_millis(10), |fen: &Fen, _: Duration| {
+        let epd: &Epd = positions.iter().find(|epd: &&Epd| epd.fen.to_string() == fen.to_string()).unwrap();
+        epd.best_moves()[0]
+    });
+    assert_eq!(report.solved(), 2);
+}
+
+#[test]
+fn errors() {
+    assert_eq!("4k3/8/8/8 w".parse::<Epd>().unwrap_err(), EpdError::MissingFields);
+    assert_eq!("4k3/8/8/8/8/8/4P3/4K3 w - - bm e4".parse::<Epd>().unwrap_err(), EpdError::UnterminatedOperation);
+    assert_eq!(r#"4k3/8/8/8/8/8/4P3/4K3 w - - id "open;"#.parse::<Epd>().unwrap_err(), EpdError::UnterminatedOperation);
+    assert_eq!(
+        "4k3/8/8/8/8/8/4P3/4K3 w - - bm e5;".parse::<Epd>().unwrap_err(),
+        EpdError::Fen(FenError::IllegalSan("e5".to_string()))
+    );
+    assert_eq!("4k3/8/8/8/8/8/4P3/4K3 x - - bm e4;".parse::<Epd>().unwrap_err(), EpdError::Fen(FenError::BadSideToMove("x".to_string())));
+    assert_eq!(
+        "4k3/8/8/8/8/8/4P3/4K3 w - - acd deep;".parse::<Epd>().unwrap_err(),
+        EpdError::BadOperand { opcode: "acd".to_string(), operand: "deep".to_string() }
+    );
+}
@@ -0,0 +1,72 @@
+use fenlib::Fen;
+use fenlib::game::Game;
+use fenlib::result::{GameResult, Reason};
+
+const SHUFFLE: [&str; 4] = ["g1f3", "g8f6", "f3g1", "f6g8"];
+
+fn play(game: &mut Game, moves: &[&str]) {
+    for lan in moves {
+        assert!(game.fen().is_legal_move_lan(lan), "{} is not legal in {}", lan, game.fen());
+        game.make_move_lan(lan).unwrap();
+    }
+}
+
+#[test]
+fn threefold_repetition() {
+    let mut game: Game = Game::new();
+    assert_eq!(game.repetitions(), 1);
+
+    play(&mut game, &SHUFFLE);
+    assert_eq!(game.repetitions(), 2);
+    assert!(!game.is_threefold_repetition());
+    assert!(game.result().is_ongoing());
+
+    play(&mut game, &SHUFFLE);
+    assert!(game.is_threefold_repetition());
+    assert!(!game.is_fivefold_repetition());
+    assert_eq!(game.result(), GameResult::draw(Reason::Repetition));
+
+    // a single position does not know about the repetition
+    assert!(game.fen().result().is_ongoing());
+
+    play(&mut game, &SHUFFLE);
+    play(&mut game, &SHUFFLE);
+    assert!(game.is_fivefold_repetition());
+}
+
+#[test]
+fn irreversible_move_resets_repetitions() {
+    let mut game: Game = Game::new();
+    play(&mut game, &SHUFFLE);
+    play(&mut game, &["e2e4", "e7e5"]);
+    play(&mut game, &SHUFFLE);
+    play(&mut game, &SHUFFLE);
+
+    assert_eq!(game.repetitions(), 3);
+}
+
+#[test]
+fn undo_restores_the_position_and_history() {
+    let mut game: Game = Game::new();
+    play(&mut game, &SHUFFLE);
+    play(&mut game, &SHUFFLE);
+    assert!(game.is_threefold_repetition());
+
+    assert_eq!(game.undo().map(|move1| move1.to_string()), Some("f6g8".to_string()));
+    assert!(!game.is_threefold_repetition());
+    assert_eq!(game.moves().len(), 7);
+
+    while game.undo().is_some() {}
+    assert_eq!(game.fen().to_string(), Fen::new().to_string());
+    assert_eq!(game.repetitions(), 1);
+}
+
+#[test]
+fn starting_position_with_halfmove_counter() {
+    // the earlier positions are unknown, so only the positions of the game are compared
+    let fen: Fen = "4k3/8/8/8/8/8/8/4K2R w - - 40 60".parse().unwrap();
+    let mut game: Game = Game::from_fen(fen);
+
+    play(&mut game, &["h1h2", "e8d8", "h2h1", "d8e8"]);
+    assert_eq!(game.repetitions(), 2);
+}
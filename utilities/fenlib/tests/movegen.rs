@@ -0,0 +1,57 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::movegen::{MAX_LEGAL_MOVES, MoveList};
+use fenlib::undo::Undo;
+
+mod common;
+
+use common::*;
+
+#[test]
+fn more_moves_than_the_list_holds() {
+    // not reachable in a game, but the parser accepts it and white has more than 256 legal moves
+    let fen: Fen = "kQQQQQQQ/1Q5Q/Q6R/QQ5Q/5Q1Q/Q6Q/Q6R/QQQQQ1QK w - - 0 1".parse().unwrap();
+    let moves: MoveList = fen.legal_moves();
+
+    assert_eq!(moves.len(), MAX_LEGAL_MOVES);
+    assert!(moves.iter().all(|move1: &Move| fen.is_legal_move(move1)));
+    assert!(!fen.get_all_possible_moves_lan().is_empty());
+
+    let mut list: MoveList = MoveList::new();
+    for _ in 0..MAX_LEGAL_MOVES {
+        assert!(list.push(Move::NULL));
+    }
+    assert!(!list.push(Move::NULL));
+    assert_eq!(list.len(), MAX_LEGAL_MOVES);
+}
+
+/// Checks in every position up to `depth` plies deep that the tactical moves are exactly the legal captures
+/// and promotions, or all legal moves in check.
+fn assert_tactical_moves(fen: &mut Fen, depth: u32) {
+    let legal: Vec<Move> = fen.legal_moves().to_vec();
+    let mut expected: Vec<Move> = if fen.in_check() {
+        legal.clone()
+    } else {
+        legal.iter().copied().filter(|move1: &Move| move1.is_capture() || move1.is_promotion()).collect()
+    };
+    let mut tactical: Vec<Move> = fen.tactical_moves().to_vec();
+
+    expected.sort_by_key(|move1: &Move| move1.raw());
+    tactical.sort_by_key(|move1: &Move| move1.raw());
+    assert_eq!(tactical, expected, "{}", fen);
+
+    if depth > 1 {
+        for move1 in legal {
+            let undo: Undo = fen.make_move(move1);
+            assert_tactical_moves(fen, depth - 1);
+            fen.unmake_move(move1, undo);
+        }
+    }
+}
+
+#[test]
+fn tactical_moves() {
+    for fen_str in [START, KIWIPETE, POSITION_3, POSITION_4, POSITION_5, POSITION_6] {
+        assert_tactical_moves(&mut fen_str.parse().unwrap(), 3);
+    }
+}
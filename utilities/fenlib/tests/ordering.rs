@@ -0,0 +1,147 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::ordering::{self, MAX_HISTORY, MoveOrdering, MovePicker, Stage};
+
+/// White can take the queen on d5 with the pawn, the knight, the bishop or the rook.
+const CAPTURES: &str = "4k3/8/2n5/3q4/2P1B3/4N3/8/3RK3 w - - 0 1";
+
+fn picked(fen: &Fen, tt_move: Move, previous: Move, ply: u32, ordering: &MoveOrdering) -> Vec<(Move, Stage)> {
+    let mut picker: MovePicker = MovePicker::new(fen, fen.legal_moves(), tt_move, previous, ply, ordering);
+    std::iter::from_fn(|| picker.next_move(fen, ordering)).collect()
+}
+
+fn lan(moves: &[(Move, Stage)]) -> Vec<String> {
+    moves.iter().map(|(move1, _): &(Move, Stage)| move1.to_string()).collect()
+}
+
+#[test]
+fn picks_every_move_once() {
+    let fen: Fen = CAPTURES.parse().unwrap();
+    let mut picked: Vec<Move> = picked(&fen, Move::NULL, Move::NULL, 0, &MoveOrdering::new()).iter().map(|&(move1, _): &(Move, Stage)| move1).collect();
+    let mut legal: Vec<Move> = fen.legal_moves().to_vec();
+
+    picked.sort_by_key(|move1: &Move| move1.raw());
+    legal.sort_by_key(|move1: &Move| move1.raw());
+    assert_eq!(picked, legal);
+}
+
+#[test]
+fn most_valuable_victim_least_valuable_attacker() {
+    let fen: Fen = CAPTURES.parse().unwrap();
+    let moves: Vec<(Move, Stage)> = picked(&fen, Move::NULL, Move::NULL, 0, &MoveOrdering::new());
+
+    assert_eq!(lan(&moves[..4]), vec!["c4d5", "e3d5", "e4d5", "d1d5"]);
+    assert!(moves[..4].iter().all(|&(_, stage): &(Move, Stage)| stage == Stage::Captures));
+    assert!(moves[4..].iter().all(|&(_, stage): &(Move, Stage)| stage == Stage::Quiets));
+
+    assert!(ordering::mvv_lva(&fen, moves[0].0) > ordering::mvv_lva(&fen, moves[3].0));
+}
+
+#[test]
+fn tt_move_comes_first() {
+    let fen: Fen = CAPTURES.parse().unwrap();
+    let tt_move: Move = fen.parse_lan("e1f2").unwrap();
+    let moves: Vec<(Move, Stage)> = picked(&fen, tt_move, Move::NULL, 0, &MoveOrdering::new());
+
+    assert_eq!(moves[0], (tt_move, Stage::TtMove));
+    assert_eq!(moves.iter().filter(|&&(move1, _): &&(Move, Stage)| move1 == tt_move).count(), 1);
+    assert_eq!(moves[1].0.to_string(), "c4d5");
+}
+
+#[test]
+fn killers_countermoves_and_history() {
+    let mut fen: Fen = Fen::new();
+    let previous: Move = fen.parse_lan("e2e4").unwrap();
+    fen.make_move(previous);
+
+    let killer: Move = fen.parse_lan("g8f6").unwrap();
+    let counter: Move = fen.parse_lan("c7c5").unwrap();
+    let good: Move = fen.parse_lan("e7e5").unwrap();
+    let bad: Move = fen.parse_lan("a7a6").unwrap();
+
+    let mut ordering: MoveOrdering = MoveOrdering::new();
+    ordering.update(&fen, good, Move::NULL, 5, 4, &[bad]);
+    ordering.update(&fen, counter, previous, 3, 1, &[]);
+    ordering.update(&fen, killer, Move::NULL, 2, 1, &[]);
+
+    assert_eq!(ordering.killers(2), [killer, Move::NULL]);
+    assert_eq!(ordering.countermove(&fen, previous), counter);
+    assert_eq!(ordering.history(&fen, good), 16);
+    assert_eq!(ordering.history(&fen, bad), -16);
+
+    let moves: Vec<(Move, Stage)> = picked(&fen, Move::NULL, previous, 2, &ordering);
+    assert_eq!(moves[0], (killer, Stage::Killers));
+    assert_eq!(moves[1], (counter, Stage::Countermove));
+    assert_eq!(moves[2], (good, Stage::Quiets));
+    assert_eq!(moves.last().unwrap().0, bad);
+
+    // the killers of another ply and the countermove of another move do not apply
+    let moves: Vec<(Move, Stage)> = picked(&fen, Move::NULL, Move::NULL, 4, &ordering);
+    assert_eq!(moves[0], (good, Stage::Quiets));
+
+    // a new search forgets the killers and halves the history
+    ordering.new_search();
+    assert_eq!(ordering.killers(2), [Move::NULL; 2]);
+    assert_eq!(ordering.history(&fen, good), 8);
+    assert_eq!(ordering.countermove(&fen, previous), counter);
+}
+
+#[test]
+fn history_is_bounded() {
+    let fen: Fen = Fen::new();
+    let move1: Move = fen.parse_lan("g1f3").unwrap();
+    let mut ordering: MoveOrdering = MoveOrdering::new();
+
+    for _ in 0..1000 {
+        ordering.update(&fen, move1, Move::NULL, 0, 20, &[]);
+    }
+    let history: i32 = ordering.history(&fen, move1);
+    assert!(history > MAX_HISTORY / 2 && history <= MAX_HISTORY, "{}", history);
+}
+
+#[test]
+fn captures_do_not_become_killers() {
+    let fen: Fen = CAPTURES.parse().unwrap();
+    let capture: Move = fen.parse_lan("c4d5").unwrap();
+    let mut ordering: MoveOrdering = MoveOrdering::new();
+
+    ordering.update(&fen, capture, Move::NULL, 0, 3, &[]);
+    assert_eq!(ordering.killers(0), [Move::NULL; 2]);
+    assert_eq!(ordering.history(&fen, capture), 0);
+}
+
+#[test]
+fn losing_captures_come_after_quiet_moves() {
+    // the pawn on d5 is defended by the pawn on c6
+    let fen: Fen = "4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1".parse().unwrap();
+    let moves: Vec<(Move, Stage)> = picked(&fen, Move::NULL, Move::NULL, 0, &MoveOrdering::new());
+
+    assert_eq!(moves.last().unwrap().0.to_string(), "d1d5");
+    assert_eq!(moves.last().unwrap().1, Stage::Bad);
+    assert!(moves[..moves.len() - 1].iter().all(|&(_, stage): &(Move, Stage)| stage == Stage::Quiets));
+}
+
+#[test]
+fn underpromotions_come_last() {
+    let fen: Fen = "8/P6k/8/8/8/8/8/K7 w - - 0 1".parse().unwrap();
+    let moves: Vec<(Move, Stage)> = picked(&fen, Move::NULL, Move::NULL, 0, &MoveOrdering::new());
+
+    assert_eq!(moves[0].0.to_string(), "a7a8q");
+    assert!(moves[moves.len() - 3..].iter().all(|&(move1, stage): &(Move, Stage)| move1.is_promotion() && stage == Stage::Bad));
+}
+
+#[test]
+fn first_move_cutoff_rate() {
+    let fen: Fen = Fen::new();
+    let moves: Vec<Move> = fen.legal_moves().to_vec();
+    let mut ordering: MoveOrdering = MoveOrdering::new();
+    assert_eq!(ordering.first_move_cutoff_rate(), None);
+
+    ordering.update(&fen, moves[0], Move::NULL, 0, 1, &[]);
+    ordering.update(&fen, moves[0], Move::NULL, 0, 1, &[]);
+    ordering.update(&fen, moves[0], Move::NULL, 0, 1, &[]);
+    ordering.update(&fen, moves[2], Move::NULL, 0, 1, &moves[..2]);
+
+    assert_eq!(ordering.cutoffs(), 4);
+    assert_eq!(ordering.first_move_cutoff_rate(), Some(0.75));
+}
@@ -0,0 +1,73 @@
+use fenlib::Fen;
+use fenlib::error::FenError;
+use fenlib::parsing;
+
+/// Parses the fen notation, expecting it to fail.
+fn parse_error(fen_str: &str) -> FenError {
+    fen_str.parse::<Fen>().expect_err(fen_str)
+}
+
+#[test]
+fn wrong_field_count() {
+    assert_eq!(parse_error(""), FenError::WrongFieldCount(0));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq -"), FenError::WrongFieldCount(4));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1 extra"), FenError::WrongFieldCount(7));
+}
+
+#[test]
+fn bad_board() {
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::WrongRankCount(7));
+
+    // too few and too many files
+    assert_eq!(parse_error("rnbqkbnr/ppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::BadRankLength("ppppppp".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/44P/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::BadRankLength("44P".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/9/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 1"), FenError::UnknownPiece('9'));
+
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNX w KQkq - 0 1"), FenError::UnknownPiece('X'));
+}
+
+#[test]
+fn bad_side_to_move() {
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR x KQkq - 0 1"), FenError::BadSideToMove("x".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR W KQkq - 0 1"), FenError::BadSideToMove("W".to_string()));
+}
+
+#[test]
+fn bad_castling() {
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkx - 0 1"), FenError::BadCastling("KQkx".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KK - 0 1"), FenError::BadCastling("KK".to_string()));
+}
+
+#[test]
+fn bad_en_passant() {
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e4 0 1"), FenError::BadEnPassant("e4".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq i6 0 1"), FenError::BadEnPassant("i6".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq e63 0 1"), FenError::BadEnPassant("e63".to_string()));
+}
+
+#[test]
+fn bad_counters() {
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - x 1"), FenError::BadCounter("x".to_string()));
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 -1"), FenError::BadCounter("-1".to_string()));
+
+    // the counters are u16
+    assert_eq!(parse_error("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 65536"), FenError::BadCounter("65536".to_string()));
+    assert!("rnbqkbnr/pppppppp/8/8/8/8/PPPPPPPP/RNBQKBNR w KQkq - 0 65535".parse::<Fen>().is_ok());
+}
+
+#[test]
+fn bad_tiles_and_moves() {
+    assert_eq!(parsing::tile_to_bit("e4").map(|bit: u64| bit.count_ones()), Ok(1));
+    assert_eq!(parsing::tile_to_bit("e9"), Err(FenError::BadTile("e9".to_string())));
+    assert_eq!(parsing::tile_to_bit("é4"), Err(FenError::BadTile("é4".to_string())));
+
+    let mut fen: Fen = Fen::new();
+    assert_eq!(fen.lan_to_fen("e2"), Err(FenError::BadMove("e2".to_string())));
+    assert_eq!(fen.lan_to_fen("e2-e4"), Err(FenError::BadMove("e2-e4".to_string())));
+    assert_eq!(fen.lan_to_fen("-e4-"), Err(FenError::BadMove("-e4-".to_string())));
+    assert_eq!(fen.lan_to_fen("e7e8x"), Err(FenError::BadPromotion('x')));
+    assert!(!fen.is_legal_move_lan("ü2e4"));
+
+    // nothing was played
+    assert_eq!(fen.to_string(), Fen::new().to_string());
+}
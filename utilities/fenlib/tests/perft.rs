@@ -0,0 +1,131 @@
+//! Perft regression suite, node counts from https://www.chessprogramming.org/Perft_Results.
+//!
+//! The deeper counts take a while in a debug build and are ignored by default, run them with
+//! `cargo test --release -p fenlib -- --include-ignored`.
+
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+
+mod common;
+
+use common::*;
+
+/// Checks the perft count of every depth, starting at depth 1.
+fn assert_perft(fen_str: &str, counts: &[u64]) {
+    let fen: Fen = fen_str.parse().unwrap();
+
+    for (depth, &expected) in (1..).zip(counts) {
+        assert_eq!(fen.perft(depth), expected, "perft({}) of {}", depth, fen_str);
+    }
+}
+
+#[test]
+fn start_position() {
+    assert_perft(START, &[20, 400, 8902, 197281]);
+}
+
+#[test]
+#[ignore]
+fn start_position_deep() {
+    assert_perft(START, &[20, 400, 8902, 197281, 4865609]);
+}
+
+#[test]
+fn kiwipete() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862]);
+}
+
+#[test]
+#[ignore]
+fn kiwipete_deep() {
+    assert_perft(KIWIPETE, &[48, 2039, 97862, 4085603]);
+}
+
+#[test]
+fn position_3() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624]);
+}
+
+#[test]
+#[ignore]
+fn position_3_deep() {
+    assert_perft(POSITION_3, &[14, 191, 2812, 43238, 674624, 11030083]);
+}
+
+#[test]
+fn position_4() {
+    assert_perft(POSITION_4, &[6, 264, 9467, 422333]);
+}
+
+#[test]
+fn position_4_mirrored() {
+    assert_perft(POSITION_4_MIRRORED, &[6, 264, 9467, 422333]);
+}
+
+#[test]
+#[ignore]
+fn position_4_deep() {
+    assert_perft(POSITION_4, &[6, 264, 9467, 422333, 15833292]);
+}
+
+#[test]
+fn position_5() {
+    assert_perft(POSITION_5, &[44, 1486, 62379]);
+}
+
+#[test]
+#[ignore]
+fn position_5_deep() {
+    assert_perft(POSITION_5, &[44, 1486, 62379, 2103487]);
+}
+
+#[test]
+fn position_6() {
+    assert_perft(POSITION_6, &[46, 2079, 89890]);
+}
+
+#[test]
+#[ignore]
+fn position_6_deep() {
+    assert_perft(POSITION_6, &[46, 2079, 89890, 3894594]);
+}
+
+#[test]
+fn divide_sums_to_perft() {
+    let fen: Fen = KIWIPETE.parse().unwrap();
+    let divide: Vec<(Move, u64)> = fen.perft_divide(3);
+
+    assert_eq!(divide.len(), 48);
+    assert_eq!(divide.iter().map(|(_, nodes): &(Move, u64)| nodes).sum::<u64>(), 97862);
+
+    let castle: &(Move, u64) = divide.iter().find(|(move1, _): &&(Move, u64)| move1.to_string() == "e1g1").unwrap();
+    assert_eq!(castle.1, 2059);
+}
+
+#[test]
+fn castling_through_attacked_square() {
+    // the rook on f8 attacks f1, so white cannot castle kingside, queenside is fine
+    let fen: Fen = "r3kr2/8/8/8/8/8/8/R3K2R w KQ - 0 1".parse().unwrap();
+
+    assert!(!fen.is_legal_move_lan("e1g1"));
+    assert!(fen.is_legal_move_lan("e1c1"));
+    assert!(!fen.get_all_possible_moves_lan().contains(&"e1g1".to_string()));
+}
+
+#[test]
+fn castling_out_of_check() {
+    let fen: Fen = "4k3/8/8/8/8/8/4r3/R3K2R w KQ - 0 1".parse().unwrap();
+
+    assert!(!fen.is_legal_move_lan("e1g1"));
+    assert!(!fen.is_legal_move_lan("e1c1"));
+}
+
+#[test]
+fn knight_on_edge_does_not_wrap() {
+    let fen: Fen = "4k3/8/8/8/7N/8/8/4K3 w - - 0 1".parse().unwrap();
+    let moves: Vec<String> = fen.get_possible_moves_tile("h4").unwrap();
+
+    let mut sorted: Vec<&str> = moves.iter().map(String::as_str).collect();
+    sorted.sort();
+    assert_eq!(sorted, ["h4f3", "h4f5", "h4g2", "h4g6"]);
+}
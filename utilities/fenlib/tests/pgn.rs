@@ -0,0 +1,163 @@
+use fenlib::Fen;
+use fenlib::error::FenError;
+use fenlib::game::Game;
+use fenlib::pgn::{self, PgnError, PgnErrorKind, PgnGame, PgnMove};
+use fenlib::result::{Outcome, Reason};
+
+const OPERA_GAME: &str = r#"[Event "Paris"]
+[Site "Paris FRA"]
+[Date "1858.??.??"]
+[Round "?"]
+[White "Paul Morphy"]
+[Black "Duke Karl / Count Isouard"]
+[Result "1-0"]
+[ECO "C41"]
+
+1.e4 e5 2.Nf3 d6 3.d4 Bg4 {This is a weak move already.} 4.dxe5 Bxf3 5.Qxf3 dxe5
+6.Bc4 Nf6 7.Qb3 Qe7 8.Nc3 c6 9.Bg5 {Black is in what's like a zugzwang position
+here.} b5?! 10.Nxb5! cxb5 11.Bxb5+ Nbd7 12.O-O-O Rd8 13.Rxd7 Rxd7 14.Rd1 Qe6
+15.Bxd7+ Nxd7 16.Qb8+ Nxb8 17.Rd8# 1-0
+"#;
+
+const VARIATIONS: &str = r#"[Event "Analysis"]
+[White "?"]
+[Black "?"]
+[Result "*"]
+
+{The Italian} 1. e4 e5 2. Nf3 (2. f4 exf4 (2... d5 $5 {Falkbeer}) 3. Nf3) 2... Nc6
+3. Bc4 $1 ; the main line
+*
+"#;
+
+fn mainline_lan(game: &PgnGame) -> Vec<String> {
+    game.mainline().iter().map(|move1| move1.to_string()).collect()
+}
+
+#[test]
+fn read_tags_and_moves() {
+    let game: PgnGame = OPERA_GAME.parse().unwrap();
+
+    assert_eq!(game.tag("White"), Some("Paul Morphy"));
+    assert_eq!(game.tag("ECO"), Some("C41"));
+    assert_eq!(game.tag("Annotator"), None);
+    assert_eq!(game.result, Outcome::WhiteWins);
+    assert_eq!(game.moves.len(), 33);
+
+    assert_eq!(game.moves[5].comment.as_deref(), Some("This is a weak move already."));
+    assert_eq!(game.moves[16].comment.as_deref(), Some("Black is in what's like a zugzwang position here."));
+    assert_eq!(game.moves[17].nags, vec![6]);
+    assert_eq!(game.moves[18].nags, vec![1]);
+
+    let end: Game = game.to_game();
+    assert_eq!(end.result().outcome, Outcome::WhiteWins);
+    assert_eq!(end.result().reason, Some(Reason::Checkmate));
+}
+
+#[test]
+fn read_variations() {
+    let game: PgnGame = VARIATIONS.parse().unwrap();
+
+    assert_eq!(mainline_lan(&game), vec!["e2e4", "e7e5", "g1f3", "b8c6", "f1c4"]);
+    assert_eq!(game.result, Outcome::Ongoing);
+    assert_eq!(game.moves[0].comment_before.as_deref(), Some("The Italian"));
+    assert_eq!(game.moves[4].nags, vec![1]);
+    assert_eq!(game.moves[4].comment.as_deref(), Some("the main line"));
+
+    // 2. f4 is an alternative to 2. Nf3, with 2... d5 as an alternative to 2... exf4
+    let kings_gambit: &Vec<PgnMove> = &game.moves[2].variations[0];
+    assert_eq!(kings_gambit.len(), 3);
+    assert_eq!(kings_gambit[0].move1.to_string(), "f2f4");
+    assert_eq!(kings_gambit[1].variations[0][0].move1.to_string(), "d7d5");
+    assert_eq!(kings_gambit[1].variations[0][0].nags, vec![5]);
+    assert_eq!(kings_gambit[1].variations[0][0].comment.as_deref(), Some("Falkbeer"));
+}
+
+#[test]
+fn write_and_read_back() {
+    for text in [OPERA_GAME, VARIATIONS] {
+        let game: PgnGame = text.parse().unwrap();
+        let written: String = game.to_string();
+        let read_back: PgnGame = written.parse().unwrap();
+
+        assert_eq!(read_back.moves, game.moves, "{}", written);
+        assert_eq!(read_back.result, game.result);
+        assert_eq!(read_back.tag("White"), game.tag("White"));
+        assert!(written.lines().all(|line: &str| line.len() <= 80), "{}", written);
+    }
+
+    let written: String = VARIATIONS.parse::<PgnGame>().unwrap().to_string();
+    assert!(written.contains("{The Italian} 1. e4 e5 2. Nf3 (2. f4 exf4 (2... d5 $5 {Falkbeer}) 3. Nf3) 2..."), "{}", written);
+    assert!(written.contains("Nc6 3. Bc4 $1 {the main line} *"), "{}", written);
+    assert!(written.starts_with("[Event \"Analysis\"]\n[Site \"?\"]\n[Date \"????.??.??\"]"), "{}", written);
+}
+
+#[test]
+fn multiple_games() {
+    let text: String = format!("{}\n{}\n{}", OPERA_GAME, VARIATIONS, "1. f3 e5 2. g4 Qh4# 0-1");
+    let games: Vec<PgnGame> = pgn::read_pgn(&text).unwrap();
+
+    assert_eq!(games.len(), 3);
+    assert_eq!(games[2].result, Outcome::BlackWins);
+    assert!(games[2].tags.is_empty());
+
+    let written: String = pgn::write_pgn(&games);
+    assert_eq!(pgn::read_pgn(&written).unwrap().len(), 3);
+}
+
+#[test]
+fn setup_position() {
+    let text: &str = r#"[SetUp "1"]
+[FEN "4k3/8/8/8/8/8/4P3/4K3 b - - 0 30"]
+
+30... Kd7 31. e4 Kd6 *"#;
+    let game: PgnGame = text.parse().unwrap();
+    assert_eq!(mainline_lan(&game), vec!["e8d7", "e2e4", "d7d6"]);
+
+    let written: String = game.to_string();
+    assert!(written.contains("30... Kd7 31. e4 Kd6 *"), "{}", written);
+}
+
+#[test]
+fn export_game() {
+    let fen: Fen = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1".parse().unwrap();
+    let mut game: Game = Game::from_fen(fen);
+    game.make_move_lan("e2e4").unwrap();
+    game.resign(fenlib::piece::Color::Black);
+
+    let mut pgn_game: PgnGame = PgnGame::from_game(&game);
+    pgn_game.set_tag("White", "BotV1");
+
+    let written: String = pgn_game.to_string();
+    assert!(written.contains("[White \"BotV1\"]"), "{}", written);
+    assert!(written.contains("[FEN \"4k3/8/8/8/8/8/4P3/4K3 w - - 0 1\"]"), "{}", written);
+    assert!(written.contains("1. e4 1-0"), "{}", written);
+
+    let read_back: PgnGame = written.parse().unwrap();
+    assert_eq!(read_back.to_game().fen().to_string(), game.fen().to_string());
+}
+
+#[test]
+fn errors() {
+    let illegal: PgnError = pgn::read_pgn("1. e4 e5 2. Ke3 *").unwrap_err();
+    assert_eq!(illegal, PgnError { game: 1, move_number: Some(2), kind: PgnErrorKind::Fen(FenError::IllegalSan("Ke3".to_string())) });
+    assert_eq!(illegal.to_string(), "invalid pgn: game 1: move 2: move 'Ke3' is not legal in this position");
+
+    let second_game: PgnError = pgn::read_pgn("1. e4 e5 1-0\n\n1. d4 {unterminated").unwrap_err();
+    assert_eq!(second_game, PgnError { game: 2, move_number: None, kind: PgnErrorKind::UnterminatedComment });
+
+    let bad_fen: PgnError = pgn::read_pgn("[FEN \"8/8/8 w - - 0 1\"]\n\n*").unwrap_err();
+    assert_eq!(bad_fen.kind, PgnErrorKind::Fen(FenError::WrongRankCount(3)));
+
+    let error_kind = |text: &str| pgn::read_pgn(text).unwrap_err().kind;
+    assert_eq!(error_kind("1. e4 (1. d4 *"), PgnErrorKind::UnterminatedVariation);
+    assert_eq!(error_kind("[Event \"unterminated]"), PgnErrorKind::UnterminatedTag("Event \"unterminated]".to_string()));
+    assert_eq!(error_kind("[Event]"), PgnErrorKind::MalformedTag("Event".to_string()));
+    assert_eq!(error_kind("1. e4 $300 *"), PgnErrorKind::BadAnnotation("$300".to_string()));
+    assert_eq!(error_kind("1. e4 !!! *"), PgnErrorKind::BadAnnotation("!!!".to_string()));
+    assert_eq!(error_kind("1. e4 & *"), PgnErrorKind::UnexpectedCharacter('&'));
+    assert_eq!(error_kind("(1. e4) *"), PgnErrorKind::VariationBeforeFirstMove);
+    assert_eq!(error_kind("1. e4) *"), PgnErrorKind::UnmatchedParenthesis);
+
+    assert_eq!("1. e4 * 1. d4 *".parse::<PgnGame>().unwrap_err().kind, PgnErrorKind::WrongGameCount(2));
+    assert!(pgn::read_pgn("").unwrap().is_empty());
+}
@@ -0,0 +1,52 @@
+use fenlib::Fen;
+use fenlib::game::Game;
+use fenlib::piece::Color;
+use fenlib::result::{GameResult, Outcome, Reason};
+
+#[test]
+fn checkmate() {
+    // fool's mate
+    let mut game: Game = Game::new();
+    for lan in ["f2f3", "e7e5", "g2g4", "d8h4"] {
+        game.make_move_lan(lan).unwrap();
+    }
+
+    let result: GameResult = game.result();
+    assert_eq!(result, GameResult::win(Color::Black, Reason::Checkmate));
+    assert_eq!(result.winner(), Some(Color::Black));
+    assert_eq!(result.pgn(), "0-1");
+    assert_eq!(result.to_string(), "black wins by checkmate");
+}
+
+#[test]
+fn ongoing() {
+    let result: GameResult = Fen::new().result();
+
+    assert!(result.is_ongoing());
+    assert_eq!(result.reason, None);
+    assert_eq!(result.pgn(), "*");
+    assert_eq!(result.to_string(), "ongoing");
+}
+
+#[test]
+fn resignation_is_cleared_by_undo() {
+    let mut game: Game = Game::new();
+    game.make_move_lan("e2e4").unwrap();
+    game.resign(Color::Black);
+    assert_eq!(game.result(), GameResult::win(Color::White, Reason::Resignation));
+    assert_eq!(game.result().pgn(), "1-0");
+
+    game.undo();
+    assert!(game.result().is_ongoing());
+}
+
+#[test]
+fn pgn_results() {
+    for outcome in [Outcome::WhiteWins, Outcome::BlackWins, Outcome::Draw, Outcome::Ongoing] {
+        assert_eq!(Outcome::from_pgn(outcome.pgn()), Some(outcome));
+    }
+
+    assert_eq!(Outcome::from_pgn("½-½"), Some(Outcome::Draw));
+    assert_eq!(Outcome::from_pgn("draw"), None);
+    assert_eq!(GameResult::draw(Reason::Stalemate).pgn(), "1/2-1/2");
+}
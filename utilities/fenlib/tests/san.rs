@@ -0,0 +1,128 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::error::FenError;
+use fenlib::undo::Undo;
+
+mod common;
+
+fn san(fen_str: &str, lan: &str) -> String {
+    let fen: Fen = fen_str.parse().unwrap();
+    let move1: Move = fen.parse_lan(lan).unwrap();
+    assert!(fen.is_legal_move(&move1), "{} is not legal in {}", lan, fen);
+    fen.move_to_san(&move1)
+}
+
+fn lan(fen_str: &str, san: &str) -> String {
+    let fen: Fen = fen_str.parse().unwrap();
+    fen.san_to_move(san).unwrap().to_string()
+}
+
+/// Converts every move of the tree to SAN and back and checks that the same move comes out.
+fn check_tree(fen: &mut Fen, depth: u32) {
+    if depth == 0 {
+        return;
+    }
+
+    for &move1 in &fen.legal_moves() {
+        let san: String = fen.move_to_san(&move1);
+        assert_eq!(fen.san_to_move(&san), Ok(move1), "{} in {}", san, fen);
+
+        let undo: Undo = fen.make_move(move1);
+        check_tree(fen, depth - 1);
+        fen.unmake_move(move1, undo);
+    }
+}
+
+#[test]
+fn round_trip() {
+    for fen_str in common::TREE_POSITIONS {
+        let mut fen: Fen = fen_str.parse().unwrap();
+        check_tree(&mut fen, 2);
+    }
+}
+
+#[test]
+fn move_to_san() {
+    assert_eq!(san(fenlib::utils::DEFAULT, "e2e4"), "e4");
+    assert_eq!(san(fenlib::utils::DEFAULT, "g1f3"), "Nf3");
+
+    // Kiwipete
+    let kiwipete: &str = common::KIWIPETE;
+    assert_eq!(san(kiwipete, "e1g1"), "O-O");
+    assert_eq!(san(kiwipete, "e1c1"), "O-O-O");
+    assert_eq!(san(kiwipete, "d5e6"), "dxe6");
+    assert_eq!(san(kiwipete, "e5f7"), "Nxf7");
+    assert_eq!(san(kiwipete, "f3f6"), "Qxf6");
+    assert_eq!(san(kiwipete, "c3b5"), "Nb5");
+
+    // promotions with capture and check
+    assert_eq!(san("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7d8q"), "exd8=Q+");
+    assert_eq!(san("3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1", "e7e8n"), "e8=N");
+    assert_eq!(san("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), "exd6");
+}
+
+#[test]
+fn disambiguation() {
+    // both knights can reach d2, they stand on different files
+    assert_eq!(san("r3k3/8/8/8/8/8/8/1N2KN2 w - - 0 1", "b1d2"), "Nbd2");
+
+    // both rooks stand on the a-file
+    assert_eq!(san("4k3/8/R7/8/8/8/R7/4K3 w - - 0 1", "a2a4"), "R2a4");
+
+    // three queens that share the file with one and the rank with another
+    let queens: &str = "1k6/8/8/8/4Q2Q/8/K7/7Q w - - 0 1";
+    assert_eq!(san(queens, "h4e1"), "Qh4e1");
+    assert_eq!(san(queens, "e4e1"), "Qee1");
+    assert_eq!(san(queens, "h1e1"), "Q1e1");
+
+    // a pinned knight does not count
+    assert_eq!(san("4k3/8/8/b7/8/8/3N4/4K1N1 w - - 0 1", "g1f3"), "Nf3");
+}
+
+#[test]
+fn checkmate() {
+    let fools_mate: &str = "rnbqkbnr/pppp1ppp/8/4p3/6P1/5P2/PPPPP2P/RNBQKBNR b KQkq - 0 2";
+    assert_eq!(san(fools_mate, "d8h4"), "Qh4#");
+    assert_eq!(san("4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1", "e1c1"), "O-O-O");
+    assert_eq!(san("3k4/8/8/8/8/8/8/R3K3 w Q - 0 1", "e1c1"), "O-O-O+");
+    assert_eq!(san("6k1/5ppp/8/8/8/8/8/4K2R w K - 0 1", "e1g1"), "O-O");
+    assert_eq!(san("3k4/R7/8/8/8/8/8/4K2R w K - 0 1", "h1h8"), "Rh8#");
+}
+
+#[test]
+fn san_variants() {
+    let kiwipete: &str = common::KIWIPETE;
+    assert_eq!(lan(kiwipete, "0-0"), "e1g1");
+    assert_eq!(lan(kiwipete, "O-O-O"), "e1c1");
+    assert_eq!(lan(kiwipete, "Qxf6"), "f3f6");
+    assert_eq!(lan(kiwipete, "Qf6+"), "f3f6");
+    assert_eq!(lan(kiwipete, "Qxf6!?"), "f3f6");
+    assert_eq!(lan(kiwipete, "dxe6"), "d5e6");
+    assert_eq!(lan(kiwipete, "de6"), "d5e6");
+    assert_eq!(lan(kiwipete, "Nc3b5"), "c3b5");
+    assert_eq!(lan(kiwipete, "c3b5"), "c3b5");
+    assert_eq!(lan(kiwipete, "a2a3"), "a2a3");
+
+    let promotion: &str = "3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1";
+    assert_eq!(lan(promotion, "e8Q"), "e7e8q");
+    assert_eq!(lan(promotion, "e8=N"), "e7e8n");
+    assert_eq!(lan(promotion, "exd8=Q+"), "e7d8q");
+    assert_eq!(lan(promotion, "ed8q"), "e7d8q");
+}
+
+#[test]
+fn san_errors() {
+    let fen: Fen = Fen::new();
+    assert_eq!(fen.san_to_move("Nf4"), Err(FenError::IllegalSan("Nf4".to_string())));
+    assert_eq!(fen.san_to_move("O-O"), Err(FenError::IllegalSan("O-O".to_string())));
+    assert_eq!(fen.san_to_move("Zf3"), Err(FenError::BadSan("Zf3".to_string())));
+    assert_eq!(fen.san_to_move(""), Err(FenError::BadSan("".to_string())));
+
+    let knights: Fen = "r3k3/8/8/8/8/8/8/1N2KN2 w - - 0 1".parse().unwrap();
+    assert_eq!(knights.san_to_move("Nd2"), Err(FenError::AmbiguousSan("Nd2".to_string())));
+    assert_eq!(knights.san_to_move("Nbd2").map(|move1: Move| move1.to_string()), Ok("b1d2".to_string()));
+
+    // a promotion has to name the piece
+    let promotion: Fen = "3r3k/4P3/8/8/8/8/8/4K3 w - - 0 1".parse().unwrap();
+    assert_eq!(promotion.san_to_move("e8"), Err(FenError::IllegalSan("e8".to_string())));
+}
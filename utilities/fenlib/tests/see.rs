@@ -0,0 +1,106 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::undo::Undo;
+
+mod common;
+
+/// Returns the static exchange evaluation of the move in LAN.
+fn see(fen_str: &str, lan: &str) -> i32 {
+    let fen: Fen = fen_str.parse().unwrap();
+    let move1: Move = fen.parse_lan(lan).unwrap();
+    assert!(fen.is_legal_move(&move1), "{} in {}", lan, fen_str);
+
+    fen.see(move1)
+}
+
+#[test]
+fn undefended_capture() {
+    assert_eq!(see("1k1r4/1pp4p/p7/4p3/8/P5P1/1PP4P/2K1R3 w - - 0 1", "e1e5"), 100);
+}
+
+#[test]
+fn defended_capture() {
+    // Nxe5 Nxe5 and white stops, the queens and the rook behind the pieces do not change that
+    assert_eq!(see("1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1", "d3e5"), -220);
+
+    // a pawn defended by a pawn costs the queen
+    assert_eq!(see("4k3/8/2p5/3p4/8/8/8/3QK3 w - - 0 1", "d1d5"), 100 - 900);
+
+    // taking the queen is good even if the rook is lost
+    assert_eq!(see("4k3/8/2p5/3q4/8/8/8/3RK3 w - - 0 1", "d1d5"), 900 - 500);
+}
+
+#[test]
+fn x_rays() {
+    // the queen behind the rook takes back, so the knight is won
+    assert_eq!(see("4k3/8/4r3/4n3/8/8/4R3/4Q1K1 w - - 0 1", "e2e5"), 320);
+
+    // without it the rook is lost for the knight
+    assert_eq!(see("4k3/8/4r3/4n3/8/8/4R3/6K1 w - - 0 1", "e2e5"), 320 - 500);
+
+    // with the queen in front, Qxe5 Rxe5 Rxe5 still loses material
+    assert_eq!(see("4k3/8/4r3/4n3/8/8/4Q3/4R1K1 w - - 0 1", "e2e5"), 320 - 900 + 500);
+}
+
+#[test]
+fn king_only_takes_undefended_pieces() {
+    // Rxd5 Kxd5 loses the rook
+    assert_eq!(see("8/8/2k5/3p4/8/8/3R4/4K3 w - - 0 1", "d2d5"), 100 - 500);
+
+    // with the queen behind the rook the king cannot take back
+    assert_eq!(see("8/8/2k5/3p4/8/8/3R4/3QK3 w - - 0 1", "d2d5"), 100);
+}
+
+#[test]
+fn quiet_moves() {
+    assert_eq!(see("4k3/8/8/8/8/8/8/4K1N1 w - - 0 1", "g1f3"), 0);
+
+    // the knight steps onto a square the pawn attacks
+    assert_eq!(see("4k3/8/8/8/4p3/8/8/4K1N1 w - - 0 1", "g1f3"), -320);
+    assert_eq!(see("r3k3/8/8/8/8/8/8/4K3 b q - 0 1", "e8c8"), 0);
+}
+
+#[test]
+fn en_passant_and_promotions() {
+    assert_eq!(see("4k3/8/8/3pP3/8/8/8/4K3 w - d6 0 1", "e5d6"), 100);
+
+    assert_eq!(see("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), 800);
+    assert_eq!(see("4k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8n"), 220);
+
+    // the rook takes the new queen
+    assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7a8q"), 800 - 900);
+    assert_eq!(see("1r2k3/P7/8/8/8/8/8/4K3 w - - 0 1", "a7b8q"), 500 + 800);
+}
+
+#[test]
+fn see_ge() {
+    let fen: Fen = "4k3/8/2p5/3q4/8/8/8/3RK3 w - - 0 1".parse().unwrap();
+    let move1: Move = fen.parse_lan("d1d5").unwrap();
+
+    assert!(fen.see_ge(move1, 0));
+    assert!(fen.see_ge(move1, 400));
+    assert!(!fen.see_ge(move1, 401));
+}
+
+/// Checks in every position up to `depth` plies deep that `Fen::see_ge` agrees with `Fen::see` around the result.
+fn assert_see_ge_agrees(fen: &mut Fen, depth: u32) {
+    for move1 in fen.legal_moves().to_vec() {
+        let see: i32 = fen.see(move1);
+        for threshold in [see - 1, see, see + 1, -500, -100, 0, 100, 500] {
+            assert_eq!(fen.see_ge(move1, threshold), see >= threshold, "{} with threshold {} in {}", move1, threshold, fen);
+        }
+
+        if depth > 1 {
+            let undo: Undo = fen.make_move(move1);
+            assert_see_ge_agrees(fen, depth - 1);
+            fen.unmake_move(move1, undo);
+        }
+    }
+}
+
+#[test]
+fn see_ge_agrees_with_see() {
+    for fen_str in [common::KIWIPETE, common::POSITION_4, common::POSITION_5, common::POSITION_6, "1k1r3q/1ppn3p/p4b2/4p3/8/P2N2P1/1PP1R1BP/2K1Q3 w - - 0 1"] {
+        assert_see_ge_agrees(&mut fen_str.parse().unwrap(), 2);
+    }
+}
@@ -0,0 +1,155 @@
+use std::time::Duration;
+
+use fenlib::Fen;
+use fenlib::bot::SearchLimits;
+use fenlib::chess_move::Move;
+use fenlib::time_manager::{self, TimeManager};
+
+const KPK: &str = "4k3/8/8/8/8/8/4P3/4K3 w - - 0 1";
+
+/// Kxb2 is the only legal move.
+const FORCED: &str = "k7/8/8/8/8/8/1q6/K7 w - - 0 1";
+
+fn clock(wtime: u64, btime: u64) -> SearchLimits {
+    SearchLimits {
+        wtime: Some(Duration::from_millis(wtime)),
+        btime: Some(Duration::from_millis(btime)),
+        ..SearchLimits::default()
+    }
+}
+
+fn some_move(fen: &Fen) -> Move {
+    fen.legal_moves()[0]
+}
+
+#[test]
+fn movetime() {
+    let limits: SearchLimits = SearchLimits { movetime: Some(Duration::from_millis(500)), move_overhead: Duration::from_millis(20), ..SearchLimits::default() };
+    let time_manager: TimeManager = TimeManager::new(&limits, &Fen::new());
+
+    assert_eq!(time_manager.soft_limit(), Some(Duration::from_millis(480)));
+    assert_eq!(time_manager.hard_limit(), Some(Duration::from_millis(480)));
+}
+
+#[test]
+fn movetime_is_used_in_full() {
+    let limits: SearchLimits = SearchLimits { movetime: Some(Duration::from_secs(60)), ..SearchLimits::default() };
+
+    // a stable best move does not shorten it
+    let fen: Fen = Fen::new();
+    let mut time_manager: TimeManager = TimeManager::new(&limits, &fen);
+    for depth in 1..=6 {
+        assert!(time_manager.next_iteration(depth, some_move(&fen)));
+        assert_eq!(time_manager.adjusted_soft_limit(), Some(Duration::from_secs(60)));
+    }
+
+    // neither does a forced move
+    let fen: Fen = FORCED.parse().unwrap();
+    let mut time_manager: TimeManager = TimeManager::new(&limits, &fen);
+    assert!(time_manager.next_iteration(1, some_move(&fen)));
+}
+
+#[test]
+fn no_time_limit() {
+    let time_manager: TimeManager = TimeManager::new(&SearchLimits::depth(3), &Fen::new());
+    assert_eq!(time_manager.soft_limit(), None);
+    assert_eq!(time_manager.hard_limit(), None);
+
+    // an infinite search ignores the other limits
+    let limits: SearchLimits = SearchLimits { infinite: true, nodes: Some(10), ..clock(1000, 1000) };
+    let time_manager: TimeManager = TimeManager::new(&limits, &Fen::new());
+    assert_eq!(time_manager.hard_limit(), None);
+    assert!(!time_manager.is_out_of_time(1_000_000));
+}
+
+#[test]
+fn spends_the_clock_of_the_side_to_move() {
+    let white: TimeManager = TimeManager::new(&clock(60_000, 1_000), &Fen::new());
+    let black: TimeManager = TimeManager::new(&clock(1_000, 60_000), &"rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".parse().unwrap());
+
+    assert_eq!(white.soft_limit(), black.soft_limit());
+    assert_eq!(white.soft_limit(), Some(Duration::from_millis(60_000 / 40)));
+    assert!(white.hard_limit().unwrap() > white.soft_limit().unwrap());
+    assert!(white.hard_limit().unwrap() <= Duration::from_millis(45_000));
+}
+
+#[test]
+fn increment_and_moves_to_go() {
+    let without: Duration = TimeManager::new(&clock(60_000, 60_000), &Fen::new()).soft_limit().unwrap();
+    let with: Duration = TimeManager::new(&SearchLimits { winc: Duration::from_secs(1), ..clock(60_000, 60_000) }, &Fen::new()).soft_limit().unwrap();
+    assert_eq!(with, without + Duration::from_millis(750));
+
+    let ten: Duration = TimeManager::new(&SearchLimits { movestogo: Some(10), ..clock(60_000, 60_000) }, &Fen::new()).soft_limit().unwrap();
+    assert_eq!(ten, Duration::from_millis(6_000));
+
+    // the last move before the time control still keeps a quarter of the clock in reserve
+    let last: TimeManager = TimeManager::new(&SearchLimits { movestogo: Some(1), ..clock(60_000, 60_000) }, &Fen::new());
+    assert_eq!(last.hard_limit(), Some(Duration::from_millis(45_000)));
+    assert_eq!(last.soft_limit(), Some(Duration::from_millis(45_000)));
+}
+
+#[test]
+fn game_phase() {
+    let endgame: Fen = KPK.parse().unwrap();
+    assert_eq!(time_manager::expected_moves_to_go(&Fen::new()), 40);
+    assert_eq!(time_manager::expected_moves_to_go(&endgame), 20);
+
+    let opening: Duration = TimeManager::new(&clock(60_000, 60_000), &Fen::new()).soft_limit().unwrap();
+    let ending: Duration = TimeManager::new(&clock(60_000, 60_000), &endgame).soft_limit().unwrap();
+    assert!(ending > opening);
+}
+
+#[test]
+fn move_overhead() {
+    let limits: SearchLimits = SearchLimits { move_overhead: Duration::from_millis(50), ..clock(50, 50) };
+    let time_manager: TimeManager = TimeManager::new(&limits, &Fen::new());
+
+    assert_eq!(time_manager.hard_limit(), Some(Duration::from_millis(1)));
+}
+
+#[test]
+fn stops_early_on_a_forced_move() {
+    let fen: Fen = FORCED.parse().unwrap();
+    assert_eq!(fen.legal_moves().len(), 1);
+
+    let mut time_manager: TimeManager = TimeManager::new(&clock(60_000, 60_000), &fen);
+    assert!(!time_manager.next_iteration(1, some_move(&fen)));
+
+    // a search for a depth still gets that depth
+    let mut time_manager: TimeManager = TimeManager::new(&SearchLimits::depth(3), &fen);
+    assert!(time_manager.next_iteration(1, some_move(&fen)));
+    assert!(time_manager.next_iteration(2, some_move(&fen)));
+    assert!(!time_manager.next_iteration(3, some_move(&fen)));
+}
+
+#[test]
+fn stable_best_move_shortens_the_search() {
+    let fen: Fen = Fen::new();
+    let moves: Vec<Move> = fen.legal_moves().to_vec();
+    let mut time_manager: TimeManager = TimeManager::new(&clock(60_000, 60_000), &fen);
+    let soft: Duration = time_manager.soft_limit().unwrap();
+
+    // a new best move gets more time than planned
+    time_manager.next_iteration(1, moves[0]);
+    assert_eq!(time_manager.adjusted_soft_limit(), Some(soft * 140 / 100));
+
+    let mut previous: Duration = time_manager.adjusted_soft_limit().unwrap();
+    for depth in 2..=6 {
+        time_manager.next_iteration(depth, moves[0]);
+        let adjusted: Duration = time_manager.adjusted_soft_limit().unwrap();
+        assert!(adjusted <= previous, "depth {}", depth);
+        previous = adjusted;
+    }
+    assert_eq!(previous, soft / 2);
+
+    time_manager.next_iteration(7, moves[1]);
+    assert_eq!(time_manager.adjusted_soft_limit(), Some(soft * 140 / 100));
+}
+
+#[test]
+fn node_limit() {
+    let time_manager: TimeManager = TimeManager::new(&SearchLimits { nodes: Some(100), ..SearchLimits::default() }, &Fen::new());
+
+    assert!(!time_manager.is_out_of_time(99));
+    assert!(time_manager.is_out_of_time(100));
+}
@@ -0,0 +1,121 @@
+use std::mem;
+
+use fenlib::Fen;
+use fenlib::bot::MATE_SCORE;
+use fenlib::chess_move::Move;
+use fenlib::transposition::{Bound, Entry, TranspositionTable};
+
+fn some_move(index: usize) -> Move {
+    Fen::new().legal_moves()[index]
+}
+
+#[test]
+fn size() {
+    assert_eq!(mem::size_of::<Entry>(), 16);
+    assert_eq!(TranspositionTable::new(1).len(), 1024 * 1024 / 16);
+    assert_eq!(TranspositionTable::new(0).len(), 1);
+
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+    tt.resize(2);
+    assert_eq!(tt.len(), 2 * 1024 * 1024 / 16);
+}
+
+#[test]
+fn store_and_probe() {
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+    let hash: u64 = Fen::new().hash();
+    assert_eq!(tt.probe(hash), None);
+
+    tt.store(hash, 5, 0, 42, Bound::Exact, some_move(0));
+    let entry: Entry = tt.probe(hash).unwrap();
+    assert_eq!(entry.depth(), 5);
+    assert_eq!(entry.score(0), 42);
+    assert_eq!(entry.bound(), Bound::Exact);
+    assert_eq!(entry.best_move(), some_move(0));
+
+    // another position in the same slot is not mistaken for it
+    assert_eq!(tt.probe(hash + tt.len() as u64), None);
+
+    tt.clear();
+    assert_eq!(tt.probe(hash), None);
+}
+
+#[test]
+fn cutoffs() {
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+
+    tt.store(1, 4, 0, 50, Bound::Exact, Move::NULL);
+    let exact: Entry = tt.probe(1).unwrap();
+    assert_eq!(exact.cutoff(4, 0, -100, 100), Some(50));
+    assert_eq!(exact.cutoff(5, 0, -100, 100), None);
+
+    tt.store(2, 4, 0, 50, Bound::Lower, Move::NULL);
+    let lower: Entry = tt.probe(2).unwrap();
+    assert_eq!(lower.cutoff(3, 0, 0, 50), Some(50));
+    assert_eq!(lower.cutoff(3, 0, 0, 60), None);
+
+    tt.store(3, 4, 0, 50, Bound::Upper, Move::NULL);
+    let upper: Entry = tt.probe(3).unwrap();
+    assert_eq!(upper.cutoff(3, 0, 50, 100), Some(50));
+    assert_eq!(upper.cutoff(3, 0, 40, 100), None);
+}
+
+#[test]
+fn mate_scores_are_stored_relative_to_the_position() {
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+
+    // mate in 3 plies from a position 4 plies from the root
+    tt.store(1, 3, 4, MATE_SCORE - 7, Bound::Exact, Move::NULL);
+    let entry: Entry = tt.probe(1).unwrap();
+    assert_eq!(entry.score(4), MATE_SCORE - 7);
+    assert_eq!(entry.score(2), MATE_SCORE - 5);
+
+    tt.store(2, 3, 4, 7 - MATE_SCORE, Bound::Exact, Move::NULL);
+    assert_eq!(tt.probe(2).unwrap().score(6), 9 - MATE_SCORE);
+
+    // normal scores do not change with the ply
+    tt.store(3, 3, 4, 250, Bound::Exact, Move::NULL);
+    assert_eq!(tt.probe(3).unwrap().score(10), 250);
+}
+
+#[test]
+fn replacement() {
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+    let other: u64 = 1 + tt.len() as u64;
+
+    // a shallower search of another position does not replace a deeper one of the same search
+    tt.store(1, 6, 0, 10, Bound::Lower, some_move(0));
+    tt.store(other, 2, 0, 20, Bound::Lower, some_move(1));
+    assert!(tt.probe(1).is_some());
+    assert_eq!(tt.probe(other), None);
+
+    // but an exact score does
+    tt.store(other, 2, 0, 20, Bound::Exact, some_move(1));
+    assert_eq!(tt.probe(1), None);
+    assert!(tt.probe(other).is_some());
+
+    // entries of earlier searches are always replaced
+    tt.store(1, 8, 0, 10, Bound::Lower, some_move(0));
+    tt.new_search();
+    tt.store(other, 1, 0, 20, Bound::Upper, some_move(1));
+    assert!(tt.probe(other).is_some());
+
+    // the same position keeps its best move when the new search has none
+    tt.store(other, 3, 0, 0, Bound::Upper, Move::NULL);
+    assert_eq!(tt.probe(other).unwrap().best_move(), some_move(1));
+}
+
+#[test]
+fn hashfull() {
+    let mut tt: TranspositionTable = TranspositionTable::new(1);
+    assert_eq!(tt.hashfull(), 0);
+
+    for hash in 0..500 {
+        tt.store(hash, 1, 0, 0, Bound::Exact, Move::NULL);
+    }
+    assert_eq!(tt.hashfull(), 500);
+
+    // only the current search counts
+    tt.new_search();
+    assert_eq!(tt.hashfull(), 0);
+}
@@ -0,0 +1,62 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::undo::Undo;
+
+mod common;
+
+/// How many moves of each special kind the tree walk played.
+#[derive(Debug, Default)]
+struct Played {
+    en_passant: u32,
+    castles: u32,
+    promotion_captures: u32,
+}
+
+/// Plays every move of the tree and checks that taking it back restores the whole position,
+/// the board, castling rights, en passant square, counters and hash.
+fn check_tree(fen: &mut Fen, depth: u32, played: &mut Played) {
+    if depth == 0 {
+        return;
+    }
+
+    let before: Fen = fen.clone();
+    for &move1 in &fen.legal_moves() {
+        played.en_passant += u32::from(move1.is_en_passant());
+        played.castles += u32::from(move1.is_castle());
+        played.promotion_captures += u32::from(move1.is_promotion() && move1.is_capture());
+
+        let undo: Undo = fen.make_move(move1);
+        check_tree(fen, depth - 1, played);
+        fen.unmake_move(move1, undo);
+
+        assert!(*fen == before, "taking back {} gives {} instead of {}", move1, fen, before);
+    }
+}
+
+#[test]
+fn unmake_move_restores_the_position() {
+    let mut played: Played = Played::default();
+    for fen_str in [common::KIWIPETE, common::POSITION_3, common::POSITION_4] {
+        let mut fen: Fen = fen_str.parse().unwrap();
+        check_tree(&mut fen, 3, &mut played);
+    }
+
+    assert!(played.en_passant > 0 && played.castles > 0 && played.promotion_captures > 0, "{:?}", played);
+}
+
+#[test]
+fn counters_stop_at_the_limit() {
+    let fen_str: &str = "4k3/8/8/8/8/8/8/4K2R b K - 65535 65535";
+    let mut fen: Fen = fen_str.parse().unwrap();
+
+    let move1: Move = fen.parse_lan("e8d8").unwrap();
+    let undo: Undo = fen.make_move(move1);
+    assert_eq!((fen.halfmove, fen.fullmove), (u16::MAX, u16::MAX));
+    fen.unmake_move(move1, undo);
+    assert_eq!(fen.to_string(), fen_str);
+
+    let undo: Undo = fen.make_null_move();
+    assert_eq!((fen.halfmove, fen.fullmove), (u16::MAX, u16::MAX));
+    fen.unmake_null_move(undo);
+    assert_eq!(fen.to_string(), fen_str);
+}
@@ -0,0 +1,88 @@
+use fenlib::Fen;
+use fenlib::bitboard::Bitboard;
+use fenlib::error::FenError;
+use fenlib::square::Square;
+use fenlib::utils::*;
+use fenlib::validation::Violation;
+
+mod common;
+
+fn violations(fen_str: &str) -> Vec<Violation> {
+    fen_str.parse::<Fen>().unwrap().validate()
+}
+
+fn bit(square: Square) -> u64 {
+    Bitboard::from_square(square).0
+}
+
+#[test]
+fn valid_positions() {
+    assert_eq!(Fen::new().validate(), vec![]);
+    assert_eq!(violations(common::KIWIPETE), vec![]);
+
+    // a pawn has just moved two squares, for either side
+    assert_eq!(violations("rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq e3 0 1"), vec![]);
+    assert_eq!(violations("rnbqkbnr/ppp1pppp/8/3pP3/8/8/PPPP1PPP/RNBQKBNR w KQkq d6 0 2"), vec![]);
+}
+
+#[test]
+fn wrong_king_count() {
+    assert_eq!(violations("8/8/8/8/8/8/8/4K3 w - - 0 1"), vec![Violation::WrongKingCount { white: false, count: 0 }]);
+    assert_eq!(violations("4k3/8/8/8/8/8/8/3KK3 w - - 0 1"), vec![Violation::WrongKingCount { white: true, count: 2 }]);
+}
+
+#[test]
+fn pawns_on_back_rank() {
+    assert_eq!(violations("P3k3/8/8/8/8/8/8/4K3 w - - 0 1"), vec![Violation::PawnsOnBackRank(bit(Square::A8))]);
+    assert_eq!(violations("4k3/8/8/8/8/8/8/4K2p w - - 0 1"), vec![Violation::PawnsOnBackRank(bit(Square::H1))]);
+}
+
+#[test]
+fn opponent_in_check() {
+    // white to move could capture the black king
+    assert_eq!(violations("4k3/8/8/8/8/8/8/4RK2 w - - 0 1"), vec![Violation::OpponentInCheck]);
+
+    // the side to move may be in check
+    assert_eq!(violations("4k3/8/8/8/8/8/8/4RK2 b - - 0 1"), vec![]);
+}
+
+#[test]
+fn invalid_en_passant() {
+    // no pawn in front of the square
+    assert_eq!(violations("4k3/8/8/8/8/8/8/4K3 w - e6 0 1"), vec![Violation::InvalidEnPassant(bit(Square::E6))]);
+
+    // the pawn belongs to the side to move
+    assert_eq!(violations("4k3/8/8/4P3/8/8/8/4K3 w - e6 0 1"), vec![Violation::InvalidEnPassant(bit(Square::E6))]);
+
+    // the square the pawn came from is occupied
+    assert_eq!(violations("4k3/4n3/8/4p3/8/8/8/4K3 w - e6 0 1"), vec![Violation::InvalidEnPassant(bit(Square::E6))]);
+
+    // a square on the rank of the side to move
+    assert_eq!(violations("4k3/8/8/8/4P3/8/8/4K3 w - e3 0 1"), vec![Violation::InvalidEnPassant(bit(Square::E3))]);
+}
+
+#[test]
+fn invalid_castling() {
+    // the white king moved
+    assert_eq!(
+        violations("r3k2r/8/8/8/8/8/8/R4K1R w KQkq - 0 1"),
+        vec![Violation::InvalidCastling(WHITE_KINGSIDE_INFO), Violation::InvalidCastling(WHITE_QUEENSIDE_INFO)]
+    );
+
+    // the black rook on h8 moved
+    assert_eq!(violations("r3k1r1/8/8/8/8/8/8/R3K2R w KQkq - 0 1"), vec![Violation::InvalidCastling(BLACK_KINGSIDE_INFO)]);
+    assert_eq!(violations("r3k1r1/8/8/8/8/8/8/R3K2R w KQq - 0 1"), vec![]);
+}
+
+#[test]
+fn from_str_strict() {
+    assert!(Fen::from_str_strict(DEFAULT).is_ok());
+
+    assert_eq!(
+        Fen::from_str_strict("8/8/8/8/8/8/8/4K3 w - - 0 1").unwrap_err(),
+        FenError::InvalidPosition(vec![Violation::WrongKingCount { white: false, count: 0 }])
+    );
+
+    // syntax errors come first
+    assert_eq!(Fen::from_str_strict("8/8/8 w - - 0 1").unwrap_err(), FenError::WrongRankCount(3));
+}
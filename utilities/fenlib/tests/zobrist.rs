@@ -0,0 +1,94 @@
+use fenlib::Fen;
+use fenlib::chess_move::Move;
+use fenlib::undo::Undo;
+
+mod common;
+
+/// Plays every move of the tree and checks that the incremental hash equals a recomputation,
+/// both after the move and after taking it back.
+fn check_tree(fen: &mut Fen, depth: u32) {
+    if depth == 0 {
+        return;
+    }
+
+    let hash: u64 = fen.hash();
+
+    for &move1 in &fen.legal_moves() {
+        let undo: Undo = fen.make_move(move1);
+        assert_eq!(fen.hash(), fen.compute_hash(), "after {} in {}", move1, fen);
+
+        check_tree(fen, depth - 1);
+
+        fen.unmake_move(move1, undo);
+        assert_eq!(fen.hash(), hash, "after taking back {} in {}", move1, fen);
+    }
+}
+
+fn play(fen_str: &str, moves: &[&str]) -> Fen {
+    let mut fen: Fen = fen_str.parse().unwrap();
+    for lan in moves {
+        let move1: Move = fen.parse_lan(lan).unwrap();
+        assert!(fen.is_legal_move(&move1), "{} is not legal in {}", lan, fen);
+        fen.move_to_fen(&move1);
+    }
+    fen
+}
+
+#[test]
+fn incremental_hash_matches_recomputation() {
+    for fen_str in common::TREE_POSITIONS {
+        let mut fen: Fen = fen_str.parse().unwrap();
+        check_tree(&mut fen, 3);
+    }
+}
+
+#[test]
+fn transpositions_have_the_same_hash() {
+    let start: Fen = Fen::new();
+    let knights_back: Fen = play(fenlib::utils::DEFAULT, &["g1f3", "g8f6", "f3g1", "f6g8"]);
+    assert_eq!(start.hash(), knights_back.hash());
+
+    let first: Fen = play(fenlib::utils::DEFAULT, &["e2e4", "e7e5", "g1f3", "b8c6"]);
+    let second: Fen = play(fenlib::utils::DEFAULT, &["g1f3", "b8c6", "e2e4", "e7e5"]);
+    assert_eq!(first.hash(), second.hash());
+    assert_eq!(first.hash(), first.to_string().parse::<Fen>().unwrap().hash());
+}
+
+#[test]
+fn side_to_move_and_castling_change_the_hash() {
+    let white: Fen = "4k3/8/8/8/8/8/8/R3K2R w KQ - 0 1".parse().unwrap();
+    let black: Fen = "4k3/8/8/8/8/8/8/R3K2R b KQ - 0 1".parse().unwrap();
+    let no_castling: Fen = "4k3/8/8/8/8/8/8/R3K2R w - - 0 1".parse().unwrap();
+
+    assert_ne!(white.hash(), black.hash());
+    assert_ne!(white.hash(), no_castling.hash());
+}
+
+#[test]
+fn en_passant_only_counts_when_a_capture_is_possible() {
+    // no black pawn can capture on e3, so the position is the same as without the en passant square
+    let pushed: Fen = play(fenlib::utils::DEFAULT, &["e2e4"]);
+    let without: Fen = "rnbqkbnr/pppppppp/8/8/4P3/8/PPPP1PPP/RNBQKBNR b KQkq - 0 1".parse().unwrap();
+    assert_eq!(pushed.hash(), without.hash());
+
+    // the pawn on d4 can capture on e3
+    let capturable: Fen = "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1".parse().unwrap();
+    let not_capturable: Fen = "4k3/8/8/8/3pP3/8/8/4K3 b - - 0 1".parse().unwrap();
+    assert_ne!(capturable.hash(), not_capturable.hash());
+}
+
+#[test]
+fn null_move_passes_the_turn() {
+    let fen_str: &str = "4k3/8/8/8/3pP3/8/8/4K3 b - e3 0 1";
+    let mut fen: Fen = fen_str.parse().unwrap();
+
+    // the en passant square is gone after passing
+    let undo: Undo = fen.make_null_move();
+    let passed: Fen = "4k3/8/8/8/3pP3/8/8/4K3 w - - 1 2".parse().unwrap();
+    assert_eq!(fen.to_string(), passed.to_string());
+    assert_eq!(fen.hash(), passed.hash());
+
+    fen.unmake_null_move(undo);
+    assert_eq!(fen.to_string(), fen_str);
+    assert_eq!(fen.hash(), fen_str.parse::<Fen>().unwrap().hash());
+}
@@ -0,0 +1,25 @@
+use std::env;
+use std::io;
+use std::process;
+use std::sync::{Arc, Mutex};
+
+use fenlib::bot::Bot;
+use protocols::Output;
+use protocols::xboard;
+
+/*
+Speaks the Chess Engine Communication Protocol (xboard/WinBoard) on stdin and stdout, for interfaces and tools that do not support UCI.
+
+Usage: cargo run --release --bin xboard -- [botv1|botv2]
+*/
+
+fn main() {
+    let name: String = env::args().nth(1).unwrap_or(bot_registry::BOTS[0].to_string());
+    let bot: Box<dyn Bot> = bot_registry::bot_by_name(&name).unwrap_or_else(|| {
+        eprintln!("unknown bot '{}', expected one of {}", name, bot_registry::BOTS.join(", "));
+        process::exit(2);
+    });
+
+    let output: Output = Arc::new(Mutex::new(io::stdout()));
+    xboard::run(bot, io::stdin().lock(), output);
+}
@@ -0,0 +1,157 @@
+use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::thread::{self, JoinHandle};
+use std::time::{Duration, Instant};
+
+use fenlib::bot::{Bot, BotOption, OptionError, SearchInfo, SearchLimits, SearchResult};
+use fenlib::chess_move::Move;
+use fenlib::game::Game;
+
+/// The engine core shared by the protocol front ends: it keeps the game and runs the search of a bot
+/// on a separate thread, so the front end can keep reading commands like "stop" while it searches.
+pub struct Engine {
+    game: Game,
+
+    /// The bot is moved to the thread of a running search, and back when the search is done.
+    bot: Option<Box<dyn Bot>>,
+    name: String,
+    options: Vec<BotOption>,
+    stop: Arc<AtomicBool>,
+    search: Option<JoinHandle<Box<dyn Bot>>>,
+    infinite: bool,
+}
+
+impl Engine {
+    pub fn new(bot: Box<dyn Bot>) -> Self {
+        let name: String = bot.name().to_string();
+        let options: Vec<BotOption> = bot.options();
+        let stop: Arc<AtomicBool> = bot.stop_signal();
+
+        Self { game: Game::new(), bot: Some(bot), name, options, stop, search: None, infinite: false }
+    }
+
+    /// Returns the name of the bot.
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the settings of the bot, see `Bot::options`.
+    pub fn options(&self) -> &[BotOption] {
+        &self.options
+    }
+
+    /// Changes a setting of the bot, stopping a running search first.
+    pub fn set_option(&mut self, name: &str, value: i64) -> Result<(), OptionError> {
+        self.stop();
+        match self.bot.as_mut() {
+            Some(bot) => bot.set_option(name, value),
+            None => Err(OptionError::Unknown(name.to_string())),
+        }
+    }
+
+    /// Returns the game the next search starts from.
+    pub fn game(&self) -> &Game {
+        &self.game
+    }
+
+    /// Replaces the game, stopping a running search first.
+    pub fn set_game(&mut self, game: Game) {
+        self.stop();
+        self.game = game;
+    }
+
+    /// Starts a new game from the starting position, the bot forgets what it remembers from the last game.
+    pub fn new_game(&mut self) {
+        self.stop();
+        self.game = Game::new();
+        if let Some(bot) = self.bot.as_mut() {
+            bot.new_game();
+        }
+    }
+
+    /// Plays a move in the game, stopping a running search first.
+    ///
+    /// **NOTE:** this function does not check whether the move is legal
+    pub fn make_move(&mut self, move1: Move) {
+        self.stop();
+        self.game.make_move(move1);
+    }
+
+    /// Takes back the last move of the game, stopping a running search first.
+    pub fn undo(&mut self) -> Option<Move> {
+        self.stop();
+        self.game.undo()
+    }
+
+    /// Checks whether a search is still running.
+    pub fn is_searching(&self) -> bool {
+        self.search.as_ref().is_some_and(|search: &JoinHandle<Box<dyn Bot>>| !search.is_finished())
+    }
+
+    /// Starts searching the current position on a separate thread, stopping a running search first.
+    ///
+    /// `on_info` is called after every completed depth with the time searched so far,
+    /// `on_done` is called once with the best move, or `Move::NULL` if there are no legal moves.
+    pub fn go<I, D>(&mut self, limits: SearchLimits, mut on_info: I, on_done: D)
+    where
+        I: FnMut(&SearchInfo, Duration) + Send + 'static,
+        D: FnOnce(Move) + Send + 'static,
+    {
+        self.stop();
+        self.stop.store(false, Ordering::Relaxed);
+        self.infinite = limits.infinite;
+
+        // the bot is only missing if an earlier search panicked, its message has already been printed
+        let Some(mut bot) = self.bot.take() else {
+            on_done(Move::NULL);
+            return;
+        };
+        bot.set_position(self.game.fen().clone());
+
+        let stop: Arc<AtomicBool> = Arc::clone(&self.stop);
+        self.search = Some(thread::spawn(move || {
+            let start_time: Instant = Instant::now();
+            let result: SearchResult = bot.choose_move(&limits, &mut |info: &SearchInfo| on_info(info, start_time.elapsed()));
+
+            // an infinite search only reports its move when it is told to stop
+            if limits.infinite {
+                while !stop.load(Ordering::Relaxed) {
+                    thread::sleep(Duration::from_millis(1));
+                }
+            }
+
+            on_done(result.best_move);
+            bot
+        }));
+    }
+
+    /// Stops a running search and waits until it has reported its move.
+    pub fn stop(&mut self) {
+        self.stop.store(true, Ordering::Relaxed);
+        self.wait();
+    }
+
+    /// Waits until a running search has reported its move, an infinite search is stopped instead.
+    pub fn finish(&mut self) {
+        if self.infinite {
+            self.stop();
+        } else {
+            self.wait();
+        }
+    }
+
+    fn wait(&mut self) {
+        if let Some(search) = self.search.take() {
+            // a panicking search has already printed its message, there is no move to report
+            if let Ok(bot) = search.join() {
+                self.bot = Some(bot);
+            }
+        }
+    }
+}
+
+impl Drop for Engine {
+    fn drop(&mut self) {
+        self.stop();
+    }
+}
@@ -0,0 +1,18 @@
+pub mod engine;
+pub mod uci;
+pub mod xboard;
+
+use std::io::Write;
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Where a protocol front end writes its replies, shared with the thread that runs the search.
+pub type Output = Arc<Mutex<dyn Write + Send>>;
+
+/// Writes a line to the output and flushes it, so the GUI sees it immediately.
+pub fn send(output: &Output, line: &str) {
+    let mut output = output.lock().unwrap_or_else(PoisonError::into_inner);
+
+    // if the GUI closed the pipe there is nobody left to tell
+    let _ = writeln!(output, "{}", line);
+    let _ = output.flush();
+}
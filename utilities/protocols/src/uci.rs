@@ -0,0 +1,215 @@
+use std::io::BufRead;
+use std::time::Duration;
+
+use fenlib::Fen;
+use fenlib::bot::{Bot, BotOption, SearchInfo, SearchLimits};
+use fenlib::chess_move::Move;
+use fenlib::game::Game;
+
+use crate::engine::Engine;
+use crate::{Output, send};
+
+const AUTHOR: &str = "David van den Beukel";
+
+/// Milliseconds kept in reserve on every move for the communication with the GUI.
+const DEFAULT_MOVE_OVERHEAD: u64 = 10;
+const MAX_MOVE_OVERHEAD: u64 = 5000;
+
+/// Parses the arguments after "go", arguments that are unknown or lack a valid value are ignored.
+///
+/// A "go" without any limit searches until it is stopped, like "go infinite".
+pub fn parse_go(args: &[&str], move_overhead: Duration) -> SearchLimits {
+    let mut limits: SearchLimits = SearchLimits { move_overhead, ..SearchLimits::default() };
+    let mut args = args.iter();
+
+    while let Some(&arg) = args.next() {
+        let mut value = || args.next().and_then(|value: &&str| value.parse::<i64>().ok());
+
+        // GUIs send a negative time when the clock has run out
+        let mut millis = || value().map(|millis: i64| Duration::from_millis(millis.max(0) as u64));
+
+        match arg {
+            "wtime" => limits.wtime = millis(),
+            "btime" => limits.btime = millis(),
+            "winc" => limits.winc = millis().unwrap_or_default(),
+            "binc" => limits.binc = millis().unwrap_or_default(),
+            "movetime" => limits.movetime = millis(),
+            "movestogo" => limits.movestogo = value().map(|moves: i64| moves.max(0) as u32),
+            "depth" => limits.depth = value().map(|depth: i64| depth.max(1) as u32),
+            "nodes" => limits.nodes = value().map(|nodes: i64| nodes.max(1) as u64),
+            "infinite" => limits.infinite = true,
+            _ => {},
+        }
+    }
+
+    limits.infinite |= !limits.is_limited();
+    limits
+}
+
+/// Formats a move for UCI, the null move is written as "0000".
+fn uci_move(move1: Move) -> String {
+    if move1.is_null() {
+        "0000".to_string()
+    } else {
+        move1.to_string()
+    }
+}
+
+/// Formats the "info" line reported after every completed depth.
+pub fn info_line(info: &SearchInfo, time: Duration) -> String {
+    let millis: u128 = time.as_millis();
+    let nps: u128 = info.nodes as u128 * 1000 / millis.max(1);
+    let pv: Vec<String> = info.pv.iter().map(|&move1: &Move| uci_move(move1)).collect();
+
+    let score: String = match info.mate() {
+        Some(moves) => format!("mate {}", moves),
+        None => format!("cp {}", info.score),
+    };
+
+    let hashfull: String = info.hashfull.map(|hashfull: u32| format!(" hashfull {}", hashfull)).unwrap_or_default();
+
+    format!("info depth {} score {} nodes {} nps {}{} time {} pv {}", info.depth, score, info.nodes, nps, hashfull, millis, pv.join(" "))
+}
+
+/// A UCI front end for a bot, it reads commands line by line and writes its replies to the output.
+pub struct Uci {
+    engine: Engine,
+    output: Output,
+    move_overhead: Duration,
+}
+
+impl Uci {
+    pub fn new(bot: Box<dyn Bot>, output: Output) -> Self {
+        Self { engine: Engine::new(bot), output, move_overhead: Duration::from_millis(DEFAULT_MOVE_OVERHEAD) }
+    }
+
+    /// Handles a single command, returns false if the engine has to quit.
+    pub fn handle(&mut self, line: &str) -> bool {
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return true;
+        };
+
+        match command {
+            "uci" => {
+                send(&self.output, &format!("id name {}", self.engine.name()));
+                send(&self.output, &format!("id author {}", AUTHOR));
+                send(&self.output, &format!("option name Move Overhead type spin default {} min 0 max {}", DEFAULT_MOVE_OVERHEAD, MAX_MOVE_OVERHEAD));
+                for option in self.engine.options() {
+                    send(&self.output, &format!("option name {} type spin default {} min {} max {}", option.name, option.default, option.min, option.max));
+                }
+                send(&self.output, "uciok");
+            },
+            "isready" => send(&self.output, "readyok"),
+            "ucinewgame" => self.engine.new_game(),
+            "position" => self.position(args),
+            "go" => self.go(args),
+            "stop" => self.engine.stop(),
+            "setoption" => self.set_option(args),
+            "quit" => {
+                self.engine.stop();
+                return false;
+            },
+            "debug" | "register" | "ponderhit" => {},
+            _ => send(&self.output, &format!("info string unknown command '{}'", command)),
+        }
+
+        true
+    }
+
+    /// Waits for a running search to report its move, an infinite search is stopped.
+    pub fn finish(&mut self) {
+        self.engine.finish();
+    }
+
+    /// Handles "position startpos|fen <fen> [moves <lan>...]".
+    fn position(&mut self, args: &[&str]) {
+        let moves_at: usize = args.iter().position(|&arg: &&str| arg == "moves").unwrap_or(args.len());
+        let (setup, moves): (&[&str], &[&str]) = (&args[..moves_at], args.get(moves_at + 1..).unwrap_or(&[]));
+
+        let fen: Fen = match setup.split_first() {
+            Some((&"startpos", _)) => Fen::new(),
+            Some((&"fen", fields)) => match fields.join(" ").parse() {
+                Ok(fen) => fen,
+                Err(err) => {
+                    send(&self.output, &format!("info string {}", err));
+                    return;
+                },
+            },
+            _ => {
+                send(&self.output, "info string expected 'position startpos' or 'position fen <fen>'");
+                return;
+            },
+        };
+
+        let mut game: Game = Game::from_fen(fen);
+        for &lan in moves {
+            if !game.fen().is_legal_move_lan(lan) {
+                send(&self.output, &format!("info string illegal move '{}' in {}", lan, game.fen()));
+                break;
+            }
+
+            // a legal move can always be parsed
+            let _ = game.make_move_lan(lan);
+        }
+
+        self.engine.set_game(game);
+    }
+
+    /// Handles "go", the best move is reported by the search thread when it is done.
+    fn go(&mut self, args: &[&str]) {
+        let limits: SearchLimits = parse_go(args, self.move_overhead);
+
+        let info_output: Output = self.output.clone();
+        let done_output: Output = self.output.clone();
+        self.engine.go(
+            limits,
+            move |info: &SearchInfo, time: Duration| send(&info_output, &info_line(info, time)),
+            move |best_move: Move| send(&done_output, &format!("bestmove {}", uci_move(best_move))),
+        );
+    }
+
+    /// Handles "setoption name <name> [value <value>]".
+    fn set_option(&mut self, args: &[&str]) {
+        let value_at: usize = args.iter().position(|&arg: &&str| arg == "value").unwrap_or(args.len());
+        let name: String = args[..value_at].iter().skip_while(|&&arg: &&&str| arg == "name").copied().collect::<Vec<&str>>().join(" ");
+        let value: String = args.get(value_at + 1..).unwrap_or(&[]).join(" ");
+
+        match name.to_ascii_lowercase().as_str() {
+            "move overhead" => match value.parse::<u64>() {
+                Ok(millis) if millis <= MAX_MOVE_OVERHEAD => self.move_overhead = Duration::from_millis(millis),
+                _ => send(&self.output, &format!("info string invalid value '{}' for Move Overhead", value)),
+            },
+            _ => {
+                let result: Result<(), String> = match value.parse::<i64>() {
+                    Ok(value) => self.engine.set_option(&name, value).map_err(|err| err.to_string()),
+                    Err(_) if self.engine.options().iter().any(|option: &BotOption| option.name.eq_ignore_ascii_case(&name)) => {
+                        Err(format!("invalid value '{}' for {}", value, name))
+                    },
+                    Err(_) => Err(format!("unknown option '{}'", name)),
+                };
+
+                if let Err(message) = result {
+                    send(&self.output, &format!("info string {}", message));
+                }
+            },
+        }
+    }
+}
+
+/// Runs a UCI session until "quit" or the end of the input, at the end of the input a running search is finished first.
+pub fn run<R: BufRead>(bot: Box<dyn Bot>, input: R, output: Output) {
+    let mut uci: Uci = Uci::new(bot, output);
+
+    for line in input.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        if !uci.handle(&line) {
+            return;
+        }
+    }
+
+    uci.finish();
+}
@@ -0,0 +1,363 @@
+use std::io::BufRead;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex, PoisonError};
+use std::time::Duration;
+
+use fenlib::Fen;
+use fenlib::bot::{Bot, SearchInfo, SearchLimits};
+use fenlib::chess_move::Move;
+use fenlib::game::Game;
+use fenlib::piece::Color;
+use fenlib::result::GameResult;
+
+use crate::engine::Engine;
+use crate::{Output, send};
+
+/// Milliseconds kept in reserve on every move for the communication with the interface.
+const MOVE_OVERHEAD: u64 = 10;
+
+/// The time control set by "level", "st" and "sd".
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct TimeControl {
+    /// The number of moves per session, 0 means the whole game is played in one session.
+    pub moves_per_session: u32,
+    pub base: Duration,
+    pub increment: Duration,
+
+    /// A fixed time per move set by "st", it overrides the session.
+    pub per_move: Option<Duration>,
+    pub depth: Option<u32>,
+}
+
+impl Default for TimeControl {
+    fn default() -> Self {
+        Self { moves_per_session: 40, base: Duration::from_secs(5 * 60), increment: Duration::ZERO, per_move: None, depth: None }
+    }
+}
+
+impl TimeControl {
+    /// Parses the arguments of "level MPS BASE INC", where the base is given in minutes or as "minutes:seconds"
+    /// and the increment in seconds.
+    pub fn parse_level(args: &[&str]) -> Option<(u32, Duration, Duration)> {
+        let [moves, base, increment] = args else {
+            return None;
+        };
+
+        let base: Duration = match base.split_once(':') {
+            Some((minutes, seconds)) => Duration::from_secs(minutes.parse::<u64>().ok()? * 60 + seconds.parse::<u64>().ok()?),
+            None => Duration::from_secs(base.parse::<u64>().ok()? * 60),
+        };
+        let increment: Duration = Duration::try_from_secs_f64(increment.parse().ok()?).ok()?;
+
+        Some((moves.parse().ok()?, base, increment))
+    }
+
+    /// Converts the time control to the limits of the next search.
+    ///
+    /// `clock` and `opponent_clock` are the time left on the clocks as reported by "time" and "otim",
+    /// without them the base time is used. `fullmove` is the number of the move the engine is about to play.
+    pub fn limits(&self, white_to_move: bool, clock: Option<Duration>, opponent_clock: Option<Duration>, fullmove: u32, move_overhead: Duration) -> SearchLimits {
+        if let Some(per_move) = self.per_move {
+            return SearchLimits { movetime: Some(per_move), depth: self.depth, move_overhead, ..SearchLimits::default() };
+        }
+
+        let movestogo: Option<u32> = match self.moves_per_session {
+            0 => None,
+            moves => Some(moves - (fullmove.max(1) - 1) % moves),
+        };
+        let (clock, opponent_clock): (Duration, Duration) = (clock.unwrap_or(self.base), opponent_clock.unwrap_or(self.base));
+        let (wtime, btime): (Duration, Duration) = if white_to_move { (clock, opponent_clock) } else { (opponent_clock, clock) };
+
+        SearchLimits {
+            depth: self.depth,
+            wtime: Some(wtime),
+            btime: Some(btime),
+            winc: self.increment,
+            binc: self.increment,
+            movestogo,
+            move_overhead,
+            ..SearchLimits::default()
+        }
+    }
+}
+
+/// Formats the result of a finished game as xboard expects it, e.g. "1-0 {white wins by checkmate}".
+fn result_line(result: GameResult) -> String {
+    format!("{} {{{}}}", result.pgn(), result)
+}
+
+/// Formats the thinking output sent after every completed depth when "post" is on:
+/// the depth, the score in centipawns or as a mate, the time in centiseconds, the nodes and the principal variation in SAN.
+pub fn thinking_line(fen: &Fen, info: &SearchInfo, time: Duration) -> String {
+    let mut fen: Fen = fen.clone();
+    let mut pv: Vec<String> = Vec::new();
+    for &move1 in &info.pv {
+        if move1.is_null() {
+            break;
+        }
+
+        pv.push(fen.move_to_san(&move1));
+        fen.make_move(move1);
+    }
+
+    // xboard shows a score of 100000 + n as a mate in n moves
+    let score: i32 = match info.mate() {
+        Some(moves) => moves.signum() * (100_000 + moves.abs()),
+        None => info.score,
+    };
+
+    format!("{} {} {} {} {}", info.depth, score, time.as_millis() / 10, info.nodes, pv.join(" "))
+}
+
+/// A CECP (xboard/WinBoard) front end for a bot, it reads commands line by line and writes its replies to the output.
+///
+/// Unlike UCI the engine keeps track of the game itself: the moves it plays are added to the game as well.
+pub struct Xboard {
+    engine: Engine,
+    output: Output,
+
+    /// The color the engine plays, `None` in force mode.
+    engine_color: Option<Color>,
+    time_control: TimeControl,
+
+    /// The time left on the clocks of the engine and the opponent, as reported by "time" and "otim".
+    clock: Option<Duration>,
+    opponent_clock: Option<Duration>,
+    post: bool,
+
+    /// The move the last search played, it is added to the game before the next command is handled.
+    played: Arc<Mutex<Option<Move>>>,
+
+    /// Set when the running search has to be abandoned without playing its move.
+    cancelled: Arc<AtomicBool>,
+}
+
+impl Xboard {
+    pub fn new(bot: Box<dyn Bot>, output: Output) -> Self {
+        Self {
+            engine: Engine::new(bot),
+            output,
+            engine_color: Some(Color::Black),
+            time_control: TimeControl::default(),
+            clock: None,
+            opponent_clock: None,
+            post: false,
+            played: Arc::new(Mutex::new(None)),
+            cancelled: Arc::new(AtomicBool::new(false)),
+        }
+    }
+
+    /// Handles a single command, returns false if the engine has to quit.
+    pub fn handle(&mut self, line: &str) -> bool {
+        self.sync();
+
+        let words: Vec<&str> = line.split_whitespace().collect();
+        let Some((&command, args)) = words.split_first() else {
+            return true;
+        };
+
+        match command {
+            "xboard" | "accepted" | "rejected" | "random" | "hard" | "easy" | "computer" | "name" | "rating" | "ics" => {},
+            "post" => self.post = true,
+            "nopost" => self.post = false,
+            "protover" => {
+                send(&self.output, &format!("feature myname=\"{}\" ping=1 setboard=1 usermove=1 san=0 time=1 draw=0 sigint=0 sigterm=0 reuse=1 analyze=0 colors=0", self.engine.name()));
+                for option in self.engine.options() {
+                    send(&self.output, &format!("feature option=\"{} -spin {} {} {}\"", option.name, option.default, option.min, option.max));
+                }
+                send(&self.output, "feature done=1");
+            },
+            "option" => self.set_option(&args.join(" ")),
+            "ping" => send(&self.output, &format!("pong {}", args.join(" "))),
+            "new" => {
+                self.abort();
+                self.engine.new_game();
+                self.engine_color = Some(Color::Black);
+                self.time_control.depth = None;
+                self.clock = None;
+                self.opponent_clock = None;
+            },
+            "setboard" => {
+                self.abort();
+                match args.join(" ").parse::<Fen>() {
+                    Ok(fen) => self.engine.set_game(Game::from_fen(fen)),
+                    Err(err) => send(&self.output, &format!("tellusererror Illegal position: {}", err)),
+                }
+            },
+            "force" | "result" => {
+                self.abort();
+                self.engine_color = None;
+            },
+            "go" => {
+                self.abort();
+                self.engine_color = Some(self.engine.game().fen().side_to_move());
+                self.think();
+            },
+            "?" => self.engine.stop(),
+            "usermove" => self.user_move(args.first().copied().unwrap_or_default()),
+            "undo" => {
+                self.abort();
+                self.engine.undo();
+            },
+            "remove" => {
+                self.abort();
+                self.engine.undo();
+                self.engine.undo();
+            },
+            "level" => match TimeControl::parse_level(args) {
+                Some((moves_per_session, base, increment)) => {
+                    self.time_control = TimeControl { moves_per_session, base, increment, per_move: None, ..self.time_control };
+                },
+                None => send(&self.output, &format!("Error (invalid time control): {}", line)),
+            },
+            "st" => match args.first().and_then(|seconds: &&str| seconds.parse::<f64>().ok()).and_then(|seconds: f64| Duration::try_from_secs_f64(seconds).ok()) {
+                Some(per_move) => self.time_control.per_move = Some(per_move),
+                None => send(&self.output, &format!("Error (invalid time): {}", line)),
+            },
+            "sd" => match args.first().and_then(|depth: &&str| depth.parse::<u32>().ok()) {
+                Some(depth) => self.time_control.depth = Some(depth.max(1)),
+                None => send(&self.output, &format!("Error (invalid depth): {}", line)),
+            },
+            "time" => self.clock = parse_centiseconds(args).or(self.clock),
+            "otim" => self.opponent_clock = parse_centiseconds(args).or(self.opponent_clock),
+            "quit" => {
+                self.abort();
+                return false;
+            },
+            // interfaces that did not accept usermove=1 send moves as they are
+            _ if self.engine.game().fen().parse_lan(command).is_ok() => self.user_move(command),
+            _ => send(&self.output, &format!("Error (unknown command): {}", command)),
+        }
+
+        true
+    }
+
+    /// Waits for a running search to play its move.
+    pub fn finish(&mut self) {
+        self.engine.finish();
+        self.sync();
+    }
+
+    /// Handles "option NAME=VALUE" for the options sent with the features.
+    fn set_option(&mut self, option: &str) {
+        let Some((name, value)) = option.split_once('=') else {
+            send(&self.output, &format!("Error (invalid option): {}", option));
+            return;
+        };
+
+        let result: Result<(), String> = match value.trim().parse::<i64>() {
+            Ok(value) => self.engine.set_option(name.trim(), value).map_err(|err| err.to_string()),
+            Err(_) => Err(format!("invalid value '{}' for {}", value.trim(), name.trim())),
+        };
+
+        if let Err(message) = result {
+            send(&self.output, &format!("tellusererror {}", message));
+        }
+    }
+
+    /// Adds the move played by the last search to the game.
+    fn sync(&mut self) {
+        let played: Option<Move> = self.played.lock().unwrap_or_else(PoisonError::into_inner).take();
+        if let Some(move1) = played {
+            self.engine.make_move(move1);
+        }
+    }
+
+    /// Stops a running search without playing its move.
+    fn abort(&mut self) {
+        self.cancelled.store(true, Ordering::Relaxed);
+        self.engine.stop();
+    }
+
+    /// Plays the opponent's move and starts thinking if it is the engine's turn.
+    fn user_move(&mut self, lan: &str) {
+        self.abort();
+
+        let fen: &Fen = self.engine.game().fen();
+        if !fen.is_legal_move_lan(lan) {
+            send(&self.output, &format!("Illegal move: {}", lan));
+            return;
+        }
+
+        // a legal move can always be parsed
+        let move1: Move = fen.parse_lan(lan).unwrap_or(Move::NULL);
+        self.engine.make_move(move1);
+
+        if self.engine_color == Some(self.engine.game().fen().side_to_move()) && self.engine.game().result().is_ongoing() {
+            self.think();
+        }
+    }
+
+    /// Starts searching for the engine's move, the move is sent by the search thread when it is done.
+    fn think(&mut self) {
+        let game: Game = self.engine.game().clone();
+        if !game.result().is_ongoing() {
+            send(&self.output, &result_line(game.result()));
+            return;
+        }
+
+        let limits: SearchLimits = self.time_control.limits(
+            game.fen().white_to_move,
+            self.clock,
+            self.opponent_clock,
+            game.fen().fullmove.into(),
+            Duration::from_millis(MOVE_OVERHEAD),
+        );
+
+        self.cancelled = Arc::new(AtomicBool::new(false));
+        let cancelled: Arc<AtomicBool> = Arc::clone(&self.cancelled);
+        let played: Arc<Mutex<Option<Move>>> = Arc::clone(&self.played);
+
+        let post: bool = self.post;
+        let fen: Fen = game.fen().clone();
+        let info_output: Output = self.output.clone();
+        let done_output: Output = self.output.clone();
+
+        self.engine.go(
+            limits,
+            move |info: &SearchInfo, time: Duration| {
+                if post {
+                    send(&info_output, &thinking_line(&fen, info, time));
+                }
+            },
+            move |best_move: Move| {
+                if cancelled.load(Ordering::Relaxed) || best_move.is_null() {
+                    return;
+                }
+
+                // the move is stored before it is sent, so it is part of the game when the opponent replies
+                *played.lock().unwrap_or_else(PoisonError::into_inner) = Some(best_move);
+                send(&done_output, &format!("move {}", best_move));
+
+                let mut game: Game = game;
+                game.make_move(best_move);
+                if !game.result().is_ongoing() {
+                    send(&done_output, &result_line(game.result()));
+                }
+            },
+        );
+    }
+}
+
+/// Parses the centiseconds of "time" and "otim", a negative time means the clock has run out.
+fn parse_centiseconds(args: &[&str]) -> Option<Duration> {
+    let centis: i64 = args.first()?.parse().ok()?;
+    Some(Duration::from_millis(centis.max(0) as u64 * 10))
+}
+
+/// Runs an xboard session until "quit" or the end of the input, at the end of the input a running search is finished first.
+pub fn run<R: BufRead>(bot: Box<dyn Bot>, input: R, output: Output) {
+    let mut xboard: Xboard = Xboard::new(bot, output);
+
+    for line in input.lines() {
+        let Ok(line) = line else {
+            break;
+        };
+
+        if !xboard.handle(&line) {
+            return;
+        }
+    }
+
+    xboard.finish();
+}
@@ -0,0 +1,136 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::thread;
+use std::time::Duration;
+
+use fenlib::Fen;
+use fenlib::bot::{Bot, BotOption, OptionError, SearchInfo, SearchLimits, SearchResult};
+use fenlib::chess_move::Move;
+use bot_registry::BOTS;
+use protocols::{Output, uci, xboard};
+
+fn every_bot() -> Vec<Box<dyn Bot>> {
+    BOTS.iter().map(|&name: &&str| bot_registry::bot_by_name(name).unwrap()).collect()
+}
+
+#[test]
+fn bots_by_name() {
+    let names: Vec<String> = every_bot().into_iter().map(|bot: Box<dyn Bot>| bot.name().to_string()).collect();
+    assert_eq!(names, vec!["BotV1", "BotV2"]);
+
+    assert!(bot_registry::bot_by_name("BOTV2").is_some());
+    assert!(bot_registry::bot_by_name("stockfish").is_none());
+}
+
+#[test]
+fn every_bot_finds_mate_in_one() {
+    let fen: Fen = "6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1".parse().unwrap();
+    let limits: SearchLimits = SearchLimits::depth(2);
+
+    for mut bot in every_bot() {
+        bot.set_position(fen.clone());
+
+        let mut depths: Vec<u32> = Vec::new();
+        let result: SearchResult = bot.choose_move(&limits, &mut |info: &SearchInfo| depths.push(info.depth));
+
+        assert_eq!(result.best_move.to_string(), "a1a8", "{}", bot.name());
+        assert_eq!(result.info.as_ref().map(|info: &SearchInfo| info.depth), depths.last().copied(), "{}", bot.name());
+        assert_eq!(result.info.unwrap().pv[0], result.best_move, "{}", bot.name());
+    }
+}
+
+#[test]
+fn every_bot_follows_applied_moves() {
+    for mut bot in every_bot() {
+        let mut fen: Fen = Fen::new();
+        bot.set_position(fen.clone());
+
+        for lan in ["f2f3", "e7e5", "g2g4"] {
+            let move1: Move = fen.parse_lan(lan).unwrap();
+            bot.apply_move(move1);
+            fen.make_move(move1);
+        }
+
+        let result: SearchResult = bot.choose_move(&SearchLimits::depth(2), &mut |_: &SearchInfo| {});
+        assert_eq!(result.best_move.to_string(), "d8h4", "{}", bot.name());
+    }
+}
+
+#[test]
+fn every_bot_reports_no_move_without_legal_moves() {
+    let fen: Fen = "7k/5QQ1/8/8/8/8/8/K7 b - - 0 1".parse().unwrap();
+
+    for mut bot in every_bot() {
+        bot.set_position(fen.clone());
+
+        let result: SearchResult = bot.choose_move(&SearchLimits::depth(2), &mut |_: &SearchInfo| {});
+        assert!(result.best_move.is_null(), "{}", bot.name());
+        assert_eq!(result.info, None, "{}", bot.name());
+    }
+}
+
+#[test]
+fn stopping_an_infinite_search() {
+    let mut bot: Box<dyn Bot> = bot_registry::bot_by_name("botv1").unwrap();
+    let stop = bot.stop_signal();
+
+    let search = thread::spawn(move || bot.choose_move(&SearchLimits { infinite: true, ..SearchLimits::default() }, &mut |_: &SearchInfo| {}));
+    thread::sleep(Duration::from_millis(50));
+    stop.store(true, std::sync::atomic::Ordering::Relaxed);
+
+    let result: SearchResult = search.join().unwrap();
+    assert!(Fen::new().is_legal_move(&result.best_move));
+}
+
+#[test]
+fn options() {
+    let mut botv1: Box<dyn Bot> = bot_registry::bot_by_name("botv1").unwrap();
+    let options: Vec<BotOption> = botv1.options();
+    assert_eq!(options[0], BotOption { name: "Hash", default: 16, min: 1, max: 1024 });
+    assert_eq!(options[1], BotOption { name: "NullMove", default: 1, min: 0, max: 1 });
+    assert_eq!(options.len(), 8);
+    assert_eq!(botv1.set_option("hash", 1), Ok(()));
+    assert_eq!(botv1.set_option("Hash", 0), Err(OptionError::BadValue("Hash".to_string(), 0)));
+    assert_eq!(botv1.set_option("lmr", 0), Ok(()));
+    assert_eq!(botv1.set_option("LMR", 2), Err(OptionError::BadValue("LMR".to_string(), 2)));
+    assert_eq!(botv1.set_option("Depth", 3), Err(OptionError::Unknown("Depth".to_string())));
+
+    let mut botv2: Box<dyn Bot> = bot_registry::bot_by_name("botv2").unwrap();
+    assert_eq!(botv2.options(), vec![BotOption { name: "Depth", default: 4, min: 1, max: 8 }]);
+    assert_eq!(botv2.set_option("depth", 2), Ok(()));
+    assert_eq!(botv2.set_option("Depth", 9), Err(OptionError::BadValue("Depth".to_string(), 9)));
+
+    // the option is used when the limits do not give a depth
+    let result: SearchResult = botv2.choose_move(&SearchLimits::default(), &mut |_: &SearchInfo| {});
+    assert_eq!(result.info.unwrap().depth, 2);
+}
+
+/// Runs a scripted session with BotV2 and returns everything the engine wrote.
+fn session(run: fn(Box<dyn Bot>, Cursor<String>, Output), script: &str) -> String {
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    run(bot_registry::bot_by_name("botv2").unwrap(), Cursor::new(script.to_string()), buffer.clone());
+
+    let bytes: Vec<u8> = buffer.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+#[test]
+fn options_over_uci() {
+    let output: String = session(uci::run, "uci\nsetoption name Depth value 2\nsetoption name Depth value 20\nsetoption name Depth value two\nposition startpos\ngo movetime 5000\n");
+
+    assert!(output.starts_with("id name BotV2\n"), "{}", output);
+    assert!(output.contains("option name Depth type spin default 4 min 1 max 8\n"), "{}", output);
+    assert!(output.contains("info string value 20 is out of range for option 'Depth'"), "{}", output);
+    assert!(output.contains("info string invalid value 'two' for Depth"), "{}", output);
+    assert!(output.contains("info depth 2 "), "{}", output);
+}
+
+#[test]
+fn options_over_xboard() {
+    let output: String = session(xboard::run, "xboard\nprotover 2\noption Depth=2\noption Depth=0\nnew\npost\nusermove e2e4\n");
+
+    assert!(output.contains("feature myname=\"BotV2\""), "{}", output);
+    assert!(output.contains("feature option=\"Depth -spin 4 1 8\"\nfeature done=1\n"), "{}", output);
+    assert!(output.contains("tellusererror value 0 is out of range for option 'Depth'"), "{}", output);
+    assert!(output.lines().any(|line: &str| line.starts_with("2 ")), "{}", output);
+}
@@ -0,0 +1,144 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use botv1::BotV1;
+use fenlib::Fen;
+use fenlib::bot::SearchLimits;
+use protocols::Output;
+use protocols::uci::{self, Uci};
+
+/// Runs a scripted session and returns everything the engine wrote.
+fn session(script: &str) -> String {
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let output: Output = buffer.clone();
+    uci::run(Box::new(BotV1::new()), Cursor::new(script.to_string()), output);
+
+    let bytes: Vec<u8> = buffer.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+fn best_move(output: &str) -> &str {
+    let line: &str = output.lines().find(|line: &&str| line.starts_with("bestmove ")).unwrap_or_else(|| panic!("no bestmove in:\n{}", output));
+    line.split_whitespace().nth(1).unwrap()
+}
+
+#[test]
+fn handshake() {
+    let output: String = session("uci\nisready\nquit\n");
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert_eq!(lines[0], "id name BotV1");
+    assert!(lines.contains(&"uciok"));
+    assert_eq!(lines.last(), Some(&"readyok"));
+}
+
+#[test]
+fn go_depth() {
+    let output: String = session("ucinewgame\nposition startpos moves e2e4 e7e5\ngo depth 2\n");
+
+    assert!(output.contains("info depth 1 score cp "), "{}", output);
+    assert!(output.contains("info depth 2 score cp "), "{}", output);
+    assert!(!output.contains("info depth 3"), "{}", output);
+
+    let mut fen: Fen = Fen::new();
+    fen.lan_to_fen("e2e4").unwrap();
+    fen.lan_to_fen("e7e5").unwrap();
+    assert!(fen.is_legal_move_lan(best_move(&output)), "{}", output);
+}
+
+#[test]
+fn hash_table() {
+    let output: String = session("uci\nsetoption name Hash value 1\nposition startpos\ngo depth 3\nucinewgame\nposition startpos\ngo depth 3\n");
+
+    assert!(output.contains("option name Hash type spin default 16 min 1 max 1024\n"), "{}", output);
+    assert!(output.contains("option name NullMove type spin default 1 min 0 max 1\n"), "{}", output);
+    assert!(output.lines().any(|line: &str| line.starts_with("info depth 3 ") && line.contains(" hashfull ")), "{}", output);
+    assert_eq!(output.matches("bestmove ").count(), 2, "{}", output);
+}
+
+#[test]
+fn finds_mate_in_one() {
+    let output: String = session("position fen 6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1\ngo depth 2\n");
+    assert_eq!(best_move(&output), "a1a8");
+    assert!(output.contains("info depth 2 score mate 1 "), "{}", output);
+
+    let output: String = session("position fen k7/8/1K6/8/8/8/8/7R b - - 0 1\ngo depth 2\n");
+    assert!(output.contains("info depth 2 score mate -1 "), "{}", output);
+}
+
+#[test]
+fn go_movetime() {
+    let start: Instant = Instant::now();
+    let output: String = session("position startpos\ngo movetime 100\n");
+
+    assert!(Fen::new().is_legal_move_lan(best_move(&output)));
+    assert!(start.elapsed() < Duration::from_secs(2), "took {:?}", start.elapsed());
+}
+
+#[test]
+fn go_nodes() {
+    let output: String = session("position startpos\ngo nodes 100\n");
+    assert!(Fen::new().is_legal_move_lan(best_move(&output)));
+}
+
+#[test]
+fn stop_infinite_search() {
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let mut uci: Uci = Uci::new(Box::new(BotV1::new()), buffer.clone());
+
+    uci.handle("position startpos");
+    uci.handle("go infinite");
+    std::thread::sleep(Duration::from_millis(50));
+    assert!(!String::from_utf8(buffer.lock().unwrap().clone()).unwrap().contains("bestmove"));
+
+    uci.handle("stop");
+    let output: String = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+    assert!(Fen::new().is_legal_move_lan(best_move(&output)), "{}", output);
+}
+
+#[test]
+fn no_legal_moves() {
+    let output: String = session("position fen 7k/5QQ1/8/8/8/8/8/K7 b - - 0 1\ngo depth 3\n");
+    assert_eq!(best_move(&output), "0000");
+}
+
+#[test]
+fn bad_input() {
+    let output: String = session("position startpos moves e2e4 e2e4\nposition fen nonsense\nsetoption name Threads value 2\nfoo\nisready\n");
+
+    assert!(output.contains("info string illegal move 'e2e4'"), "{}", output);
+    assert!(output.contains("info string"), "{}", output);
+    assert!(output.contains("info string unknown option 'Threads'"), "{}", output);
+    assert!(output.contains("info string unknown command 'foo'"), "{}", output);
+    assert!(output.ends_with("readyok\n"), "{}", output);
+}
+
+#[test]
+fn parse_go() {
+    let overhead: Duration = Duration::from_millis(10);
+
+    let limits: SearchLimits = uci::parse_go(&["wtime", "60000", "btime", "-50", "winc", "1000", "movestogo", "20", "depth", "abc"], overhead);
+    assert_eq!(limits.wtime, Some(Duration::from_millis(60000)));
+    assert_eq!(limits.btime, Some(Duration::ZERO));
+    assert_eq!(limits.winc, Duration::from_millis(1000));
+    assert_eq!(limits.binc, Duration::ZERO);
+    assert_eq!(limits.movestogo, Some(20));
+    assert_eq!(limits.depth, None);
+    assert_eq!(limits.move_overhead, overhead);
+    assert!(!limits.infinite);
+
+    assert!(uci::parse_go(&[], overhead).infinite);
+    assert!(uci::parse_go(&["infinite"], overhead).infinite);
+    assert_eq!(uci::parse_go(&["movetime", "500"], overhead).movetime, Some(Duration::from_millis(500)));
+    assert_eq!(uci::parse_go(&["nodes", "1000"], overhead).nodes, Some(1000));
+}
+
+#[test]
+fn go_with_clock() {
+    let start: Instant = Instant::now();
+    let output: String = session("position startpos\ngo wtime 3000 btime 3000\n");
+
+    assert!(Fen::new().is_legal_move_lan(best_move(&output)));
+    assert!(start.elapsed() < Duration::from_millis(1000), "took {:?}", start.elapsed());
+}
@@ -0,0 +1,177 @@
+use std::io::Cursor;
+use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+
+use botv1::BotV1;
+use fenlib::Fen;
+use fenlib::bot::{MATE_SCORE, SearchInfo, SearchLimits};
+use protocols::Output;
+use protocols::xboard::{self, TimeControl, Xboard};
+
+/// Runs a scripted session and returns everything the engine wrote.
+fn session(script: &str) -> String {
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let output: Output = buffer.clone();
+    xboard::run(Box::new(BotV1::new()), Cursor::new(script.to_string()), output);
+
+    let bytes: Vec<u8> = buffer.lock().unwrap().clone();
+    String::from_utf8(bytes).unwrap()
+}
+
+/// Returns the moves the engine played, in order.
+fn engine_moves(output: &str) -> Vec<&str> {
+    output.lines().filter_map(|line: &str| line.strip_prefix("move ")).collect()
+}
+
+/// Waits until the engine has played `count` moves in total.
+fn wait_for_moves(buffer: &Arc<Mutex<Vec<u8>>>, count: usize) -> String {
+    let start: Instant = Instant::now();
+    loop {
+        let output: String = String::from_utf8(buffer.lock().unwrap().clone()).unwrap();
+        if engine_moves(&output).len() >= count {
+            return output;
+        }
+
+        assert!(start.elapsed() < Duration::from_secs(10), "the engine did not move:\n{}", output);
+        std::thread::sleep(Duration::from_millis(5));
+    }
+}
+
+#[test]
+fn feature_negotiation() {
+    let output: String = session("xboard\nprotover 2\nping 7\n");
+    let lines: Vec<&str> = output.lines().collect();
+
+    assert!(lines[0].starts_with("feature "), "{}", output);
+    assert!(lines[0].contains("usermove=1") && lines[0].contains("setboard=1"), "{}", output);
+    assert!(lines.contains(&"feature done=1"), "{}", output);
+    assert_eq!(lines.last(), Some(&"pong 7"));
+}
+
+#[test]
+fn replies_as_black() {
+    let output: String = session("xboard\nnew\nsd 2\nusermove e2e4\n");
+    let moves: Vec<&str> = engine_moves(&output);
+    assert_eq!(moves.len(), 1, "{}", output);
+
+    let mut fen: Fen = Fen::new();
+    fen.lan_to_fen("e2e4").unwrap();
+    assert!(fen.is_legal_move_lan(moves[0]), "{}", output);
+}
+
+#[test]
+fn force_mode_and_go() {
+    let output: String = session("new\nforce\nsd 2\nusermove e2e4\ne7e5\ngo\n");
+    let moves: Vec<&str> = engine_moves(&output);
+    assert_eq!(moves.len(), 1, "{}", output);
+
+    let mut fen: Fen = Fen::new();
+    fen.lan_to_fen("e2e4").unwrap();
+    fen.lan_to_fen("e7e5").unwrap();
+    assert!(fen.is_legal_move_lan(moves[0]), "{}", output);
+}
+
+#[test]
+fn keeps_track_of_its_own_moves() {
+    let buffer: Arc<Mutex<Vec<u8>>> = Arc::new(Mutex::new(Vec::new()));
+    let mut xboard: Xboard = Xboard::new(Box::new(BotV1::new()), buffer.clone());
+
+    xboard.handle("new");
+    xboard.handle("sd 1");
+    xboard.handle("go");
+    let output: String = wait_for_moves(&buffer, 1);
+
+    // reply with any legal move, it is only legal if the engine's move was added to the game
+    let mut fen: Fen = Fen::new();
+    fen.lan_to_fen(engine_moves(&output)[0]).unwrap();
+    let reply: String = fen.legal_moves()[0].to_string();
+    fen.lan_to_fen(&reply).unwrap();
+
+    xboard.handle(&format!("usermove {}", reply));
+    let output: String = wait_for_moves(&buffer, 2);
+    assert!(!output.contains("Illegal move"), "{}", output);
+    assert!(fen.is_legal_move_lan(engine_moves(&output)[1]), "{}", output);
+}
+
+#[test]
+fn mates_and_reports_the_result() {
+    let output: String = session("new\nforce\nsetboard 6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1\nsd 2\ngo\n");
+
+    assert_eq!(engine_moves(&output), vec!["a1a8"]);
+    assert!(output.contains("1-0 {white wins by checkmate}"), "{}", output);
+}
+
+#[test]
+fn thinking_output() {
+    let output: String = session("new\npost\nsd 2\nusermove e2e4\n");
+    let thinking: Vec<&str> = output.lines().filter(|line: &&str| !line.starts_with("move ")).collect();
+
+    assert_eq!(thinking.len(), 2, "{}", output);
+    let fields: Vec<&str> = thinking[1].split_whitespace().collect();
+    assert_eq!(fields[0], "2");
+    assert!(fields[1].parse::<i32>().is_ok() && fields[2].parse::<u64>().is_ok() && fields[3].parse::<u64>().is_ok(), "{}", output);
+    assert!(fields.len() >= 5, "{}", output);
+
+    assert!(!session("new\nnopost\nsd 2\nusermove e2e4\n").lines().any(|line: &str| line.starts_with("1 ")));
+}
+
+#[test]
+fn thinking_output_for_a_mate() {
+    let output: String = session("new\nforce\nsetboard 6k1/5ppp/8/8/8/8/5PPP/R5K1 w - - 0 1\npost\nsd 2\ngo\n");
+    assert!(output.contains("\n2 100001 "), "{}", output);
+
+    let info: SearchInfo = SearchInfo { depth: 2, score: -(MATE_SCORE - 2), nodes: 10, pv: Vec::new(), hashfull: None };
+    assert!(xboard::thinking_line(&Fen::new(), &info, Duration::ZERO).starts_with("2 -100001 0 10"));
+}
+
+#[test]
+fn undo_and_remove() {
+    let output: String = session("new\nforce\nusermove e2e4\nusermove e7e5\nremove\nusermove e2e4\nundo\nusermove e7e5\n");
+    let errors: Vec<&str> = output.lines().collect();
+
+    assert_eq!(errors, vec!["Illegal move: e7e5"]);
+}
+
+#[test]
+fn bad_input() {
+    let output: String = session("new\nusermove e2e5\nsetboard nonsense\nlevel 40\nfoo\n");
+
+    assert!(output.contains("Illegal move: e2e5"), "{}", output);
+    assert!(output.contains("tellusererror Illegal position"), "{}", output);
+    assert!(output.contains("Error (invalid time control): level 40"), "{}", output);
+    assert!(output.contains("Error (unknown command): foo"), "{}", output);
+    assert!(engine_moves(&output).is_empty(), "{}", output);
+}
+
+#[test]
+fn result_stops_the_engine() {
+    let output: String = session("new\nresult 1-0 {White resigns}\nusermove e2e4\n");
+    assert!(engine_moves(&output).is_empty(), "{}", output);
+}
+
+#[test]
+fn time_control() {
+    assert_eq!(TimeControl::parse_level(&["40", "5", "0"]), Some((40, Duration::from_secs(300), Duration::ZERO)));
+    assert_eq!(TimeControl::parse_level(&["0", "2:30", "1.5"]), Some((0, Duration::from_secs(150), Duration::from_millis(1500))));
+    assert_eq!(TimeControl::parse_level(&["40", "five", "0"]), None);
+
+    let overhead: Duration = Duration::from_millis(10);
+    let level: TimeControl = TimeControl { moves_per_session: 40, base: Duration::from_secs(300), increment: Duration::from_secs(2), ..TimeControl::default() };
+
+    // the clocks are given from the view of the engine, the limits per color
+    let black: SearchLimits = level.limits(false, Some(Duration::from_secs(10)), None, 40, overhead);
+    assert_eq!((black.wtime, black.btime), (Some(Duration::from_secs(300)), Some(Duration::from_secs(10))));
+    assert_eq!((black.winc, black.binc), (Duration::from_secs(2), Duration::from_secs(2)));
+    assert_eq!(black.movestogo, Some(1));
+    assert_eq!(black.move_overhead, overhead);
+
+    let white: SearchLimits = level.limits(true, Some(Duration::from_secs(10)), Some(Duration::from_secs(20)), 41, overhead);
+    assert_eq!((white.wtime, white.btime), (Some(Duration::from_secs(10)), Some(Duration::from_secs(20))));
+    assert_eq!(white.movestogo, Some(40));
+
+    let st: TimeControl = TimeControl { per_move: Some(Duration::from_secs(1)), depth: Some(3), ..TimeControl::default() };
+    let limits: SearchLimits = st.limits(true, None, None, 1, overhead);
+    assert_eq!(limits.movetime, Some(Duration::from_secs(1)));
+    assert_eq!(limits.depth, Some(3));
+    assert_eq!(limits.wtime, None);
+}
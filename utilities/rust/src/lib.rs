@@ -1,13 +1,35 @@
 use pyo3::prelude::*;
+use pyo3::exceptions::PyValueError;
 use fenlib::Fen;
-use botv1::BotV1;
+use fenlib::chess_move::Move;
+use fenlib::error::FenError;
+use fenlib::game::Game;
+use fenlib::pgn::{PgnError, PgnGame};
+use fenlib::result::{GameResult, Outcome, Reason};
+use fenlib::bot::{Bot, BotOption, OptionError, SearchInfo, SearchLimits, SearchResult};
+use std::time::Duration;
 
-/// A Python-exposed wrapper for the `Fen` struct from fenlib, representing a chess position.
+/// The time a bot searches when it is asked for a move without any limit, in milliseconds.
+const DEFAULT_MOVE_TIME_MS: u64 = 1000;
+
+/// Converts a parsing error from fenlib into a Python `ValueError`.
+fn to_py_err(err: FenError) -> PyErr {
+    PyValueError::new_err(err.to_string())
+}
+
+/// A Python-exposed wrapper for the `Game` struct from fenlib, representing a chess position
+/// together with the moves that led to it.
 /// Code by David van den Beukel, documentation by ChatGPT.
 #[pyclass]
 #[derive(Debug, Clone)]
 pub struct FenPy {
-    fen: Fen,
+    game: Game,
+}
+
+impl Default for FenPy {
+    fn default() -> Self {
+        Self::new()
+    }
 }
 
 #[pymethods]
@@ -15,30 +37,78 @@ impl FenPy {
     /// Creates a new empty (default) FEN position.
     #[new]
     pub fn new() -> Self {
-        let fen: Fen = Fen::new();
-        Self { fen }
+        let game: Game = Game::new();
+        Self { game }
     }
 
     /// Constructs a `FenPy` object from a FEN string.
     ///
     /// # Arguments
     /// * `fen_str` - A valid FEN string representing the chess position.
+    ///
+    /// Raises a `ValueError` if the FEN string is malformed.
+    #[staticmethod]
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(fen_str: &str) -> PyResult<Self> {
+        let fen: Fen = fen_str.parse().map_err(to_py_err)?;
+        Ok(Self { game: Game::from_fen(fen) })
+    }
+
+    /// Constructs a `FenPy` object from a FEN string, rejecting impossible positions.
+    ///
+    /// # Arguments
+    /// * `fen_str` - A valid FEN string representing a position that can occur in a legal game.
+    ///
+    /// Raises a `ValueError` if the FEN string is malformed or the position is invalid.
     #[staticmethod]
-    pub fn from_str(fen_str: &str) -> Self {
-        let fen: Fen = Fen::from_str(fen_str);
-        Self { fen }
+    pub fn from_str_strict(fen_str: &str) -> PyResult<Self> {
+        let fen: Fen = Fen::from_str_strict(fen_str).map_err(to_py_err)?;
+        Ok(Self { game: Game::from_fen(fen) })
+    }
+
+    /// Constructs a `FenPy` object from the main line of the first game in a PGN text.
+    ///
+    /// Raises a `ValueError` if the PGN is malformed, contains an illegal move or no game.
+    #[staticmethod]
+    pub fn from_pgn(pgn: &str) -> PyResult<Self> {
+        let games: Vec<PgnGame> = fenlib::pgn::read_pgn(pgn).map_err(|err: PgnError| PyValueError::new_err(err.to_string()))?;
+        match games.first() {
+            Some(game) => Ok(Self { game: game.to_game() }),
+            None => Err(PyValueError::new_err("no game found in pgn")),
+        }
+    }
+
+    /// Returns the moves played so far as a PGN game.
+    ///
+    /// # Arguments
+    /// * `tags` - Optional list of (name, value) pairs, e.g. [("White", "BotV1")].
+    #[pyo3(signature = (tags=None))]
+    pub fn to_pgn(&self, tags: Option<Vec<(String, String)>>) -> String {
+        let mut pgn: PgnGame = PgnGame::from_game(&self.game);
+        for (name, value) in tags.unwrap_or_default() {
+            pgn.set_tag(&name, &value);
+        }
+        pgn.to_string()
+    }
+
+    /// Returns a description of every reason the position cannot occur in a legal game.
+    ///
+    /// An empty list means the position is valid.
+    pub fn validate(&self) -> Vec<String> {
+        self.game.fen().validate().iter().map(|violation| violation.to_string()).collect()
     }
 
     /// Returns the current FEN string representation of the position.
+    #[allow(clippy::inherent_to_string)]
     pub fn to_string(&self) -> String {
-        self.fen.to_string()
+        self.game.fen().to_string()
     }
 
     /// Returns a visual 8x8 representation of the board as strings.
     ///
     /// Each element is a piece symbol or empty string.
     pub fn to_visual(&self) -> [[String; 8]; 8] {
-        self.fen.to_visual()
+        self.game.fen().to_visual()
     }
 
     /// Checks if a given move in LAN (long algebraic notation) is legal.
@@ -48,81 +118,353 @@ impl FenPy {
     ///
     /// This may temporarily mutate internal state for checking legality.
     pub fn is_legal_move_lan(&mut self, lan: &str) -> bool {
-        self.fen.is_legal_move_lan(lan)
+        self.game.fen().is_legal_move_lan(lan)
     }
 
     /// Returns a list of legal moves for the piece on the given tile.
     ///
     /// # Arguments
     /// * `tile` - A square in algebraic notation, e.g., "e2".
-    pub fn get_possible_moves_tile(&mut self, tile: &str) -> Vec<String> {
-        self.fen.get_possible_moves_tile(tile)
+    ///
+    /// Raises a `ValueError` if the tile is malformed.
+    pub fn get_possible_moves_tile(&mut self, tile: &str) -> PyResult<Vec<String>> {
+        self.game.fen().get_possible_moves_tile(tile).map_err(to_py_err)
     }
 
     /// Returns true if the current player is in check.
     ///
     /// This may mutate internal state during the computation.
     pub fn in_check(&mut self) -> bool {
-        self.fen.in_check()
+        self.game.fen().in_check()
     }
 
     /// Makes a move (in LAN format) and updates the FEN state accordingly.
     ///
     /// # Arguments
     /// * `lan` - The move string to apply.
-    pub fn lan_to_fen(&mut self, lan: &str) {
-        self.fen.lan_to_fen(lan);
+    ///
+    /// Raises a `ValueError` if the move is not in LAN.
+    pub fn lan_to_fen(&mut self, lan: &str) -> PyResult<()> {
+        self.game.make_move_lan(lan).map_err(to_py_err)
+    }
+
+    /// Makes a move in SAN (e.g. "Nf3", "exd8=Q+" or "O-O") and updates the FEN state accordingly.
+    ///
+    /// Raises a `ValueError` if the move is not in SAN, not legal or ambiguous.
+    pub fn san_to_fen(&mut self, san: &str) -> PyResult<()> {
+        let move1: Move = self.game.fen().san_to_move(san).map_err(to_py_err)?;
+        self.game.make_move(move1);
+        Ok(())
+    }
+
+    /// Converts a legal move in LAN to SAN, e.g. "g1f3" to "Nf3".
+    ///
+    /// Raises a `ValueError` if the move is not in LAN or not legal.
+    pub fn lan_to_san(&self, lan: &str) -> PyResult<String> {
+        let fen: &Fen = self.game.fen();
+        let move1: Move = fen.parse_lan(lan).map_err(to_py_err)?;
+        if !fen.is_legal_move(&move1) {
+            return Err(PyValueError::new_err(format!("move '{}' is not legal in this position", lan)));
+        }
+
+        Ok(fen.move_to_san(&move1))
+    }
+
+    /// Returns the material in centipawns the legal move in LAN wins, or loses if negative, when both sides
+    /// keep capturing on its end square, e.g. to spot captures that hang a piece.
+    ///
+    /// Raises a `ValueError` if the move is not in LAN or not legal.
+    pub fn see(&self, lan: &str) -> PyResult<i32> {
+        let fen: &Fen = self.game.fen();
+        let move1: Move = fen.parse_lan(lan).map_err(to_py_err)?;
+        if !fen.is_legal_move(&move1) {
+            return Err(PyValueError::new_err(format!("move '{}' is not legal in this position", lan)));
+        }
+
+        Ok(fen.see(move1))
+    }
+
+    /// Converts a move in SAN to LAN, e.g. "Nf3" to "g1f3".
+    ///
+    /// Raises a `ValueError` if the move is not in SAN, not legal or ambiguous.
+    pub fn san_to_lan(&self, san: &str) -> PyResult<String> {
+        let move1: Move = self.game.fen().san_to_move(san).map_err(to_py_err)?;
+        Ok(move1.to_string())
+    }
+
+    /// Takes back the last move made with `lan_to_fen` or `san_to_fen`.
+    ///
+    /// Returns false if there is no move to take back.
+    pub fn undo(&mut self) -> bool {
+        self.game.undo().is_some()
+    }
+
+    /// Returns true if the current position has occurred at least three times.
+    pub fn is_threefold_repetition(&self) -> bool {
+        self.game.is_threefold_repetition()
+    }
+
+    /// Returns true if the current position has occurred at least five times.
+    pub fn is_fivefold_repetition(&self) -> bool {
+        self.game.is_fivefold_repetition()
     }
 
     /// Returns a list of all legal moves for the current player.
     pub fn get_all_possible_moves_lan(&mut self) -> Vec<String> {
-        self.fen.get_all_possible_moves_lan()
+        self.game.fen().get_all_possible_moves_lan()
     }
 
-    /// Checks if the game has ended, and returns the result.
+    /// Returns the result of the game, a `GameResult` whose outcome is `Outcome.Ongoing` while the game goes on.
     ///
-    /// Return values:
-    /// * "1-0" if white wins
-    /// * "0-1" if black wins
-    /// * "½-½" if draw
-    /// * "not ended" if the game is still ongoing
-    pub fn game_ended(&mut self) -> String {
-        self.fen.game_ended()
+    /// Checkmate, stalemate, the fifty and seventy-five move rules, threefold repetition,
+    /// insufficient material and dead positions are recognized.
+    pub fn result(&self) -> GameResultPy {
+        GameResultPy::from(self.game.result())
     }
 
     /// Returns true if it's white's turn to move, false if it's black's.
     pub fn white_to_move(&self) -> bool {
-        self.fen.white_to_move
+        self.game.fen().white_to_move
     }
 }
 
 
-#[pyclass]
-#[derive(Debug, Clone)]
-pub struct BotV1Py {
-    botv1: BotV1,
+/// Who won the game, mirrors `Outcome` from fenlib.
+#[pyclass(name = "Outcome", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OutcomePy {
+    WhiteWins,
+    BlackWins,
+    Draw,
+    Ongoing,
+}
+
+impl From<Outcome> for OutcomePy {
+    fn from(outcome: Outcome) -> Self {
+        match outcome {
+            Outcome::WhiteWins => OutcomePy::WhiteWins,
+            Outcome::BlackWins => OutcomePy::BlackWins,
+            Outcome::Draw => OutcomePy::Draw,
+            Outcome::Ongoing => OutcomePy::Ongoing,
+        }
+    }
+}
+
+/// Why the game ended, mirrors `Reason` from fenlib.
+#[pyclass(name = "Reason", eq, eq_int)]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ReasonPy {
+    Checkmate,
+    Stalemate,
+    FiftyMove,
+    SeventyFiveMove,
+    Repetition,
+    InsufficientMaterial,
+    DeadPosition,
+    Resignation,
+    Timeout,
+    Adjudication,
+}
+
+impl From<Reason> for ReasonPy {
+    fn from(reason: Reason) -> Self {
+        match reason {
+            Reason::Checkmate => ReasonPy::Checkmate,
+            Reason::Stalemate => ReasonPy::Stalemate,
+            Reason::FiftyMove => ReasonPy::FiftyMove,
+            Reason::SeventyFiveMove => ReasonPy::SeventyFiveMove,
+            Reason::Repetition => ReasonPy::Repetition,
+            Reason::InsufficientMaterial => ReasonPy::InsufficientMaterial,
+            Reason::DeadPosition => ReasonPy::DeadPosition,
+            Reason::Resignation => ReasonPy::Resignation,
+            Reason::Timeout => ReasonPy::Timeout,
+            Reason::Adjudication => ReasonPy::Adjudication,
+        }
+    }
+}
+
+/// The result of a game together with the reason it ended, the reason is None while the game is ongoing.
+#[pyclass(name = "GameResult")]
+#[derive(Debug, Clone, Copy)]
+pub struct GameResultPy {
+    result: GameResult,
+}
+
+impl From<GameResult> for GameResultPy {
+    fn from(result: GameResult) -> Self {
+        Self { result }
+    }
 }
 
 #[pymethods]
-impl BotV1Py {
+impl GameResultPy {
+    #[getter]
+    pub fn outcome(&self) -> OutcomePy {
+        OutcomePy::from(self.result.outcome)
+    }
+
+    #[getter]
+    pub fn reason(&self) -> Option<ReasonPy> {
+        self.result.reason.map(ReasonPy::from)
+    }
+
+    /// Returns true while the game has not ended.
+    pub fn is_ongoing(&self) -> bool {
+        self.result.is_ongoing()
+    }
+
+    /// Returns the result as written in PGN: "1-0", "0-1", "1/2-1/2" or "*".
+    pub fn pgn(&self) -> &'static str {
+        self.result.pgn()
+    }
+
+    /// Returns the result for people, e.g. "white wins by checkmate".
+    pub fn __str__(&self) -> String {
+        self.result.to_string()
+    }
+}
+
+
+/// A Python-exposed wrapper for any bot implementing the `Bot` trait from fenlib, e.g. Bot("botv1") or Bot("botv2").
+///
+/// The wrapper keeps track of the position of the bot, so moves can be passed in and out in LAN.
+#[pyclass(name = "Bot")]
+pub struct BotPy {
+    bot: Box<dyn Bot>,
+    fen: Fen,
+}
+
+#[pymethods]
+impl BotPy {
+    /// Creates the bot with the given name, starting from the given FEN string or the default setup.
+    ///
+    /// Raises a `ValueError` if there is no bot with that name or the FEN string is malformed.
     #[new]
-    pub fn new() -> Self {
-        let botv1: BotV1 = BotV1::new();
-        Self { botv1 }
+    #[pyo3(signature = (name="botv1", fen=None))]
+    pub fn new(name: &str, fen: Option<&str>) -> PyResult<Self> {
+        let mut bot: Box<dyn Bot> = bot_registry::bot_by_name(name).ok_or_else(|| {
+            PyValueError::new_err(format!("unknown bot '{}', expected one of {}", name, bot_registry::BOTS.join(", ")))
+        })?;
+
+        let fen: Fen = match fen {
+            Some(fen_str) => fen_str.parse().map_err(to_py_err)?,
+            None => Fen::new(),
+        };
+        bot.set_position(fen.clone());
+
+        Ok(Self { bot, fen })
     }
 
+    /// Returns the names of the bots that can be created.
     #[staticmethod]
-    pub fn from_fen(fen_str: &str) -> Self {
-        let botv1: BotV1 = BotV1::from_fen(fen_str);
-        Self { botv1 }
+    pub fn names() -> Vec<&'static str> {
+        bot_registry::BOTS.to_vec()
     }
 
-    pub fn get_move(&mut self) -> String {
-        self.botv1.get_move()
+    /// Returns the name the bot reports, e.g. "BotV1".
+    pub fn name(&self) -> String {
+        self.bot.name().to_string()
+    }
+
+    /// Returns the settings of the bot as a list of (name, default, min, max).
+    pub fn options(&self) -> Vec<(&'static str, i64, i64, i64)> {
+        self.bot.options().iter().map(|option: &BotOption| (option.name, option.default, option.min, option.max)).collect()
+    }
+
+    /// Changes a setting of the bot, raises a `ValueError` if the bot has no such setting or the value is out of range.
+    pub fn set_option(&mut self, name: &str, value: i64) -> PyResult<()> {
+        self.bot.set_option(name, value).map_err(|err: OptionError| PyValueError::new_err(err.to_string()))
+    }
+
+    /// Makes the bot forget what it remembers from earlier searches, e.g. its transposition table, for a new game.
+    pub fn new_game(&mut self) {
+        self.bot.new_game();
+    }
+
+    /// Replaces the position of the bot, raises a `ValueError` if the FEN string is malformed.
+    pub fn set_position(&mut self, fen_str: &str) -> PyResult<()> {
+        self.fen = fen_str.parse().map_err(to_py_err)?;
+        self.bot.set_position(self.fen.clone());
+        Ok(())
+    }
+
+    /// Plays a move in LAN in the position of the bot, raises a `ValueError` if the move is not legal.
+    pub fn apply_move(&mut self, lan: &str) -> PyResult<()> {
+        if !self.fen.is_legal_move_lan(lan) {
+            return Err(PyValueError::new_err(format!("move '{}' is not legal in this position", lan)));
+        }
+
+        let move1: Move = self.fen.parse_lan(lan).map_err(to_py_err)?;
+        self.fen.make_move(move1);
+        self.bot.apply_move(move1);
+        Ok(())
+    }
+
+    /// Searches for the best move in LAN without playing it, or returns None if there are no legal moves.
+    ///
+    /// The search ends at the first limit that is reached: `time_ms` for exactly that long, `depth`, `nodes`,
+    /// or the clocks `wtime_ms` / `btime_ms` with their increments and `movestogo`, which are spent by the time manager.
+    /// Without any limit the bot searches for `DEFAULT_MOVE_TIME_MS`.
+    #[pyo3(signature = (time_ms=None, depth=None, nodes=None, wtime_ms=None, btime_ms=None, winc_ms=0, binc_ms=0, movestogo=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn choose_move(
+        &mut self,
+        time_ms: Option<u64>,
+        depth: Option<u32>,
+        nodes: Option<u64>,
+        wtime_ms: Option<u64>,
+        btime_ms: Option<u64>,
+        winc_ms: u64,
+        binc_ms: u64,
+        movestogo: Option<u32>,
+    ) -> Option<String> {
+        let mut limits: SearchLimits = SearchLimits {
+            depth,
+            nodes,
+            movetime: time_ms.map(Duration::from_millis),
+            wtime: wtime_ms.map(Duration::from_millis),
+            btime: btime_ms.map(Duration::from_millis),
+            winc: Duration::from_millis(winc_ms),
+            binc: Duration::from_millis(binc_ms),
+            movestogo,
+            ..SearchLimits::default()
+        };
+        if !limits.is_limited() {
+            limits.movetime = Some(Duration::from_millis(DEFAULT_MOVE_TIME_MS));
+        }
+
+        self.bot.stop_signal().store(false, std::sync::atomic::Ordering::Relaxed);
+        let result: SearchResult = self.bot.choose_move(&limits, &mut |_: &SearchInfo| {});
+
+        (!result.best_move.is_null()).then(|| result.best_move.to_string())
+    }
+
+    /// Searches for the best move like `choose_move`, plays it and returns it in LAN.
+    ///
+    /// Raises a `ValueError` if there are no legal moves.
+    #[pyo3(signature = (time_ms=None, depth=None, nodes=None, wtime_ms=None, btime_ms=None, winc_ms=0, binc_ms=0, movestogo=None))]
+    #[allow(clippy::too_many_arguments)]
+    pub fn get_move(
+        &mut self,
+        time_ms: Option<u64>,
+        depth: Option<u32>,
+        nodes: Option<u64>,
+        wtime_ms: Option<u64>,
+        btime_ms: Option<u64>,
+        winc_ms: u64,
+        binc_ms: u64,
+        movestogo: Option<u32>,
+    ) -> PyResult<String> {
+        let lan: String = self
+            .choose_move(time_ms, depth, nodes, wtime_ms, btime_ms, winc_ms, binc_ms, movestogo)
+            .ok_or_else(|| PyValueError::new_err("there are no legal moves"))?;
+        self.apply_move(&lan)?;
+        Ok(lan)
     }
 
-    pub fn receive_move(&mut self, lan: &str) {
-        self.botv1.receive_move(lan);
+    /// Plays the move of the opponent in LAN, see `apply_move`.
+    pub fn receive_move(&mut self, lan: &str) -> PyResult<()> {
+        self.apply_move(lan)
     }
 }
 
@@ -131,6 +473,9 @@ impl BotV1Py {
 #[pymodule]
 fn rust_utils(m: &Bound<'_, PyModule>) -> PyResult<()> {
     m.add_class::<FenPy>()?;
-    m.add_class::<BotV1Py>()?;
+    m.add_class::<OutcomePy>()?;
+    m.add_class::<ReasonPy>()?;
+    m.add_class::<GameResultPy>()?;
+    m.add_class::<BotPy>()?;
     Ok(())
 }